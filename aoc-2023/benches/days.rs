@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use aoc_core::input::read_input;
+use aoc_core::{Puzzle, Solution};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day_01_trebuchet::Trebuchet;
+use day_02_cube_conundrum::CubeConundrum;
+use day_03_gear_ratios::GearRatios;
+use day_04_scratchcards::Scratchcards;
+use day_05_fertilizer::Fertilizer;
+use day_06_boat_racing::BoatRacing;
+use day_07_camel_cards::CamelCards;
+use day_08_wasteland::Wasteland;
+use day_09_oasis::Oasis;
+use day_10_maze::Maze;
+use day_11_cosmic_expansion::CosmicExpansion;
+use day_12_hot_springs::HotSprings;
+use day_13_mirrors::Mirrors;
+use day_14_reflector::Reflector;
+use day_15_hash::Hash;
+use day_16_floor_is_lava::FloorIsLava;
+use day_17_heat_loss::HeatLoss;
+use day_18_lagoon::Lagoon;
+use day_19_rules::Rules;
+use day_20_pulse_propagation::PulsePropagation;
+use day_21_walking::Walking;
+use day_22_tetris::Tetris;
+use day_23_slopes::Slopes;
+use day_24_hail::Hail;
+use day_25_wires::Wires;
+
+// Parsing and every part are benchmarked separately on the day's `input.txt`,
+// days without an input file are skipped.
+fn bench_day<P: Puzzle + Sync>(c: &mut Criterion, puzzle: P) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(puzzle.directory())
+        .join("input.txt");
+    let Ok(contents) = read_input(path) else {
+        return;
+    };
+    let input = P::parse(&contents).unwrap();
+
+    let mut group = c.benchmark_group(puzzle.directory());
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| P::parse(black_box(&contents))));
+    group.bench_function("part 1", |b| b.iter(|| P::part_one(black_box(&input))));
+    if P::part_two(&input).is_some() {
+        group.bench_function("part 2", |b| b.iter(|| P::part_two(black_box(&input))));
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day(c, Trebuchet);
+    bench_day(c, CubeConundrum);
+    bench_day(c, GearRatios);
+    bench_day(c, Scratchcards);
+    bench_day(c, Fertilizer);
+    bench_day(c, BoatRacing);
+    bench_day(c, CamelCards);
+    bench_day(c, Wasteland);
+    bench_day(c, Oasis);
+    bench_day(c, Maze);
+    bench_day(c, CosmicExpansion);
+    bench_day(c, HotSprings);
+    bench_day(c, Mirrors);
+    bench_day(c, Reflector);
+    bench_day(c, Hash);
+    bench_day(c, FloorIsLava);
+    bench_day(c, HeatLoss);
+    bench_day(c, Lagoon);
+    bench_day(c, Rules);
+    bench_day(c, PulsePropagation);
+    bench_day(c, Walking);
+    bench_day(c, Tetris);
+    bench_day(c, Slopes);
+    bench_day(c, Hail);
+    bench_day(c, Wires);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
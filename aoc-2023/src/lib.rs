@@ -0,0 +1,89 @@
+//! The 2023 puzzles, registered with the runner as one [`Calendar`]
+
+use std::path::PathBuf;
+
+use aoc_core::{Calendar, Solution};
+use day_01_trebuchet::Trebuchet;
+use day_02_cube_conundrum::CubeConundrum;
+use day_03_gear_ratios::GearRatios;
+use day_04_scratchcards::Scratchcards;
+use day_05_fertilizer::Fertilizer;
+use day_06_boat_racing::BoatRacing;
+use day_07_camel_cards::CamelCards;
+use day_08_wasteland::Wasteland;
+use day_09_oasis::Oasis;
+use day_10_maze::Maze;
+use day_11_cosmic_expansion::CosmicExpansion;
+use day_12_hot_springs::HotSprings;
+use day_13_mirrors::Mirrors;
+use day_14_reflector::Reflector;
+use day_15_hash::Hash;
+use day_16_floor_is_lava::FloorIsLava;
+use day_17_heat_loss::HeatLoss;
+use day_18_lagoon::Lagoon;
+use day_19_rules::Rules;
+use day_20_pulse_propagation::PulsePropagation;
+use day_21_walking::Walking;
+use day_22_tetris::Tetris;
+use day_23_slopes::Slopes;
+use day_24_hail::Hail;
+use day_25_wires::Wires;
+
+pub struct Advent2023;
+
+impl Calendar for Advent2023 {
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn solutions(&self) -> Vec<&'static dyn Solution> {
+        vec![
+            &Trebuchet,
+            &CubeConundrum,
+            &GearRatios,
+            &Scratchcards,
+            &Fertilizer,
+            &BoatRacing,
+            &CamelCards,
+            &Wasteland,
+            &Oasis,
+            &Maze,
+            &CosmicExpansion,
+            &HotSprings,
+            &Mirrors,
+            &Reflector,
+            &Hash,
+            &FloorIsLava,
+            &HeatLoss,
+            &Lagoon,
+            &Rules,
+            &PulsePropagation,
+            &Walking,
+            &Tetris,
+            &Slopes,
+            &Hail,
+            &Wires,
+        ]
+    }
+
+    // the 2023 days were written before there were other years, so their
+    // directories stay at the repository root
+    fn directory(&self) -> PathBuf {
+        PathBuf::from(".")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_registers_every_day_once() {
+        let days: Vec<u8> = Advent2023
+            .solutions()
+            .iter()
+            .map(|solution| solution.day())
+            .collect();
+        assert_eq!(days, (1..=25).collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,121 @@
+use aoc_core::Puzzle;
+use day_05_fertilizer::fertilizer::{
+    parse_map, plant_location, plant_ranged_location, MapRange, PlantMap,
+};
+use day_07_camel_cards::cards::{parse_bet, winnings, Bets, Hand, Strength};
+use day_09_oasis::history::Report;
+use day_09_oasis::Oasis;
+use day_18_lagoon::digger::{parse_plans, volume, volume_hex, Digger, Direction};
+use day_19_rules::processor::{parse_system, sum_parts, sum_ranges};
+use day_20_pulse_propagation::pulser::{count_pulses, parse_modules, ModuleType};
+use day_22_tetris::layering::{count_chain, count_redundant, parse};
+
+#[test]
+fn it_ranks_hands() {
+    assert_eq!(
+        Hand::new("QQQJA").unwrap().strength(),
+        Strength::ThreeOfKind
+    );
+    assert_eq!(
+        Hand::new_with_joker("QQQJA").unwrap().strength(),
+        Strength::FourOfKind
+    );
+    assert!(Hand::new("33332").unwrap() > Hand::new("2AAAA").unwrap());
+    assert!(Hand::new_with_joker("JKKK2").unwrap() < Hand::new_with_joker("QQQQ2").unwrap());
+
+    let bets = include_str!("../../day-07-camel-cards/sample.txt")
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_bet(index, line))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(bets[0].cards(), "32T3K");
+    assert_eq!(bets[0].bid(), 765);
+    assert_eq!(bets[0].hand(false).strength(), Strength::OnePair);
+    assert_eq!(winnings(&bets, false).iter().sum::<usize>(), 6440);
+    assert_eq!(winnings(&bets, true).iter().sum::<usize>(), 5905);
+}
+
+#[test]
+fn it_follows_the_almanac() {
+    let plant_map = parse_map(include_str!("../../day-05-fertilizer/sample.txt")).unwrap();
+    assert_eq!(plant_map.seeds, [79, 14, 55, 13]);
+    assert_eq!(plant_map.maps.len(), 7);
+    assert_eq!(plant_location(&plant_map), Ok(35));
+    assert_eq!(plant_ranged_location(&plant_map), Ok(46));
+}
+
+#[test]
+fn it_digs_instructions() {
+    let plans = parse_plans(include_str!("../../day-18-lagoon/sample.txt")).unwrap();
+    assert_eq!(plans.plan()[0].direction(), Direction::Right);
+    assert_eq!(plans.plan()[0].length(), 6);
+    assert_eq!(plans.hex_plan()[0].length(), 461937);
+
+    let mut digger = Digger::default();
+    for instruction in plans.plan() {
+        digger.dig(instruction);
+    }
+    assert_eq!(digger.volume(), 62);
+    assert_eq!(volume(&plans), 62);
+    assert_eq!(volume_hex(&plans), 952408144115);
+}
+
+#[test]
+fn it_sorts_parts_with_flows() {
+    let system = parse_system(include_str!("../../day-19-rules/sample.txt")).unwrap();
+    let flow = system.flow("in").unwrap();
+    let part = &system.parts()[0];
+    assert_eq!((part.x, part.m, part.a, part.s), (787, 2655, 1222, 2876));
+    assert_eq!(flow.next(part), "qqz");
+    assert_eq!(flow.fallback(), "qqz");
+    assert!(system.accepts(part));
+    assert!(!system.accepts(&system.parts()[1]));
+    assert_eq!(sum_parts(&system), 19114);
+    assert_eq!(sum_ranges(&system), 167409079868000);
+}
+
+#[test]
+fn it_wires_modules() {
+    let modules = parse_modules(include_str!("../../day-20-pulse-propagation/sample.txt")).unwrap();
+    let inverter = &modules["inv"];
+    assert_eq!(inverter.name(), "inv");
+    assert_eq!(inverter.module_type(), &ModuleType::Conjunction);
+    assert_eq!(inverter.destinations(), ["a"]);
+    assert_eq!(inverter.sources(), ["c"]);
+    assert_eq!(count_pulses(&modules, 1000), 32000000);
+}
+
+#[test]
+fn it_settles_bricks() {
+    let bricks = parse(include_str!("../../day-22-tetris/sample.txt")).unwrap();
+    let top = bricks
+        .iter()
+        .find(|brick| brick.name() == "1,1,8~1,1,9")
+        .unwrap();
+    assert_eq!((top.start(), top.end()), ((1, 1, 5), (1, 1, 6)));
+    assert!(top.supports().is_empty());
+    assert_eq!(top.supported_by().len(), 1);
+    assert_eq!(count_redundant(&bricks), 5);
+    assert_eq!(count_chain(&bricks), 7);
+}
+
+#[test]
+fn it_solves_inputs_built_in_code() {
+    let history = Report(vec![vec![10, 13, 16, 21, 30, 45]]);
+    assert_eq!(Oasis::part_one(&history).unwrap().to_string(), "68");
+
+    let plant_map = PlantMap {
+        seeds: vec![5, 20],
+        maps: vec![vec![MapRange {
+            destination_start: 100,
+            source_start: 0,
+            range: 10,
+        }]],
+    };
+    assert_eq!(plant_location(&plant_map), Ok(20));
+
+    let bets: Bets = "32T3K 765\nT55J5 684".parse().unwrap();
+    assert_eq!(bets.0.len(), 2);
+    assert!("32T3K".parse::<Bets>().is_err());
+}
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use crate::solution::Solution;
+
+/// The days of one year's puzzles, each year plugs into the runner as one
+pub trait Calendar: Sync {
+    fn year(&self) -> u16;
+
+    /// Every day of the year, in day order
+    fn solutions(&self) -> Vec<&'static dyn Solution>;
+
+    /// Directory holding the year's day directories, relative to the
+    /// repository root
+    fn directory(&self) -> PathBuf {
+        PathBuf::from(self.year().to_string())
+    }
+
+    fn find(&self, day: u8) -> Option<&'static dyn Solution> {
+        self.solutions()
+            .into_iter()
+            .find(|solution| solution.day() == day)
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+use std::str::FromStr;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl Span {
+    pub fn new(line: usize, column: usize, text: &str) -> Self {
+        Self {
+            line,
+            column,
+            text: text.to_string(),
+        }
+    }
+
+    // `index` is the zero based line index and `fragment` must be a slice of `line`
+    pub fn at(index: usize, line: &str, fragment: &str) -> Self {
+        let offset = (fragment.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        Self::new(index + 1, offset.min(line.len()) + 1, fragment)
+    }
+
+    pub fn line(index: usize, line: &str) -> Self {
+        Self::at(index, line, line)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "at line {}, column {}: `{}`",
+            self.line, self.column, self.text
+        )
+    }
+}
+
+pub fn parse_at<T: FromStr>(index: usize, line: &str, fragment: &str) -> Result<T, Span> {
+    let fragment = fragment.trim();
+    fragment
+        .parse::<T>()
+        .map_err(|_| Span::at(index, line, fragment))
+}
+
+// Span of the first character in `line` that isn't accepted by `valid`
+pub fn find_invalid_char(index: usize, line: &str, valid: impl Fn(char) -> bool) -> Option<Span> {
+    line.char_indices()
+        .find(|&(_, c)| !valid(c))
+        .map(|(position, c)| Span::at(index, line, &line[position..position + c.len_utf8()]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_locates_fragments() {
+        let line = "R 6 (#70c710)";
+        assert_eq!(Span::at(2, line, &line[4..]), Span::new(3, 5, "(#70c710)"));
+        assert_eq!(Span::line(0, line), Span::new(1, 1, line));
+        assert_eq!(Span::at(0, line, "elsewhere").column, line.len() + 1);
+    }
+
+    #[test]
+    fn it_parses_numbers_at_position() {
+        let line = "Game 12: 3 blue";
+        assert_eq!(parse_at::<usize>(0, line, &line[5..7]), Ok(12));
+        assert_eq!(
+            parse_at::<usize>(0, line, &line[11..]),
+            Err(Span::new(1, 12, "blue"))
+        );
+    }
+
+    #[test]
+    fn it_finds_invalid_chars() {
+        assert_eq!(find_invalid_char(0, "#..#", |c| ".#".contains(c)), None);
+        assert_eq!(
+            find_invalid_char(1, "#.é#", |c| ".#".contains(c)),
+            Some(Span::new(2, 3, "é"))
+        );
+    }
+
+    #[test]
+    fn it_displays_position() {
+        assert_eq!(
+            Span::new(3, 1, "?ab -> c").to_string(),
+            "at line 3, column 1: `?ab -> c`"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// Shared pieces of the per-day puzzle input generators
+use std::collections::{BTreeMap, BTreeSet};
+
+pub use rand::seq::SliceRandom;
+pub use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+// Seeded so a generated input can be reproduced from its seed alone
+pub type InputRng = ChaCha8Rng;
+
+pub fn seeded(seed: u64) -> InputRng {
+    InputRng::seed_from_u64(seed)
+}
+
+// Lowercase ascii word of `length` letters
+pub fn word(rng: &mut InputRng, length: usize) -> String {
+    (0..length).map(|_| rng.gen_range('a'..='z')).collect()
+}
+
+// Rows of `width` tiles picked by `tile`, joined by newlines
+pub fn grid(
+    rng: &mut InputRng,
+    width: usize,
+    height: usize,
+    mut tile: impl FnMut(&mut InputRng) -> char,
+) -> String {
+    (0..height)
+        .map(|_| (0..width).map(|_| tile(rng)).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+type Tile = (usize, usize);
+
+fn edge(a: Tile, b: Tile) -> (Tile, Tile) {
+    (a.min(b), a.max(b))
+}
+
+// Tiles of a random simple loop within a `size` by `size` square, in walking
+// order. The square is cut into three by three blocks, every picked block
+// holds a ring around its centre and neighbouring rings of a spanning tree
+// are joined by swapping a pair of parallel edges
+pub fn closed_loop(rng: &mut InputRng, size: usize) -> Vec<Tile> {
+    let blocks = (size / 3).max(1);
+    let first = (rng.gen_range(0..blocks), rng.gen_range(0..blocks));
+    let wanted = rng.gen_range(1..=blocks * blocks);
+
+    let mut picked = vec![first];
+    let mut joins: Vec<(Tile, Tile)> = vec![];
+    let mut frontier: Vec<(Tile, Tile)> = vec![];
+    let neighbours = |(row, col): Tile| {
+        [(0, 1), (2, 1), (1, 0), (1, 2)]
+            .into_iter()
+            .map(move |(dr, dc)| (row + dr, col + dc))
+            .filter(|&(row, col)| row >= 1 && col >= 1 && row <= blocks && col <= blocks)
+            .map(|(row, col)| (row - 1, col - 1))
+    };
+    frontier.extend(neighbours(first).map(|next| (first, next)));
+    while picked.len() < wanted && !frontier.is_empty() {
+        let (from, to) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if picked.contains(&to) {
+            continue;
+        }
+        picked.push(to);
+        joins.push((from, to));
+        frontier.extend(neighbours(to).map(|next| (to, next)));
+    }
+
+    // ordered so that the walk is the same for a seed
+    let mut links: BTreeSet<(Tile, Tile)> = BTreeSet::new();
+    let ring = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (2, 1),
+        (2, 0),
+        (1, 0),
+    ];
+    for (row, col) in &picked {
+        for (index, (dr, dc)) in ring.iter().enumerate() {
+            let (nr, nc) = ring[(index + 1) % ring.len()];
+            links.insert(edge(
+                (row * 3 + dr, col * 3 + dc),
+                (row * 3 + nr, col * 3 + nc),
+            ));
+        }
+    }
+    for (a, b) in joins {
+        let ((top, left), (bottom, right)) = (a.min(b), a.max(b));
+        let (row, col) = (top * 3, left * 3);
+        // upper edges of touching sides, or left edges of touching bottom
+        // and top, are replaced by the two edges across
+        let [(outer, inner), (other, other_inner)] = match top == bottom {
+            true => [
+                ((row, col + 2), (row + 1, col + 2)),
+                ((row, right * 3), (row + 1, right * 3)),
+            ],
+            false => [
+                ((row + 2, col), (row + 2, col + 1)),
+                ((bottom * 3, col), (bottom * 3, col + 1)),
+            ],
+        };
+        links.remove(&edge(outer, inner));
+        links.remove(&edge(other, other_inner));
+        links.insert(edge(outer, other));
+        links.insert(edge(inner, other_inner));
+    }
+
+    let mut next: BTreeMap<Tile, Vec<Tile>> = BTreeMap::new();
+    for (a, b) in links {
+        next.entry(a).or_default().push(b);
+        next.entry(b).or_default().push(a);
+    }
+    let start = (first.0 * 3, first.1 * 3);
+    let mut path = vec![start, next[&start][0]];
+    loop {
+        let (previous, current) = (path[path.len() - 2], path[path.len() - 1]);
+        let step = next[&current]
+            .iter()
+            .copied()
+            .find(|tile| *tile != previous)
+            .unwrap();
+        if step == start {
+            return path;
+        }
+        path.push(step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn it_repeats_seeded_inputs() {
+        let generate = |seed| {
+            let mut rng = seeded(seed);
+            grid(&mut rng, 3, 2, |rng| *b".#".choose(rng).unwrap() as char)
+        };
+        assert_eq!(generate(7), generate(7));
+        assert_ne!(generate(7), generate(8));
+        assert_eq!(generate(7).len(), 7);
+        assert_eq!(word(&mut seeded(1), 4).len(), 4);
+    }
+
+    #[test]
+    fn it_walks_simple_loops() {
+        for seed in 0..20 {
+            let tiles = closed_loop(&mut seeded(seed), 15);
+            let unique: HashSet<&Tile> = tiles.iter().collect();
+            assert_eq!(unique.len(), tiles.len());
+            assert!(tiles.len() >= 8);
+            for (index, (row, col)) in tiles.iter().enumerate() {
+                let (next_row, next_col) = tiles[(index + 1) % tiles.len()];
+                assert_eq!(row.abs_diff(next_row) + col.abs_diff(next_col), 1);
+                assert!(*row < 15 && *col < 15);
+            }
+        }
+    }
+}
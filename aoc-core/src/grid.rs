@@ -0,0 +1,420 @@
+use std::ops::{Index, IndexMut};
+
+use thiserror::Error;
+
+use crate::error::{find_invalid_char, Span};
+use crate::input::lines;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum GridError {
+    #[error("grid has no tiles")]
+    Empty,
+    #[error("unknown tile {0}")]
+    UnknownTile(Span),
+    #[error("row length differs from the first row {0}")]
+    RaggedRow(Span),
+}
+
+// Checks that `input` is a non empty rectangle made of `valid` tiles,
+// `first_line` is the zero based index of its first row in the whole input
+pub fn check_grid(
+    input: &str,
+    first_line: usize,
+    valid: impl Fn(char) -> bool,
+) -> Result<(), GridError> {
+    let mut width: Option<usize> = None;
+    for (index, row) in lines(input).enumerate() {
+        let index = first_line + index;
+        if let Some(span) = find_invalid_char(index, row, &valid) {
+            return Err(GridError::UnknownTile(span));
+        }
+        match width {
+            None => width = Some(row.len()),
+            Some(width) if width != row.len() => {
+                return Err(GridError::RaggedRow(Span::line(index, row)))
+            }
+            Some(_) => {}
+        }
+    }
+
+    match width {
+        Some(0) | None => Err(GridError::Empty),
+        Some(_) => Ok(()),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn reverse(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    // Row and column change of a single step
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::East => (0, 1),
+            Direction::South => (1, 0),
+            Direction::West => (0, -1),
+        }
+    }
+
+    pub fn arrow(&self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.arrow() == c)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+// Rectangular grid stored row by row, every access by `Point` is bounds checked
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut tile: impl FnMut(Point) -> T) -> Self {
+        let tiles = (0..height)
+            .flat_map(|row| (0..width).map(move |col| Point::new(row, col)))
+            .map(&mut tile)
+            .collect();
+        Self {
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    // Builds a grid out of rows, all of them must have the same non zero length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map(Vec::len).filter(|width| *width > 0)?;
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let height = rows.len();
+
+        Some(Self {
+            width,
+            height,
+            tiles: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // Parses `input` with `tile` mapping each character, see `check_grid` for `first_line`
+    pub fn parse(
+        input: &str,
+        first_line: usize,
+        tile: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        check_grid(input, first_line, |c| tile(c).is_some())?;
+        let rows = lines(input)
+            .map(|row| row.chars().filter_map(&tile).collect())
+            .collect();
+
+        Self::from_rows(rows).ok_or(GridError::Empty)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.row < self.height && point.col < self.width
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if !self.contains(point) {
+            return None;
+        }
+        self.tiles.get(point.row * self.width + point.col)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if !self.contains(point) {
+            return None;
+        }
+        self.tiles.get_mut(point.row * self.width + point.col)
+    }
+
+    // Neighbouring point in `direction`, `None` when it falls off the grid
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        let (row, col) = direction.offset();
+        let next = Point::new(
+            point.row.checked_add_signed(row)?,
+            point.col.checked_add_signed(col)?,
+        );
+        self.contains(next).then_some(next)
+    }
+
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = (Direction, Point)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| Some((direction, self.step(point, direction)?)))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Point::new(row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.tiles.iter())
+    }
+
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, tile)| predicate(tile))
+            .map(|(point, _)| point)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.tiles[row * self.width..(row + 1) * self.width]
+    }
+
+    // One slice per row, empty ones when the grid has no columns
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.rows().map(move |row| &row[col])
+    }
+
+    pub fn map<U>(&self, tile: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.iter().map(tile).collect(),
+        }
+    }
+
+    // Renders the grid back to text, one line per row without a trailing newline
+    pub fn render(&self, glyph: impl Fn(Point, &T) -> char) -> String {
+        self.rows()
+            .enumerate()
+            .map(|(row, tiles)| {
+                tiles
+                    .iter()
+                    .enumerate()
+                    .map(|(col, tile)| glyph(Point::new(row, col), tile))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, tile: T) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![tile; width * height],
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |point| {
+            self[Point::new(point.col, point.row)].clone()
+        })
+    }
+
+    // Mirrors the grid left to right
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width, self.height, |point| {
+            self[Point::new(point.row, self.width - 1 - point.col)].clone()
+        })
+    }
+
+    // Mirrors the grid top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |point| {
+            self[Point::new(self.height - 1 - point.row, point.col)].clone()
+        })
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+}
+
+impl Grid<char> {
+    pub fn to_text(&self) -> String {
+        self.render(|_, tile| *tile)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside of the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, 0, Some).unwrap()
+    }
+
+    #[test]
+    fn it_checks_grids() {
+        let valid = |c| ".#".contains(c);
+        assert_eq!(check_grid("#.\n.#", 0, valid), Ok(()));
+        assert_eq!(check_grid("", 0, valid), Err(GridError::Empty));
+        assert_eq!(
+            check_grid("#.\n.O", 3, valid),
+            Err(GridError::UnknownTile(Span::new(5, 2, "O")))
+        );
+        assert_eq!(
+            check_grid("#.\n.#.", 0, valid),
+            Err(GridError::RaggedRow(Span::new(2, 1, ".#.")))
+        );
+    }
+
+    #[test]
+    fn it_parses_and_renders() {
+        let tile = |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        };
+        let walls = Grid::parse("#..\n.#.", 0, tile).unwrap();
+        assert_eq!((walls.width(), walls.height()), (3, 2));
+        assert!(walls[Point::new(1, 1)]);
+        assert_eq!(walls.get(Point::new(2, 0)), None);
+        assert_eq!(
+            walls.render(|_, wall| if *wall { '#' } else { '.' }),
+            "#..\n.#."
+        );
+        assert_eq!(
+            Grid::parse("#.\n.x", 2, tile),
+            Err(GridError::UnknownTile(Span::new(4, 2, "x")))
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![2, 3]]), None);
+    }
+
+    #[test]
+    fn it_steps_within_bounds() {
+        let grid = grid("ab\ncd");
+        let corner = Point::new(0, 0);
+        assert_eq!(grid.step(corner, Direction::North), None);
+        assert_eq!(grid.step(corner, Direction::East), Some(Point::new(0, 1)));
+        assert_eq!(
+            grid.neighbours(corner).collect::<Vec<_>>(),
+            vec![
+                (Direction::East, Point::new(0, 1)),
+                (Direction::South, Point::new(1, 0))
+            ]
+        );
+        assert_eq!(grid.find(|c| *c == 'c'), Some(Point::new(1, 0)));
+        assert_eq!(grid.column(1).collect::<String>(), "bd");
+    }
+
+    #[test]
+    fn it_turns_directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(Direction::from_arrow(direction.arrow()), Some(direction));
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+    }
+
+    #[test]
+    fn it_rotates_and_flips() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.transpose().to_text(), "ad\nbe\ncf");
+        assert_eq!(grid.flip_horizontal().to_text(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_text(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().to_text(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_text(), "cf\nbe\nad");
+    }
+
+    #[test]
+    fn it_walks_rows_without_columns() {
+        let grid = Grid::filled(0, 2, 'x');
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&[] as &[char]; 2]);
+        assert_eq!(grid.render(|_, tile| *tile), "\n");
+        assert_eq!(grid.transpose().rows().count(), 0);
+    }
+}
@@ -0,0 +1,200 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::error::Error;
+
+// Input path that reads standard input instead of a file
+pub const STDIN: &str = "-";
+
+pub fn open_input<P: AsRef<Path>>(file_path: P) -> io::Result<Box<dyn BufRead>> {
+    let file_path = file_path.as_ref();
+    if file_path == Path::new(STDIN) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    Ok(Box::new(BufReader::new(File::open(file_path)?)))
+}
+
+pub fn read_input<P: AsRef<Path>>(file_path: P) -> io::Result<String> {
+    let mut contents = String::new();
+    open_input(file_path)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+pub fn input_path_from_args() -> Option<String> {
+    env::args().nth(1)
+}
+
+const BOM: &str = "\u{feff}";
+
+// Input without its byte order mark and the line breaks it ends with
+pub fn trim_input(input: &str) -> &str {
+    input
+        .strip_prefix(BOM)
+        .unwrap_or(input)
+        .trim_end_matches(['\r', '\n'])
+}
+
+// Lines ending in `\n` or `\r\n`, an empty input is still one empty line
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    trim_input(input)
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+// Blocks of lines separated by blank lines, with the zero based index of
+// their first line in the input. Blocks keep their line breaks, so they are
+// read with `lines` again.
+pub fn paragraphs(input: &str) -> Vec<(usize, &str)> {
+    let input = trim_input(input);
+    let mut paragraphs = vec![];
+    // first line and byte offset of the block being read
+    let mut block: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (index, line) in input.split('\n').enumerate() {
+        match (line.trim_end_matches('\r').is_empty(), block) {
+            (true, Some((first_line, start))) => {
+                paragraphs.push((first_line, input[start..offset - 1].trim_end_matches('\r')));
+                block = None;
+            }
+            (false, None) => block = Some((index, offset)),
+            _ => {}
+        }
+        offset += line.len() + 1;
+    }
+    if let Some((first_line, start)) = block {
+        paragraphs.push((first_line, &input[start..]));
+    }
+
+    paragraphs
+}
+
+// Calls `each` with the index and text of every record ending in
+// `separator`, without holding more than one record in memory. Records are
+// the ones `trim_input(input).split(separator)` would give, with a line's
+// `\r` left out when records are lines.
+pub fn read_records(
+    input: &mut dyn BufRead,
+    separator: u8,
+    mut each: impl FnMut(usize, &str) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut buffer = vec![];
+    // blank lines are only passed on once a line follows them
+    let mut blank_lines = 0;
+    for index in 0.. {
+        buffer.clear();
+        input.read_until(separator, &mut buffer)?;
+        let last = buffer.last() != Some(&separator);
+        let mut record = match last {
+            true => &buffer[..],
+            false => &buffer[..buffer.len() - 1],
+        };
+        if index == 0 {
+            record = record.strip_prefix(BOM.as_bytes()).unwrap_or(record);
+        }
+        if last {
+            while let [rest @ .., b'\r' | b'\n'] = record {
+                record = rest;
+            }
+        }
+        if separator == b'\n' {
+            record = record.strip_suffix(b"\r").unwrap_or(record);
+            if record.is_empty() {
+                blank_lines += 1;
+                if last {
+                    // only blank lines are one empty line, as with `lines`
+                    if blank_lines == index + 1 {
+                        each(0, "")?;
+                    }
+                    break;
+                }
+                continue;
+            }
+            for blank in index - blank_lines..index {
+                each(blank, "")?;
+            }
+            blank_lines = 0;
+        }
+        each(index, std::str::from_utf8(record)?)?;
+        if last {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(input: &str, separator: u8) -> Vec<(usize, String)> {
+        let mut records = vec![];
+        read_records(&mut input.as_bytes(), separator, |index, record| {
+            records.push((index, record.to_string()));
+            Ok(())
+        })
+        .unwrap();
+        records
+    }
+
+    #[test]
+    fn it_reads_lines() {
+        assert_eq!(lines("a\nb").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(
+            lines("\u{feff}a\r\n\r\nb\r\n\n").collect::<Vec<_>>(),
+            ["a", "", "b"]
+        );
+        assert_eq!(lines("").collect::<Vec<_>>(), [""]);
+        assert_eq!(lines("\r\n").collect::<Vec<_>>(), [""]);
+        assert_eq!(trim_input("\u{feff}rn=1,cm-\r\n"), "rn=1,cm-");
+    }
+
+    #[test]
+    fn it_splits_paragraphs() {
+        assert_eq!(paragraphs("a\nb\n\nc\n"), [(0, "a\nb"), (3, "c")]);
+        assert_eq!(
+            paragraphs("\r\na\r\n\r\n\r\nb\r\nc\r\n"),
+            [(1, "a"), (4, "b\r\nc")]
+        );
+        assert_eq!(paragraphs(""), []);
+    }
+
+    #[test]
+    fn it_reads_records_like_lines() {
+        for input in [
+            "",
+            "a",
+            "a\nbc",
+            "a\n\nbc\n",
+            "\n\n",
+            "\u{feff}a\r\n\r\nbc\r\n\r\n",
+        ] {
+            let lines: Vec<(usize, String)> = lines(input).map(String::from).enumerate().collect();
+            assert_eq!(records(input, b'\n'), lines, "{input:?}");
+        }
+        assert_eq!(
+            records("rn=1,cm-\r\n", b','),
+            [(0, "rn=1".to_string()), (1, "cm-".to_string())]
+        );
+        assert_eq!(
+            records("rn=1,\n", b','),
+            [(0, "rn=1".to_string()), (1, "".to_string())]
+        );
+    }
+
+    #[test]
+    fn it_stops_at_the_first_error() {
+        let mut seen = 0;
+        let result = read_records(&mut "1\nx\n3".as_bytes(), b'\n', |_, record| {
+            seen += 1;
+            record.parse::<u32>()?;
+            Ok(())
+        });
+        assert!(result.is_err());
+        assert_eq!(seen, 2);
+        assert!(read_records(&mut &b"\xff"[..], b'\n', |_, _| Ok(())).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+pub mod calendar;
+pub mod error;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod numbers;
+pub mod parallel;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod search;
+pub mod solution;
+pub mod visualize;
+
+pub use calendar::Calendar;
+pub use error::{Error, Span};
+pub use solution::{run, Answer, Part, Puzzle, Solution, Stopwatch, Streamed, Timed};
@@ -0,0 +1,31 @@
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    pub static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
+}
+
+// On failure returns the number that doesn't fit into `T`, as a slice of `text`
+pub fn parse_numbers<T: FromStr>(text: &str) -> Result<Vec<T>, &str> {
+    NUM_RE
+        .find_iter(text)
+        .map(|x| x.as_str().parse::<T>().map_err(|_| x.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_numbers() {
+        assert_eq!(
+            parse_numbers::<u32>("Card 1: 41 48 | 83  6"),
+            Ok(vec![1, 41, 48, 83, 6])
+        );
+        assert_eq!(parse_numbers::<u64>("no numbers"), Ok(vec![]));
+        assert_eq!(parse_numbers::<u8>("1 256 3"), Err("256"));
+    }
+}
@@ -0,0 +1,48 @@
+// Maps every item in order, on rayon's thread pool when built with the
+// `parallel` feature, so results are the same either way
+#[cfg(feature = "parallel")]
+pub fn map<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync + Send) -> Vec<U> {
+    use rayon::prelude::*;
+
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map<T, U>(items: &[T], f: impl Fn(&T) -> U) -> Vec<U> {
+    items.iter().map(f).collect()
+}
+
+// Number of threads `map` uses, can only be set once and before any `map`
+#[cfg(feature = "parallel")]
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|error| format!("can't start {threads} threads: {error}"))
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    match threads {
+        1 => Ok(()),
+        _ => Err(
+            "more threads need the `parallel` feature, rebuild with `--features parallel`"
+                .to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_maps_in_order() {
+        let squares = map(&(0..1000).collect::<Vec<u64>>(), |n| n * n);
+        assert_eq!(squares.len(), 1000);
+        assert!(squares
+            .iter()
+            .enumerate()
+            .all(|(n, square)| *square == (n * n) as u64));
+    }
+}
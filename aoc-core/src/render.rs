@@ -0,0 +1,342 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use tiny_skia::{FillRule, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+#[derive(Debug, Error)]
+pub enum RenderError {
+    #[error("unsupported image format `{0}`, expected `svg` or `png`")]
+    UnsupportedFormat(String),
+    #[error("image of {0}x{1} pixels is too large")]
+    TooLarge(u32, u32),
+    #[error("can't encode png: {0}")]
+    Png(String),
+    #[error("can't write {0}: {1}")]
+    Write(PathBuf, std::io::Error),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const BLACK: Color = Color(0, 0, 0);
+
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// Shapes are placed in the solution's own coordinates, with y growing
+// downwards, and scaled to the image when it is drawn
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Polygon(Vec<(f64, f64)>),
+    Line((f64, f64), (f64, f64)),
+    // opposite corners
+    Rect((f64, f64), (f64, f64)),
+    // marker of the same size whatever the scale
+    Dot((f64, f64)),
+}
+
+impl Shape {
+    fn points(&self) -> Vec<(f64, f64)> {
+        match self {
+            Shape::Polygon(points) => points.clone(),
+            Shape::Line(from, to) | Shape::Rect(from, to) => vec![*from, *to],
+            Shape::Dot(center) => vec![*center],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+}
+
+impl Style {
+    pub fn fill(color: Color) -> Self {
+        Self {
+            fill: Some(color),
+            stroke: None,
+        }
+    }
+
+    pub fn stroke(color: Color) -> Self {
+        Self {
+            fill: None,
+            stroke: Some(color),
+        }
+    }
+
+    pub fn with_stroke(self, color: Color) -> Self {
+        Self {
+            stroke: Some(color),
+            ..self
+        }
+    }
+}
+
+const MARGIN: f64 = 10.0;
+const DOT_RADIUS: f64 = 2.0;
+
+// Maps scene coordinates to pixels
+struct Viewport {
+    min: (f64, f64),
+    scale: f64,
+    width: u32,
+    height: u32,
+}
+
+impl Viewport {
+    fn project(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            MARGIN + (x - self.min.0) * self.scale,
+            MARGIN + (y - self.min.1) * self.scale,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scene {
+    background: Color,
+    shapes: Vec<(Shape, Style)>,
+}
+
+impl Scene {
+    pub fn new(background: Color) -> Self {
+        Self {
+            background,
+            shapes: vec![],
+        }
+    }
+
+    pub fn add(&mut self, shape: Shape, style: Style) {
+        self.shapes.push((shape, style));
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    // Fits the scene's longer side into `size` pixels
+    fn viewport(&self, size: u32) -> Viewport {
+        let points = self.shapes.iter().flat_map(|(shape, _)| shape.points());
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for (x, y) in points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        if min.0 > max.0 {
+            min = (0.0, 0.0);
+            max = (0.0, 0.0);
+        }
+
+        let extent = (max.0 - min.0).max(max.1 - min.1);
+        let drawable = (size as f64 - 2.0 * MARGIN).max(1.0);
+        let scale = if extent > 0.0 { drawable / extent } else { 1.0 };
+        let pixels = |length: f64| (length * scale + 2.0 * MARGIN).ceil() as u32;
+        Viewport {
+            min,
+            scale,
+            width: pixels(max.0 - min.0),
+            height: pixels(max.1 - min.1),
+        }
+    }
+
+    pub fn svg(&self, size: u32) -> String {
+        let view = self.viewport(size);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+            view.width, view.height
+        );
+        let _ = writeln!(
+            svg,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            self.background.hex()
+        );
+
+        for (shape, style) in &self.shapes {
+            let paint = format!(
+                "fill=\"{}\" stroke=\"{}\"",
+                style.fill.map_or("none".to_string(), |color| color.hex()),
+                style.stroke.map_or("none".to_string(), |color| color.hex())
+            );
+            let _ = match shape {
+                Shape::Polygon(points) => {
+                    let points: Vec<String> = points
+                        .iter()
+                        .map(|point| {
+                            let (x, y) = view.project(*point);
+                            format!("{x:.2},{y:.2}")
+                        })
+                        .collect();
+                    writeln!(svg, "<polygon points=\"{}\" {paint}/>", points.join(" "))
+                }
+                Shape::Line(from, to) => {
+                    let ((x1, y1), (x2, y2)) = (view.project(*from), view.project(*to));
+                    writeln!(
+                        svg,
+                        "<line x1=\"{x1:.2}\" y1=\"{y1:.2}\" x2=\"{x2:.2}\" y2=\"{y2:.2}\" {paint}/>"
+                    )
+                }
+                Shape::Rect(from, to) => {
+                    let ((x1, y1), (x2, y2)) = (view.project(*from), view.project(*to));
+                    writeln!(
+                        svg,
+                        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" {paint}/>",
+                        x1.min(x2),
+                        y1.min(y2),
+                        (x2 - x1).abs(),
+                        (y2 - y1).abs()
+                    )
+                }
+                Shape::Dot(center) => {
+                    let (x, y) = view.project(*center);
+                    writeln!(
+                        svg,
+                        "<circle cx=\"{x:.2}\" cy=\"{y:.2}\" r=\"{DOT_RADIUS}\" {paint}/>"
+                    )
+                }
+            };
+        }
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+
+    pub fn png(&self, size: u32) -> Result<Vec<u8>, RenderError> {
+        let view = self.viewport(size);
+        let mut pixmap = Pixmap::new(view.width, view.height)
+            .ok_or(RenderError::TooLarge(view.width, view.height))?;
+        let Color(r, g, b) = self.background;
+        pixmap.fill(tiny_skia::Color::from_rgba8(r, g, b, 255));
+
+        for (shape, style) in &self.shapes {
+            let point = |point: (f64, f64)| {
+                let (x, y) = view.project(point);
+                (x as f32, y as f32)
+            };
+            let path = match shape {
+                Shape::Polygon(points) => {
+                    let mut builder = PathBuilder::new();
+                    for (index, (x, y)) in points.iter().map(|p| point(*p)).enumerate() {
+                        match index {
+                            0 => builder.move_to(x, y),
+                            _ => builder.line_to(x, y),
+                        }
+                    }
+                    builder.close();
+                    builder.finish()
+                }
+                Shape::Line(from, to) => {
+                    let mut builder = PathBuilder::new();
+                    let ((x1, y1), (x2, y2)) = (point(*from), point(*to));
+                    builder.move_to(x1, y1);
+                    builder.line_to(x2, y2);
+                    builder.finish()
+                }
+                Shape::Rect(from, to) => {
+                    let ((x1, y1), (x2, y2)) = (point(*from), point(*to));
+                    Rect::from_ltrb(x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2))
+                        .map(PathBuilder::from_rect)
+                }
+                Shape::Dot(center) => {
+                    let (x, y) = point(*center);
+                    PathBuilder::from_circle(x, y, DOT_RADIUS as f32)
+                }
+            };
+            // shapes squashed to nothing at this scale
+            let Some(path) = path else {
+                continue;
+            };
+
+            if let Some(Color(r, g, b)) = style.fill {
+                let mut paint = Paint::default();
+                paint.set_color_rgba8(r, g, b, 255);
+                pixmap.fill_path(
+                    &path,
+                    &paint,
+                    FillRule::EvenOdd,
+                    Transform::identity(),
+                    None,
+                );
+            }
+            if let Some(Color(r, g, b)) = style.stroke {
+                let mut paint = Paint::default();
+                paint.set_color_rgba8(r, g, b, 255);
+                pixmap.stroke_path(
+                    &path,
+                    &paint,
+                    &Stroke::default(),
+                    Transform::identity(),
+                    None,
+                );
+            }
+        }
+
+        pixmap
+            .encode_png()
+            .map_err(|error| RenderError::Png(error.to_string()))
+    }
+
+    // Writes an svg or png file depending on the extension of `path`
+    pub fn save(&self, path: &Path, size: u32) -> Result<(), RenderError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let contents = match extension {
+            "svg" => self.svg(size).into_bytes(),
+            "png" => self.png(size)?,
+            _ => return Err(RenderError::UnsupportedFormat(extension.to_string())),
+        };
+
+        std::fs::write(path, contents)
+            .map_err(|error| RenderError::Write(path.to_path_buf(), error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene() -> Scene {
+        let mut scene = Scene::new(Color::WHITE);
+        scene.add(
+            Shape::Polygon(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0)]),
+            Style::fill(Color::BLACK),
+        );
+        scene.add(Shape::Dot((5.0, 5.0)), Style::stroke(Color(255, 0, 0)));
+        scene
+    }
+
+    #[test]
+    fn it_draws_svg() {
+        let svg = scene().svg(120);
+        assert!(svg
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"120\" height=\"70\""));
+        assert!(svg.contains(
+            "<polygon points=\"10.00,10.00 110.00,10.00 110.00,60.00\" fill=\"#000000\" stroke=\"none\"/>"
+        ));
+        assert!(svg.contains(
+            "<circle cx=\"60.00\" cy=\"60.00\" r=\"2\" fill=\"none\" stroke=\"#ff0000\"/>"
+        ));
+    }
+
+    #[test]
+    fn it_draws_png() {
+        let png = scene().png(120).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        assert!(matches!(
+            scene().save(Path::new("scene.gif"), 120),
+            Err(RenderError::UnsupportedFormat(extension)) if extension == "gif"
+        ));
+    }
+}
@@ -0,0 +1,429 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use rustc_hash::FxHashMap;
+
+// Distance in steps of every node reachable from `start`
+pub fn bfs<N, I>(start: N, successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_within(start, usize::MAX, successors)
+}
+
+// Same as `bfs` but stops exploring at `max_distance` steps from `start`
+pub fn bfs_within<N, I>(
+    start: N,
+    max_distance: usize,
+    mut successors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        if distance == max_distance {
+            continue;
+        }
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+// Cheapest path from `start` to a node accepted by `success`, including both
+// ends, and its cost
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+// Same as `dijkstra` guided by `heuristic`, which must never overestimate the
+// remaining cost
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut nodes: Vec<N> = vec![start.clone()];
+    let mut parents: Vec<Option<usize>> = vec![None];
+    let mut costs: Vec<C> = vec![C::default()];
+    let mut indices: FxHashMap<N, usize> = FxHashMap::default();
+    indices.insert(start.clone(), 0);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        if cost > costs[index] {
+            continue;
+        }
+        let node = nodes[index].clone();
+        if success(&node) {
+            return Some((path(&nodes, &parents, index), cost));
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let next_index = match indices.get(&next) {
+                Some(&known) if costs[known] <= next_cost => continue,
+                Some(&known) => {
+                    costs[known] = next_cost;
+                    parents[known] = Some(index);
+                    known
+                }
+                None => {
+                    indices.insert(next.clone(), nodes.len());
+                    nodes.push(next.clone());
+                    parents.push(Some(index));
+                    costs.push(next_cost);
+                    nodes.len() - 1
+                }
+            };
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                next_index,
+            )));
+        }
+    }
+
+    None
+}
+
+fn path<N: Clone>(nodes: &[N], parents: &[Option<usize>], end: usize) -> Vec<N> {
+    let mut path = vec![nodes[end].clone()];
+    let mut current = end;
+    while let Some(parent) = parents[current] {
+        path.push(nodes[parent].clone());
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
+// Directed graph with weighted edges, nodes are referred to by insertion index
+#[derive(Clone, Debug, Default)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, usize)>>,
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            indices: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+        self.indices.insert(node.clone(), self.nodes.len());
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, weight: usize) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn edges(&self, node: &N) -> impl Iterator<Item = (&N, usize)> {
+        self.indices
+            .get(node)
+            .into_iter()
+            .flat_map(|&index| &self.edges[index])
+            .map(|&(to, weight)| (&self.nodes[to], weight))
+    }
+
+    // Heaviest path from `start` to `end` visiting every node at most once,
+    // with its weight, linear for acyclic graphs and exhaustive otherwise
+    pub fn longest_path(&self, start: &N, end: &N) -> Option<(Vec<N>, usize)> {
+        let start = *self.indices.get(start)?;
+        let end = *self.indices.get(end)?;
+        let (mut path, weight) = match self.topological_order() {
+            Some(order) => self.longest_acyclic_path(&order, start, end),
+            None => self.longest_simple_path(start, end, &mut vec![false; self.len()]),
+        }?;
+        path.reverse();
+
+        Some((
+            path.into_iter().map(|n| self.nodes[n].clone()).collect(),
+            weight,
+        ))
+    }
+
+    fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.len()];
+        for &(to, _) in self.edges.iter().flatten() {
+            incoming[to] += 1;
+        }
+        let mut ready: Vec<usize> = (0..self.len()).filter(|&n| incoming[n] == 0).collect();
+        let mut order = vec![];
+        while let Some(node) = ready.pop() {
+            order.push(node);
+            for &(to, _) in &self.edges[node] {
+                incoming[to] -= 1;
+                if incoming[to] == 0 {
+                    ready.push(to);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    // Both searches return their path from `end` back to `start`
+    fn longest_acyclic_path(
+        &self,
+        order: &[usize],
+        start: usize,
+        end: usize,
+    ) -> Option<(Vec<usize>, usize)> {
+        let mut longest: Vec<Option<(usize, usize)>> = vec![None; self.len()];
+        longest[start] = Some((0, start));
+        for &node in order {
+            let Some((length, _)) = longest[node] else {
+                continue;
+            };
+            for &(to, weight) in &self.edges[node] {
+                if longest[to].is_none_or(|(best, _)| length + weight > best) {
+                    longest[to] = Some((length + weight, node));
+                }
+            }
+        }
+
+        let (length, _) = longest[end]?;
+        let mut path = vec![end];
+        let mut node = end;
+        while node != start {
+            node = longest[node]?.1;
+            path.push(node);
+        }
+
+        Some((path, length))
+    }
+
+    fn longest_simple_path(
+        &self,
+        node: usize,
+        end: usize,
+        visited: &mut Vec<bool>,
+    ) -> Option<(Vec<usize>, usize)> {
+        if node == end {
+            return Some((vec![end], 0));
+        }
+
+        visited[node] = true;
+        let mut longest: Option<(Vec<usize>, usize)> = None;
+        for &(to, weight) in &self.edges[node] {
+            if visited[to] {
+                continue;
+            }
+            if let Some((path, length)) = self.longest_simple_path(to, end, visited) {
+                if longest
+                    .as_ref()
+                    .is_none_or(|(_, best)| length + weight > *best)
+                {
+                    longest = Some((path, length + weight));
+                }
+            }
+        }
+        visited[node] = false;
+
+        longest.map(|(mut path, length)| {
+            path.push(node);
+            (path, length)
+        })
+    }
+}
+
+// Nodes walked from `junction` through `first` up to and including the next
+// node `is_junction` accepts, None for dead ends
+fn walk_corridor<N: Clone + Eq>(
+    junction: &N,
+    first: N,
+    is_junction: impl Fn(&N, usize) -> bool,
+    next: &mut impl FnMut(&N) -> Vec<N>,
+) -> Option<Vec<N>> {
+    let mut corridor = vec![first];
+    let mut previous = junction.clone();
+    loop {
+        let current = corridor.last()?;
+        let options = next(current);
+        if is_junction(current, options.len()) {
+            return Some(corridor);
+        }
+        let following = options.into_iter().find(|option| *option != previous)?;
+        previous = current.clone();
+        corridor.push(following);
+    }
+}
+
+// Collapses corridors, chains of nodes with at most two successors, into
+// single weighted edges between `start`, `end` and the junctions reachable
+// from `start`
+pub fn compress<N, I>(start: N, end: N, mut successors: impl FnMut(&N) -> I) -> Graph<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut next = |node: &N| successors(node).into_iter().collect::<Vec<N>>();
+    let is_junction = |node: &N, options: usize| *node == start || *node == end || options > 2;
+    let mut graph = Graph::new();
+    graph.add_node(start.clone());
+    let mut queue = vec![start.clone()];
+
+    while let Some(junction) = queue.pop() {
+        for first in next(&junction) {
+            let Some(corridor) = walk_corridor(&junction, first, is_junction, &mut next) else {
+                continue;
+            };
+            let length = corridor.len();
+            let reached = corridor.into_iter().last();
+            if let Some(reached) = reached.filter(|reached| *reached != junction) {
+                if !graph.indices.contains_key(&reached) {
+                    queue.push(reached.clone());
+                }
+                graph.add_edge(junction.clone(), reached, length);
+            }
+        }
+    }
+
+    graph
+}
+
+// Every node along a path of junctions found in a compressed graph, taking
+// the longest corridor wherever two junctions are joined by several
+pub fn unfold<N, I>(junctions: &[N], mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq,
+    I: IntoIterator<Item = N>,
+{
+    let mut next = |node: &N| successors(node).into_iter().collect::<Vec<N>>();
+    let is_junction = |node: &N, options: usize| junctions.contains(node) || options > 2;
+    let mut path: Vec<N> = junctions.first().cloned().into_iter().collect();
+
+    for pair in junctions.windows(2) {
+        let corridor = next(&pair[0])
+            .into_iter()
+            .filter_map(|first| walk_corridor(&pair[0], first, is_junction, &mut next))
+            .filter(|corridor| corridor.last() == Some(&pair[1]))
+            .max_by_key(|corridor| corridor.len());
+        path.extend(corridor.unwrap_or_else(|| vec![pair[1].clone()]));
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 - 1 - 2
+    // |       |
+    // 3 - 4 - 5 - 6
+    fn neighbours(node: &u8) -> Vec<u8> {
+        match node {
+            0 => vec![1, 3],
+            1 => vec![0, 2],
+            2 => vec![1, 5],
+            3 => vec![0, 4],
+            4 => vec![3, 5],
+            5 => vec![2, 4, 6],
+            6 => vec![5],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn it_measures_distances() {
+        let distances = bfs(0, neighbours);
+        assert_eq!(distances.len(), 7);
+        assert_eq!(distances[&5], 3);
+        assert_eq!(distances[&6], 4);
+        assert_eq!(bfs_within(0, 2, neighbours).len(), 5);
+    }
+
+    #[test]
+    fn it_finds_cheapest_paths() {
+        let weighted = |node: &u8| {
+            neighbours(node)
+                .into_iter()
+                .map(|next| (next, if next == 1 { 10 } else { 1 }))
+                .collect::<Vec<(u8, usize)>>()
+        };
+        assert_eq!(
+            dijkstra(0, weighted, |node| *node == 2),
+            Some((vec![0, 3, 4, 5, 2], 4))
+        );
+        assert_eq!(
+            astar(
+                0,
+                weighted,
+                |node| (6 - *node as usize) / 3,
+                |node| *node == 6
+            ),
+            Some((vec![0, 3, 4, 5, 6], 4))
+        );
+        assert_eq!(dijkstra(0, weighted, |node| *node == 7), None);
+    }
+
+    #[test]
+    fn it_finds_longest_paths() {
+        let graph = compress(0, 6, neighbours);
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.longest_path(&0, &6), Some((vec![0, 5, 6], 4)));
+        assert_eq!(unfold(&[0, 5, 6], neighbours), vec![0, 3, 4, 5, 6]);
+
+        let mut dag = Graph::new();
+        dag.add_edge('a', 'b', 2);
+        dag.add_edge('a', 'c', 1);
+        dag.add_edge('b', 'd', 1);
+        dag.add_edge('c', 'd', 5);
+        assert_eq!(dag.longest_path(&'a', &'d'), Some((vec!['a', 'c', 'd'], 6)));
+        assert_eq!(dag.longest_path(&'d', &'a'), None);
+        assert_eq!(
+            dag.edges(&'a').collect::<Vec<_>>(),
+            vec![(&'b', 2), (&'c', 1)]
+        );
+    }
+}
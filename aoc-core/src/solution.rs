@@ -0,0 +1,397 @@
+use std::fmt;
+use std::io::{BufRead, Read};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::generate::{seeded, InputRng};
+use crate::input::{input_path_from_args, open_input, STDIN};
+#[cfg(feature = "render")]
+use crate::render::Scene;
+use crate::visualize::Painter;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Answer {
+    U32(u32),
+    Usize(usize),
+    U64(u64),
+    U128(u128),
+    I64(i64),
+}
+
+impl Answer {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::U32(_) => "u32",
+            Answer::Usize(_) => "usize",
+            Answer::U64(_) => "u64",
+            Answer::U128(_) => "u128",
+            Answer::I64(_) => "i64",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::U32(value) => write!(f, "{value}"),
+            Answer::Usize(value) => write!(f, "{value}"),
+            Answer::U64(value) => write!(f, "{value}"),
+            Answer::U128(value) => write!(f, "{value}"),
+            Answer::I64(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::U32(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Usize(value)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::U64(value)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::U128(value)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::I64(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Answer of a part together with the time spent parsing the input and
+/// solving the part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timed {
+    pub answer: Option<Answer>,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+/// Answers of the selected parts found in a single pass over the input, each
+/// part fails on its own like it does when solved from the whole input. Parts
+/// that weren't selected, or that the day doesn't have, are `None`.
+#[derive(Debug)]
+pub struct Streamed {
+    pub part_one: Option<Result<Answer, Error>>,
+    pub part_two: Option<Result<Answer, Error>>,
+}
+
+impl Streamed {
+    pub fn get(&self, part: Part) -> Result<Option<Answer>, &Error> {
+        let answer = match part {
+            Part::One => &self.part_one,
+            Part::Two => &self.part_two,
+        };
+        answer
+            .as_ref()
+            .map(|answer| answer.as_ref().copied())
+            .transpose()
+    }
+}
+
+/// Parts a streaming day is asked to solve and the time it spends on each.
+/// Reading and parsing the input is shared, so every part is charged with
+/// it on top of its own work, like when parts are solved from the whole
+/// input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stopwatch {
+    parts: Vec<Part>,
+    solving: [Duration; 2],
+    total: Duration,
+}
+
+impl Stopwatch {
+    pub fn new(parts: &[Part]) -> Self {
+        Stopwatch {
+            parts: parts.to_vec(),
+            solving: [Duration::ZERO; 2],
+            total: Duration::ZERO,
+        }
+    }
+
+    pub fn is_selected(&self, part: Part) -> bool {
+        self.parts.contains(&part)
+    }
+
+    /// Runs the work of a selected part and adds the time it takes to the
+    /// part, work of the other parts is skipped
+    pub fn time<T>(&mut self, part: Part, work: impl FnOnce() -> T) -> Option<T> {
+        if !self.is_selected(part) {
+            return None;
+        }
+        let start = Instant::now();
+        let result = work();
+        self.solving[usize::from(part.number() - 1)] += start.elapsed();
+        Some(result)
+    }
+
+    /// Time spent reading the input and solving the part
+    pub fn elapsed(&self, part: Part) -> Duration {
+        let solving: Duration = self.solving.iter().sum();
+        self.total.saturating_sub(solving) + self.solving[usize::from(part.number() - 1)]
+    }
+}
+
+pub trait Puzzle {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part_one(input: &Self::Input) -> Result<Answer, Error>;
+
+    fn part_two(_input: &Self::Input) -> Option<Result<Answer, Error>> {
+        None
+    }
+
+    // Solves the parts selected in the stopwatch while reading the input,
+    // for the days that don't need all of it at once, timing each part's
+    // work with it. Days returning `None` must not read from the input.
+    fn stream(
+        _input: &mut dyn BufRead,
+        _stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        None
+    }
+
+    // Random input in the puzzle's format, `size` scales it in a way that
+    // depends on the day
+    fn generate(_rng: &mut InputRng, _size: usize) -> Option<String> {
+        None
+    }
+
+    // Drawing of how a part is solved, for the days that have one
+    fn visualize(
+        _input: &Self::Input,
+        _part: Part,
+        _painter: &Painter,
+    ) -> Option<Result<String, Error>> {
+        None
+    }
+
+    // Picture of a part's solution state, for image export
+    #[cfg(feature = "render")]
+    fn render(_input: &Self::Input, _part: Part) -> Option<Result<Scene, Error>> {
+        None
+    }
+}
+
+// `Sync` so that days can run on several threads
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn solve(&self, part: Part, input: &str) -> Result<Option<Answer>, Error>;
+
+    fn solve_timed(&self, part: Part, input: &str) -> Result<Timed, Error>;
+
+    fn stream(
+        &self,
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>>;
+
+    fn visualize(
+        &self,
+        part: Part,
+        input: &str,
+        painter: &Painter,
+    ) -> Result<Option<String>, Error>;
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String>;
+
+    #[cfg(feature = "render")]
+    fn render(&self, part: Part, input: &str) -> Result<Option<Scene>, Error>;
+
+    fn directory(&self) -> String {
+        format!("day-{:02}-{}", self.day(), self.title())
+    }
+}
+
+impl<P: Puzzle + Sync> Solution for P {
+    fn day(&self) -> u8 {
+        P::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        P::TITLE
+    }
+
+    fn solve(&self, part: Part, input: &str) -> Result<Option<Answer>, Error> {
+        let parsed = P::parse(input)?;
+        match part {
+            Part::One => P::part_one(&parsed).map(Some),
+            Part::Two => P::part_two(&parsed).transpose(),
+        }
+    }
+
+    fn solve_timed(&self, part: Part, input: &str) -> Result<Timed, Error> {
+        let start = Instant::now();
+        let parsed = P::parse(input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let answer = match part {
+            Part::One => P::part_one(&parsed).map(Some),
+            Part::Two => P::part_two(&parsed).transpose(),
+        }?;
+        let solve = start.elapsed();
+
+        Ok(Timed {
+            answer,
+            parse,
+            solve,
+        })
+    }
+
+    fn stream(
+        &self,
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        let start = Instant::now();
+        let streamed = P::stream(input, stopwatch);
+        stopwatch.total = start.elapsed();
+        streamed
+    }
+
+    fn visualize(
+        &self,
+        part: Part,
+        input: &str,
+        painter: &Painter,
+    ) -> Result<Option<String>, Error> {
+        let parsed = P::parse(input)?;
+        P::visualize(&parsed, part, painter).transpose()
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        P::generate(&mut seeded(seed), size)
+    }
+
+    #[cfg(feature = "render")]
+    fn render(&self, part: Part, input: &str) -> Result<Option<Scene>, Error> {
+        let parsed = P::parse(input)?;
+        P::render(&parsed, part).transpose()
+    }
+}
+
+pub fn run(solution: &dyn Solution) -> ExitCode {
+    let Some(file_path) = input_path_from_args() else {
+        eprintln!(
+            "usage: day-{:02} <input file, or {STDIN} for stdin>",
+            solution.day()
+        );
+        return ExitCode::FAILURE;
+    };
+
+    println!("In file {}", file_path);
+
+    let mut input = match open_input(&file_path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("error: can't read {file_path}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(streamed) = solution.stream(&mut input, &mut Stopwatch::new(&Part::ALL)) {
+        let streamed = match streamed {
+            Ok(streamed) => streamed,
+            Err(error) => {
+                eprintln!("error: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+        for part in Part::ALL {
+            match streamed.get(part) {
+                Ok(Some(answer)) => println!("Part {part}:\n{answer}"),
+                Ok(None) => {}
+                Err(error) => {
+                    eprintln!("error: part {part}: {error}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let mut contents = String::new();
+    if let Err(error) = input.read_to_string(&mut contents) {
+        eprintln!("error: can't read {file_path}: {error}");
+        return ExitCode::FAILURE;
+    }
+
+    for part in Part::ALL {
+        match solution.solve(part, &contents) {
+            Ok(Some(answer)) => println!("Part {part}:\n{answer}"),
+            Ok(None) => {}
+            Err(error) => {
+                eprintln!("error: part {part}: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_times_selected_parts_only() {
+        let mut stopwatch = Stopwatch::new(&[Part::Two]);
+        assert_eq!(stopwatch.time(Part::One, || 1), None);
+        assert_eq!(
+            stopwatch.time(Part::Two, || std::thread::sleep(Duration::from_millis(5))),
+            Some(())
+        );
+        stopwatch.total = Duration::from_millis(20);
+
+        assert!(stopwatch.elapsed(Part::One) < Duration::from_millis(15));
+        assert_eq!(stopwatch.elapsed(Part::Two), Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::grid::{Grid, Point};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Dim,
+}
+
+impl Color {
+    fn code(&self) -> &'static str {
+        match self {
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Blue => "34",
+            Color::Magenta => "35",
+            Color::Cyan => "36",
+            Color::Dim => "2",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+// Draws grids as text, with ANSI colours unless they are turned off
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Painter {
+    colored: bool,
+}
+
+impl Painter {
+    pub fn new(colored: bool) -> Self {
+        Self { colored }
+    }
+
+    pub fn paint(&self, text: &str, color: Option<Color>) -> String {
+        match color {
+            Some(color) if self.colored => format!("\x1b[{}m{text}{RESET}", color.code()),
+            _ => text.to_string(),
+        }
+    }
+
+    // `cell` picks the glyph and colour of every tile, neighbouring tiles of
+    // the same colour share a single escape sequence
+    pub fn grid<T>(
+        &self,
+        grid: &Grid<T>,
+        cell: impl Fn(Point, &T) -> (char, Option<Color>),
+    ) -> String {
+        let mut lines = vec![];
+        for (row, tiles) in grid.rows().enumerate() {
+            let mut line = String::new();
+            let mut run = String::new();
+            let mut run_color = None;
+            for (col, tile) in tiles.iter().enumerate() {
+                let (glyph, color) = cell(Point::new(row, col), tile);
+                if color != run_color && !run.is_empty() {
+                    line.push_str(&self.paint(&run, run_color));
+                    run.clear();
+                }
+                run_color = color;
+                run.push(glyph);
+            }
+            line.push_str(&self.paint(&run, run_color));
+            lines.push(line);
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_paints_grids() {
+        let grid = Grid::parse("#..\n.#.", 0, Some).unwrap();
+        let cell = |_, tile: &char| match tile {
+            '#' => ('#', Some(Color::Red)),
+            _ => (' ', None),
+        };
+        assert_eq!(Painter::new(false).grid(&grid, cell), "#  \n # ");
+        assert_eq!(
+            Painter::new(true).grid(&grid, cell),
+            "\x1b[31m#\x1b[0m  \n \x1b[31m#\x1b[0m "
+        );
+        assert_eq!(
+            Painter::new(true).paint("ab", Some(Color::Dim)),
+            "\x1b[2mab\x1b[0m"
+        );
+    }
+}
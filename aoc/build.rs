@@ -0,0 +1,87 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Year of the day directories at the repository root, later years keep
+// theirs in a directory named after the year
+const ROOT_YEAR: u16 = 2023;
+
+// Directories in `directory` whose name passes `keep`, sorted
+fn directories(directory: &Path, keep: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let mut directories: Vec<PathBuf> = fs::read_dir(directory)
+        .unwrap()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_dir())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(&keep)
+        })
+        .collect();
+    directories.sort();
+    directories
+}
+
+// Generates a test per year, day and recorded input from the `answers.toml`
+// files, they are included by `tests/answers.rs`.
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("..");
+    let mut years = vec![(ROOT_YEAR, root.clone())];
+    for directory in directories(&root, |name| name.len() == 4 && name.parse::<u16>().is_ok()) {
+        let year = directory
+            .file_name()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        years.push((year, directory));
+    }
+
+    let mut tests = String::new();
+    for (year, directory) in years {
+        for directory in directories(&directory, |name| name.starts_with("day-")) {
+            generate_tests(&mut tests, year, &directory);
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("answers.rs");
+    fs::write(out, tests).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+fn generate_tests(tests: &mut String, year: u16, directory: &Path) {
+    let name = directory.file_name().unwrap().to_str().unwrap();
+    let Ok(day) = name[4..6].parse::<u8>() else {
+        return;
+    };
+    let answers = directory.join("answers.toml");
+    println!("cargo:rerun-if-changed={}", answers.display());
+    let Ok(contents) = fs::read_to_string(&answers) else {
+        return;
+    };
+    let table: toml::Table = contents
+        .parse()
+        .unwrap_or_else(|error| panic!("{}: {error}", answers.display()));
+
+    for input in table.keys() {
+        let test_name: String = input
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        writeln!(
+            tests,
+            "#[test]\nfn year_{year}_day_{day:02}_{test_name}() {{\n    verify({year}, {day}, {input:?});\n}}\n"
+        )
+        .unwrap();
+        // samples are small enough to solve again with windows line breaks
+        if input.starts_with("sample") {
+            writeln!(
+                tests,
+                "#[test]\nfn year_{year}_day_{day:02}_{test_name}_crlf() {{\n    verify_crlf({year}, {day}, {input:?});\n}}\n"
+            )
+            .unwrap();
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use aoc_core::input::read_input;
+use aoc_core::{Part, Solution};
+use serde::Deserialize;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Expected answers of a day keyed by input file name without the `.txt`
+/// extension, e.g. `sample` or `input`.
+pub type Answers = BTreeMap<String, Expected>;
+
+pub fn parse_answers(contents: &str) -> Result<Answers, String> {
+    toml::from_str(contents).map_err(|error| error.to_string())
+}
+
+/// Reads `answers.toml` of a day directory, a missing file means nothing is
+/// recorded yet.
+pub fn load_answers(directory: &Path) -> Result<Answers, String> {
+    let path = directory.join(ANSWERS_FILE);
+    match fs::read_to_string(&path) {
+        Ok(contents) => {
+            parse_answers(&contents).map_err(|error| format!("{}: {error}", path.display()))
+        }
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Answers::new()),
+        Err(error) => Err(format!("can't read {}: {error}", path.display())),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    Unsolved { expected: String },
+    Error(String),
+}
+
+impl Outcome {
+    pub fn is_pass(&self) -> bool {
+        *self == Outcome::Pass
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "ok"),
+            Outcome::Mismatch { expected, actual } => {
+                write!(f, "FAILED expected {expected}, got {actual}")
+            }
+            Outcome::Unsolved { expected } => {
+                write!(f, "FAILED expected {expected}, got no answer")
+            }
+            Outcome::Error(error) => write!(f, "FAILED {error}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub input: String,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {:02} {} part {}: {}",
+            self.year, self.day, self.input, self.part, self.outcome
+        )
+    }
+}
+
+pub fn check_answer(
+    solution: &dyn Solution,
+    part: Part,
+    contents: &str,
+    expected: &str,
+) -> Outcome {
+    match solution.solve(part, contents) {
+        Ok(Some(answer)) if answer.to_string() == expected => Outcome::Pass,
+        Ok(Some(answer)) => Outcome::Mismatch {
+            expected: expected.to_string(),
+            actual: answer.to_string(),
+        },
+        Ok(None) => Outcome::Unsolved {
+            expected: expected.to_string(),
+        },
+        Err(error) => Outcome::Error(error.to_string()),
+    }
+}
+
+/// Runs every recorded part of one input of a day of the year, `root` is
+/// the directory holding the day directories.
+pub fn verify_input(
+    year: u16,
+    solution: &dyn Solution,
+    root: &Path,
+    input: &str,
+    expected: &Expected,
+) -> Vec<Check> {
+    let path = root.join(solution.directory()).join(format!("{input}.txt"));
+    let contents = read_input(&path);
+
+    Part::ALL
+        .into_iter()
+        .filter_map(|part| {
+            let expected = expected.get(part)?;
+            let outcome = match &contents {
+                Ok(contents) => check_answer(solution, part, contents, expected),
+                Err(error) => Outcome::Error(format!("can't read {}: {error}", path.display())),
+            };
+            Some(Check {
+                year,
+                day: solution.day(),
+                input: input.to_string(),
+                part,
+                outcome,
+            })
+        })
+        .collect()
+}
+
+pub fn verify_day(year: u16, solution: &dyn Solution, root: &Path) -> Result<Vec<Check>, String> {
+    let answers = load_answers(&root.join(solution.directory()))?;
+
+    Ok(answers
+        .iter()
+        .flat_map(|(input, expected)| verify_input(year, solution, root, input, expected))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::{Answer, Error, Puzzle};
+
+    struct Sum;
+
+    impl Puzzle for Sum {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "sum";
+
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(input
+                .split(",")
+                .map(|value| value.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part_one(input: &Self::Input) -> Result<Answer, Error> {
+            Ok(input.iter().sum::<u32>().into())
+        }
+    }
+
+    #[test]
+    fn it_parses_answers() {
+        let answers = parse_answers("[sample]\npart1 = \"142\"\n\n[input]\npart2 = \"9\"").unwrap();
+        assert_eq!(answers["sample"].get(Part::One), Some("142"));
+        assert_eq!(answers["sample"].get(Part::Two), None);
+        assert_eq!(answers["input"].get(Part::Two), Some("9"));
+        assert!(parse_answers("[sample]\npart3 = \"1\"").is_err());
+        assert!(parse_answers("[sample]\npart1 = 1").is_err());
+    }
+
+    #[test]
+    fn it_checks_answers() {
+        assert_eq!(check_answer(&Sum, Part::One, "1,2", "3"), Outcome::Pass);
+        assert_eq!(
+            check_answer(&Sum, Part::One, "1,2", "4"),
+            Outcome::Mismatch {
+                expected: String::from("4"),
+                actual: String::from("3")
+            }
+        );
+        assert_eq!(
+            check_answer(&Sum, Part::Two, "1,2", "3"),
+            Outcome::Unsolved {
+                expected: String::from("3")
+            }
+        );
+        assert!(matches!(
+            check_answer(&Sum, Part::One, "1,x", "3"),
+            Outcome::Error(_)
+        ));
+    }
+}
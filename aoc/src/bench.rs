@@ -0,0 +1,220 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_core::{Answer, Error, Part, Solution};
+use serde::{Deserialize, Serialize};
+
+pub const HISTORY_FILE: &str = "target/aoc-bench.jsonl";
+
+/// Median timings of a part over several runs, stored one per line in the
+/// history file.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Measurement {
+    // 0 for measurements saved before the year was kept, they match no year
+    #[serde(default)]
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub runs: usize,
+    pub parse_ns: u128,
+    pub solve_ns: u128,
+    pub recorded_at: u64,
+}
+
+impl Measurement {
+    pub fn parse(&self) -> Duration {
+        nanos(self.parse_ns)
+    }
+
+    pub fn solve(&self) -> Duration {
+        nanos(self.solve_ns)
+    }
+
+    pub fn total_ns(&self) -> u128 {
+        self.parse_ns + self.solve_ns
+    }
+
+    /// Relative change of the total time compared to an earlier measurement,
+    /// `0.1` means 10% slower.
+    pub fn change_since(&self, previous: &Measurement) -> f64 {
+        if previous.total_ns() == 0 {
+            return 0.0;
+        }
+        self.total_ns() as f64 / previous.total_ns() as f64 - 1.0
+    }
+
+    pub fn is_same_case(&self, other: &Measurement) -> bool {
+        self.year == other.year
+            && self.day == other.day
+            && self.part == other.part
+            && self.input == other.input
+    }
+}
+
+fn nanos(value: u128) -> Duration {
+    Duration::from_nanos(value.try_into().unwrap_or(u64::MAX))
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations
+        .get(durations.len() / 2)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Solves a part `runs` times and keeps the median parse and solve times,
+/// returns `None` when the day has no such part.
+pub fn measure(
+    year: u16,
+    solution: &dyn Solution,
+    part: Part,
+    input: &Path,
+    contents: &str,
+    runs: usize,
+) -> Result<Option<(Answer, Measurement)>, Error> {
+    let mut answer = None;
+    let mut parse = vec![];
+    let mut solve = vec![];
+    for _ in 0..runs.max(1) {
+        let timed = solution.solve_timed(part, contents)?;
+        let Some(value) = timed.answer else {
+            return Ok(None);
+        };
+        answer = Some(value);
+        parse.push(timed.parse);
+        solve.push(timed.solve);
+    }
+
+    let recorded_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    Ok(answer.map(|answer| {
+        (
+            answer,
+            Measurement {
+                year,
+                day: solution.day(),
+                part: part.number(),
+                input: input.display().to_string(),
+                runs: parse.len(),
+                parse_ns: median(parse).as_nanos(),
+                solve_ns: median(solve).as_nanos(),
+                recorded_at,
+            },
+        )
+    }))
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<Measurement>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(format!("can't read {}: {error}", path.display())),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|error| format!("{} line {}: {error}", path.display(), index + 1))
+        })
+        .collect()
+}
+
+pub fn append_history(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    let error = |error: io::Error| format!("can't write {}: {error}", path.display());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(error)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(error)?;
+    for measurement in measurements {
+        let line = serde_json::to_string(measurement).map_err(|error| error.to_string())?;
+        writeln!(file, "{line}").map_err(error)?;
+    }
+
+    Ok(())
+}
+
+/// Latest recorded measurement of the same year, day, part and input.
+pub fn previous<'a>(history: &'a [Measurement], current: &Measurement) -> Option<&'a Measurement> {
+    history
+        .iter()
+        .rev()
+        .find(|measurement| measurement.is_same_case(current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(parse_ns: u128, solve_ns: u128) -> Measurement {
+        Measurement {
+            year: 2023,
+            day: 17,
+            part: 1,
+            input: String::from("day-17-heat-loss/input.txt"),
+            runs: 5,
+            parse_ns,
+            solve_ns,
+            recorded_at: 1702771200,
+        }
+    }
+
+    #[test]
+    fn it_takes_the_median() {
+        let millis = |value| Duration::from_millis(value);
+        assert_eq!(median(vec![millis(9), millis(1), millis(4)]), millis(4));
+        assert_eq!(median(vec![]), Duration::ZERO);
+    }
+
+    #[test]
+    fn it_compares_with_previous_runs() {
+        let history = vec![
+            measurement(100, 900),
+            Measurement {
+                part: 2,
+                ..measurement(100, 100)
+            },
+            measurement(100, 1900),
+            Measurement {
+                year: 2022,
+                ..measurement(100, 100)
+            },
+            measurement(100, 1900),
+        ];
+        let current = measurement(200, 2800);
+
+        assert_eq!(previous(&history, &current), Some(&history[2]));
+        assert_eq!(current.change_since(&history[2]), 0.5);
+        let older = Measurement {
+            year: 2022,
+            ..current.clone()
+        };
+        assert_eq!(previous(&history, &older), Some(&history[3]));
+        assert_eq!(previous(&history[..0], &current), None);
+    }
+
+    #[test]
+    fn it_serializes_history() {
+        let line = serde_json::to_string(&measurement(1, 2)).unwrap();
+        assert_eq!(
+            line,
+            r#"{"year":2023,"day":17,"part":1,"input":"day-17-heat-loss/input.txt","runs":5,"parse_ns":1,"solve_ns":2,"recorded_at":1702771200}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Measurement>(&line).unwrap(),
+            measurement(1, 2)
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// images are only written when built with the `render` feature
+#![cfg_attr(not(feature = "render"), allow(dead_code))]
+
+use std::path::PathBuf;
+
+use aoc_core::{Part, Solution};
+use clap::ValueEnum;
+
+const IMAGE_SIZE: u32 = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ImageFormat {
+    Svg,
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Svg => "svg",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+// Where `run --export` writes its images
+#[derive(Debug)]
+pub struct Export {
+    pub directory: PathBuf,
+    pub format: ImageFormat,
+}
+
+impl Export {
+    fn path(&self, solution: &dyn Solution, part: Part) -> PathBuf {
+        self.directory.join(format!(
+            "{}-part-{part}.{}",
+            solution.directory(),
+            self.format.extension()
+        ))
+    }
+
+    #[cfg(feature = "render")]
+    pub fn prepare(&self) -> Result<(), String> {
+        std::fs::create_dir_all(&self.directory)
+            .map_err(|error| format!("can't create {}: {error}", self.directory.display()))
+    }
+
+    #[cfg(not(feature = "render"))]
+    pub fn prepare(&self) -> Result<(), String> {
+        Err("image export needs the `render` feature, rebuild with `--features render`".to_string())
+    }
+
+    // Returns the file written, days without a picture for `part` are skipped
+    #[cfg(feature = "render")]
+    pub fn write(
+        &self,
+        solution: &dyn Solution,
+        part: Part,
+        contents: &str,
+    ) -> Result<Option<PathBuf>, aoc_core::Error> {
+        let Some(scene) = solution.render(part, contents)? else {
+            return Ok(None);
+        };
+        let path = self.path(solution, part);
+        scene.save(&path, IMAGE_SIZE)?;
+
+        Ok(Some(path))
+    }
+
+    #[cfg(not(feature = "render"))]
+    pub fn write(
+        &self,
+        _solution: &dyn Solution,
+        _part: Part,
+        _contents: &str,
+    ) -> Result<Option<PathBuf>, aoc_core::Error> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Profile used when none is picked, for a single account
+pub const DEFAULT_PROFILE: &str = "default";
+/// Environment variable overriding where the cache is
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+/// Environment variable holding the session of profiles without a session file
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Puzzle inputs kept side by side for every year, account profile and day,
+/// as `<root>/<year>/<profile>/day-NN.txt`. Session cookies of the profiles
+/// are read from `<root>/sessions/<profile>`.
+#[derive(Debug)]
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Cache { root: root.into() }
+    }
+
+    /// `$AOC_CACHE_DIR`, otherwise `aoc` in the user's cache directory
+    pub fn default_root() -> Option<PathBuf> {
+        env::var_os(CACHE_DIR_VAR)
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("XDG_CACHE_HOME").map(|cache| PathBuf::from(cache).join("aoc")))
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache/aoc")))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where the input of a day is kept, whether it is cached or not
+    pub fn path(&self, year: u16, profile: &str, day: u8) -> Result<PathBuf, String> {
+        check_profile(profile)?;
+        Ok(self
+            .root
+            .join(year.to_string())
+            .join(profile)
+            .join(format!("day-{day:02}.txt")))
+    }
+
+    /// The cached input of a day, `None` when it isn't cached yet
+    pub fn find(&self, year: u16, profile: &str, day: u8) -> Result<Option<PathBuf>, String> {
+        Ok(Some(self.path(year, profile, day)?).filter(|path| path.is_file()))
+    }
+
+    pub fn store(
+        &self,
+        year: u16,
+        profile: &str,
+        day: u8,
+        contents: &str,
+    ) -> Result<PathBuf, String> {
+        let path = self.path(year, profile, day)?;
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .map_err(|error| format!("can't create {}: {error}", directory.display()))?;
+        }
+        fs::write(&path, contents)
+            .map_err(|error| format!("can't write {}: {error}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Days cached for each profile of the year
+    pub fn profiles(&self, year: u16) -> Result<BTreeMap<String, Vec<u8>>, String> {
+        let mut profiles = BTreeMap::new();
+        for (name, path) in entries(&self.root.join(year.to_string()))? {
+            if !path.is_dir() {
+                continue;
+            }
+            let days = entries(&path)?
+                .into_iter()
+                .filter_map(|(name, _)| {
+                    let day = name.strip_prefix("day-")?.strip_suffix(".txt")?;
+                    day.parse().ok()
+                })
+                .collect();
+            profiles.insert(name, days);
+        }
+        Ok(profiles)
+    }
+
+    /// Copies the inputs found in a directory into the profile: files named
+    /// after their day such as `day-05.txt` or `17.txt`, and day directories
+    /// like `day-05-fertilizer` holding an `input.txt`
+    pub fn import(
+        &self,
+        year: u16,
+        profile: &str,
+        directory: &Path,
+    ) -> Result<Vec<(u8, PathBuf)>, String> {
+        let mut found: BTreeMap<u8, PathBuf> = BTreeMap::new();
+        for (name, path) in entries(directory)? {
+            let input = match path.is_dir() {
+                true => path.join("input.txt"),
+                false if path.extension().is_some_and(|extension| extension == "txt") => {
+                    path.clone()
+                }
+                false => continue,
+            };
+            let stem = name.strip_suffix(".txt").unwrap_or(&name);
+            let Some(day) = day_of(stem).filter(|_| input.is_file()) else {
+                continue;
+            };
+            if let Some(other) = found.insert(day, input) {
+                return Err(format!(
+                    "both {} and {} are inputs for day {day}",
+                    other.display(),
+                    found[&day].display()
+                ));
+            }
+        }
+
+        let mut imported = vec![];
+        for (day, input) in found {
+            let contents = fs::read_to_string(&input)
+                .map_err(|error| format!("can't read {}: {error}", input.display()))?;
+            imported.push((day, self.store(year, profile, day, &contents)?));
+        }
+        Ok(imported)
+    }
+
+    /// The cached input of a day, fetched and kept first when it's missing
+    pub fn fetch(
+        &self,
+        fetcher: &dyn Fetch,
+        year: u16,
+        profile: &str,
+        day: u8,
+    ) -> Result<PathBuf, String> {
+        if let Some(path) = self.find(year, profile, day)? {
+            return Ok(path);
+        }
+        let contents = fetcher.fetch(year, day)?;
+        self.store(year, profile, day, &contents)
+    }
+
+    /// Session cookie of a profile, from its session file or `$AOC_SESSION`
+    pub fn session(&self, profile: &str) -> Result<String, String> {
+        check_profile(profile)?;
+        let path = self.root.join("sessions").join(profile);
+        match fs::read_to_string(&path) {
+            Ok(session) => Ok(session.trim().to_string()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                env::var(SESSION_VAR).map_err(|_| {
+                    format!(
+                        "no session for profile `{profile}`, write it to {} or set {SESSION_VAR}",
+                        path.display()
+                    )
+                })
+            }
+            Err(error) => Err(format!("can't read {}: {error}", path.display())),
+        }
+    }
+}
+
+// Profiles name a directory of the cache, they can't lead out of it
+fn check_profile(profile: &str) -> Result<(), String> {
+    if profile.is_empty()
+        || profile == "."
+        || profile.contains(['/', '\\'])
+        || profile.contains("..")
+    {
+        return Err(format!(
+            "`{profile}` can't name a profile, it must be a name other than `.` without `/`, `\\` or `..`"
+        ));
+    }
+    Ok(())
+}
+
+// Names and paths in a directory sorted by name, a missing one is empty
+fn entries(directory: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let can_read = |error: io::Error| format!("can't read {}: {error}", directory.display());
+    let read = match fs::read_dir(directory) {
+        Ok(read) => read,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(can_read(error)),
+    };
+    let mut entries = vec![];
+    for entry in read {
+        let entry = entry.map_err(can_read)?;
+        if let Some(name) = entry.file_name().to_str() {
+            entries.push((name.to_string(), entry.path()));
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+// The number after `day-`, so `2023-12-day-03` is day 3, or the whole name
+// when it's nothing but a number like `17`
+fn day_of(name: &str) -> Option<u8> {
+    let number = match name.split_once("day-") {
+        Some((_, rest)) => rest.split(|c: char| !c.is_ascii_digit()).next()?,
+        None => name,
+    };
+    if !number.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok().filter(|day| (1..=25).contains(day))
+}
+
+/// Where inputs that aren't cached yet come from
+pub trait Fetch {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String>;
+}
+
+/// Downloads inputs from the puzzle site, or a stand-in for it, with the
+/// session cookie of an account
+#[cfg(feature = "fetch")]
+pub struct Http {
+    base_url: String,
+    session: String,
+}
+
+#[cfg(feature = "fetch")]
+impl Http {
+    pub const SITE: &'static str = "https://adventofcode.com";
+
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Http {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "fetch")]
+impl Fetch for Http {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "aoc solutions runner")
+            .call()
+            .map_err(|error| match error {
+                ureq::Error::Status(status, _) => format!("fetching {url} failed with {status}"),
+                error => format!("can't fetch {url}: {error}"),
+            })?;
+        response
+            .into_string()
+            .map_err(|error| format!("can't read {url}: {error}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    // Empty directory of the test in the system's temporary directory
+    fn scratch(name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("aoc-inputs-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    struct Counting(Cell<usize>);
+
+    impl Fetch for Counting {
+        fn fetch(&self, year: u16, day: u8) -> Result<String, String> {
+            self.0.set(self.0.get() + 1);
+            Ok(format!("{year} {day}\n"))
+        }
+    }
+
+    #[test]
+    fn it_reads_days_from_names() {
+        assert_eq!(day_of("day-05-fertilizer"), Some(5));
+        assert_eq!(day_of("2023-day-17"), Some(17));
+        assert_eq!(day_of("2023-12-day-03"), Some(3));
+        assert_eq!(day_of("input-v2-day-05"), Some(5));
+        assert_eq!(day_of("25"), Some(25));
+        assert_eq!(day_of("input"), None);
+        assert_eq!(day_of("input-v2"), None);
+        assert_eq!(day_of("+5"), None);
+        assert_eq!(day_of("day-26"), None);
+    }
+
+    #[test]
+    fn it_keeps_profiles_apart() {
+        let cache = Cache::new(scratch("profiles"));
+        assert_eq!(cache.find(2023, "alice", 17), Ok(None));
+        let path = cache.store(2023, "alice", 17, "123\n").unwrap();
+        cache.store(2023, "bob", 17, "456\n").unwrap();
+        cache.store(2023, "bob", 3, "789\n").unwrap();
+
+        assert_eq!(path, cache.root().join("2023/alice/day-17.txt"));
+        assert_eq!(cache.find(2023, "alice", 17), Ok(Some(path)));
+        assert_eq!(cache.find(2022, "alice", 17), Ok(None));
+        assert_eq!(
+            cache.profiles(2023).unwrap(),
+            BTreeMap::from([
+                ("alice".to_string(), vec![17]),
+                ("bob".to_string(), vec![3, 17])
+            ])
+        );
+        assert!(cache.profiles(2015).unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_imports_named_files_and_day_directories() {
+        let source = scratch("import-source");
+        fs::write(source.join("day-01.txt"), "1abc2\n").unwrap();
+        fs::write(source.join("notes.txt"), "not an input").unwrap();
+        fs::create_dir(source.join("day-17-heat-loss")).unwrap();
+        fs::write(source.join("day-17-heat-loss/input.txt"), "2413\n").unwrap();
+        fs::create_dir(source.join("day-18-lagoon")).unwrap();
+
+        let cache = Cache::new(scratch("import"));
+        let imported = cache.import(2023, DEFAULT_PROFILE, &source).unwrap();
+        assert_eq!(
+            imported.iter().map(|(day, _)| *day).collect::<Vec<u8>>(),
+            [1, 17]
+        );
+        let path = cache.find(2023, DEFAULT_PROFILE, 17).unwrap().unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "2413\n");
+
+        fs::write(source.join("17.txt"), "9999\n").unwrap();
+        assert!(cache.import(2023, DEFAULT_PROFILE, &source).is_err());
+    }
+
+    #[test]
+    fn it_fetches_only_missing_inputs() {
+        let cache = Cache::new(scratch("fetch"));
+        let fetcher = Counting(Cell::new(0));
+        let path = cache.fetch(&fetcher, 2023, "alice", 17).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2023 17\n");
+        assert_eq!(cache.fetch(&fetcher, 2023, "alice", 17).unwrap(), path);
+        assert_eq!(fetcher.0.get(), 1);
+        cache.fetch(&fetcher, 2023, "bob", 17).unwrap();
+        assert_eq!(fetcher.0.get(), 2);
+    }
+
+    #[test]
+    fn it_reads_sessions_of_profiles() {
+        let cache = Cache::new(scratch("sessions"));
+        fs::create_dir(cache.root().join("sessions")).unwrap();
+        fs::write(cache.root().join("sessions/alice"), "53616c7465\n").unwrap();
+        assert_eq!(cache.session("alice").unwrap(), "53616c7465");
+    }
+
+    #[test]
+    fn it_keeps_profiles_inside_the_cache() {
+        let cache = Cache::new(scratch("escape"));
+        for profile in ["", ".", "../alice", "alice/bob", "alice\\bob", ".."] {
+            assert!(cache.path(2023, profile, 17).is_err(), "{profile}");
+            assert!(
+                cache.store(2023, profile, 17, "123\n").is_err(),
+                "{profile}"
+            );
+            assert!(cache.session(profile).is_err(), "{profile}");
+        }
+        assert!(!cache.root().join("2023/day-17.txt").exists());
+    }
+}
@@ -0,0 +1,608 @@
+use std::io::{IsTerminal, Read};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use aoc::answers::verify_day;
+use aoc::bench::{self, Measurement};
+use aoc::inputs::{self, Cache};
+use aoc::registry;
+use aoc_core::input::{open_input, read_input};
+use aoc_core::parallel;
+use aoc_core::visualize::Painter;
+use aoc_core::{Calendar, Solution, Stopwatch};
+use clap::{Parser, Subcommand};
+
+use crate::export::{Export, ImageFormat};
+use crate::output::{Format, Record, Reporter};
+use crate::selection::{DaySelection, PartSelection};
+
+mod export;
+mod output;
+mod selection;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Year of the puzzles, defaults to the latest registered year
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    /// Threads to run days and their independent work on, needs the `parallel` feature
+    #[arg(long, global = true)]
+    threads: Option<NonZeroUsize>,
+    /// Account whose cached inputs are used, to check answers of several accounts side by side
+    #[arg(long, global = true, default_value = inputs::DEFAULT_PROFILE)]
+    profile: String,
+    /// Directory of the input cache, defaults to `$AOC_CACHE_DIR` or `~/.cache/aoc`
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day (or `all` days) on its puzzle input
+    Run {
+        /// Day number between 1 and 25, or `all`
+        day: DaySelection,
+        /// Part 1, 2, or `all`
+        #[arg(default_value = "all")]
+        part: PartSelection,
+        /// Input file or `-` for stdin, defaults to the cached input or `<day directory>/input.txt`
+        input: Option<PathBuf>,
+        /// Output format for the answers
+        #[arg(long, value_enum, default_value = "text")]
+        format: Format,
+        /// Draw each part's solution after its answer, for the days that support it
+        #[arg(long)]
+        visualize: bool,
+        /// Directory to write images of each part's solution to, needs the `render` feature
+        #[arg(long)]
+        export: Option<PathBuf>,
+        /// Format of the exported images
+        #[arg(long, value_enum, default_value = "svg")]
+        image_format: ImageFormat,
+    },
+    /// Print a random puzzle input for a day, or write one for `all` days
+    Generate {
+        /// Day number between 1 and 25, or `all`
+        day: DaySelection,
+        /// Seed of the input, a random one is picked and reported otherwise
+        #[arg(long)]
+        seed: Option<u64>,
+        /// How large the input is, its meaning depends on the day
+        #[arg(long, default_value_t = 20)]
+        size: usize,
+        /// File to write the input to, or directory for `all` days
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Check a day (or `all` days) against the answers recorded in its `answers.toml`
+    Verify {
+        /// Day number between 1 and 25, or `all`
+        #[arg(default_value = "all")]
+        day: DaySelection,
+    },
+    /// Time parsing and solving of a day (or `all` days) and compare with earlier runs
+    Bench {
+        /// Day number between 1 and 25, or `all`
+        #[arg(default_value = "all")]
+        day: DaySelection,
+        /// Part 1, 2, or `all`
+        #[arg(default_value = "all")]
+        part: PartSelection,
+        /// How many times each part is solved, the median is reported
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+        /// Flag parts that got slower than the previous run by more than this percentage
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// File keeping the measurements of previous runs
+        #[arg(long, default_value = bench::HISTORY_FILE)]
+        history: PathBuf,
+        /// Compare with previous runs without recording this one
+        #[arg(long)]
+        no_save: bool,
+    },
+    /// List the registered days of the year
+    List,
+    /// Manage the puzzle inputs cached for each year, profile and day
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum InputsCommand {
+    /// Show the days cached for every profile
+    List,
+    /// Copy inputs into the profile, from `day-NN.txt` files or day directories holding an `input.txt`
+    Import {
+        /// Directory holding the inputs
+        directory: PathBuf,
+    },
+    /// Download the input of a day (or `all` days) that isn't cached yet, needs the `fetch` feature
+    Fetch {
+        /// Day number between 1 and 25, or `all`
+        day: DaySelection,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(threads) = cli.threads {
+        if let Err(message) = parallel::set_threads(threads.get()) {
+            eprintln!("error: {message}");
+            return ExitCode::FAILURE;
+        }
+    }
+    let calendar = match cli.year {
+        Some(year) => match registry::calendar(year) {
+            Some(calendar) => calendar,
+            None => {
+                eprintln!("error: year {year} is not registered");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => registry::latest(),
+    };
+    let source = InputSource {
+        calendar,
+        cache: cli.cache_dir.or_else(Cache::default_root).map(Cache::new),
+        profile: cli.profile,
+    };
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            visualize,
+            export,
+            image_format,
+        } => {
+            let export = export.map(|directory| Export {
+                directory,
+                format: image_format,
+            });
+            run(day, part, input, format, visualize, export, source)
+        }
+        Command::Generate {
+            day,
+            seed,
+            size,
+            output,
+        } => generate(calendar, day, seed, size, output),
+        Command::Verify { day } => verify(calendar, day),
+        Command::Bench {
+            day,
+            part,
+            runs,
+            threshold,
+            history,
+            no_save,
+        } => run_bench(day, part, runs, threshold, &history, !no_save, &source),
+        Command::List => {
+            list(calendar);
+            Ok(0)
+        }
+        Command::Inputs { command } => manage_inputs(command, &source),
+    };
+
+    match result {
+        Ok(0) => ExitCode::SUCCESS,
+        // every failed part has already been reported
+        Ok(_failures) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// What running a day shows, kept so that days solved at the same time are
+// still shown in day order
+enum Event {
+    Answer(Record),
+    Drawing(String),
+    Wrote(PathBuf),
+    Failure(String),
+}
+
+// Where days of the year read their input from when no file is given
+struct InputSource {
+    calendar: &'static dyn Calendar,
+    cache: Option<Cache>,
+    profile: String,
+}
+
+impl InputSource {
+    fn cache(&self) -> Result<&Cache, String> {
+        self.cache.as_ref().ok_or(format!(
+            "no input cache directory, set {} or pass --cache-dir",
+            inputs::CACHE_DIR_VAR
+        ))
+    }
+
+    // The cached input of the profile, only the default profile falls back
+    // to the input next to the day's code
+    fn path(&self, solution: &dyn Solution) -> Result<PathBuf, String> {
+        let cached = self
+            .cache
+            .as_ref()
+            .map(|cache| cache.find(self.calendar.year(), &self.profile, solution.day()))
+            .transpose()?
+            .flatten();
+        match cached {
+            Some(path) => Ok(path),
+            None if self.profile == inputs::DEFAULT_PROFILE => Ok(self
+                .calendar
+                .directory()
+                .join(solution.directory())
+                .join("input.txt")),
+            None => Err(format!(
+                "day {} has no input cached for profile `{}`, import or fetch it first",
+                solution.day(),
+                self.profile
+            )),
+        }
+    }
+}
+
+struct RunSettings {
+    part: PartSelection,
+    input: Option<PathBuf>,
+    source: InputSource,
+    visualize: bool,
+    export: Option<Export>,
+    painter: Painter,
+}
+
+fn run(
+    day: DaySelection,
+    part: PartSelection,
+    input: Option<PathBuf>,
+    format: Format,
+    visualize: bool,
+    export: Option<Export>,
+    source: InputSource,
+) -> Result<usize, String> {
+    let mut failures = 0;
+    let mut reporter = Reporter::new(format);
+    if day == DaySelection::All && input.is_some() {
+        return Err("an input file can only be given for a single day".to_string());
+    }
+    if visualize && format != Format::Text {
+        return Err("--visualize only works with the text format".to_string());
+    }
+    if let Some(export) = &export {
+        export.prepare()?;
+    }
+    // colours only make sense on a terminal, see https://no-color.org
+    let painter =
+        Painter::new(std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none());
+    let settings = RunSettings {
+        part,
+        input,
+        source,
+        visualize,
+        export,
+        painter,
+    };
+
+    let solutions = select(settings.source.calendar, day)?;
+    for events in parallel::map(&solutions, |solution| run_day(*solution, &settings)) {
+        // a day that can't run, such as one without an input, doesn't stop
+        // the days after it
+        let events = events.unwrap_or_else(|message| vec![Event::Failure(message)]);
+        for event in events {
+            match event {
+                Event::Answer(record) => reporter.report(&record)?,
+                Event::Drawing(drawing) => println!("{drawing}"),
+                Event::Wrote(path) => eprintln!("wrote {}", path.display()),
+                Event::Failure(message) => {
+                    eprintln!("error: {message}");
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    Ok(failures)
+}
+
+fn run_day(solution: &dyn Solution, settings: &RunSettings) -> Result<Vec<Event>, String> {
+    let mut events = vec![];
+    let input_path = match &settings.input {
+        Some(input) => input.clone(),
+        None => settings.source.path(solution)?,
+    };
+    let can_read = |error| format!("can't read {}: {error}", input_path.display());
+    let mut reader = open_input(&input_path).map_err(can_read)?;
+
+    // drawings need the whole input, otherwise days that can solve while
+    // reading never hold it in memory, and only solve the selected parts
+    let mut stopwatch = Stopwatch::new(&settings.part.parts());
+    let streamed = match settings.visualize || settings.export.is_some() {
+        true => None,
+        false => solution.stream(&mut reader, &mut stopwatch),
+    };
+    let mut contents = String::new();
+    if streamed.is_none() {
+        reader.read_to_string(&mut contents).map_err(can_read)?;
+    }
+
+    for selected_part in settings.part.parts() {
+        let start = Instant::now();
+        let solved = match &streamed {
+            Some(Ok(streamed)) => streamed
+                .get(selected_part)
+                .map_err(|error| error.to_string().into()),
+            Some(Err(error)) => Err(error.to_string().into()),
+            None => solution.solve(selected_part, &contents),
+        };
+        let elapsed = match streamed {
+            Some(_) => stopwatch.elapsed(selected_part),
+            None => start.elapsed(),
+        };
+        match &solved {
+            Ok(Some(answer)) => {
+                events.push(Event::Answer(Record::new(
+                    settings.source.calendar.year(),
+                    solution.day(),
+                    selected_part,
+                    &input_path,
+                    answer,
+                    elapsed,
+                )));
+                if let Some(export) = &settings.export {
+                    match export.write(solution, selected_part, &contents) {
+                        Ok(Some(path)) => events.push(Event::Wrote(path)),
+                        Ok(None) => {}
+                        Err(error) => events.push(Event::Failure(format!(
+                            "exporting day {:02} part {selected_part}: {error}",
+                            solution.day()
+                        ))),
+                    }
+                }
+            }
+            Ok(None) if settings.part == PartSelection::All => continue,
+            Ok(None) => {
+                return Err(format!(
+                    "day {} has no part {selected_part}",
+                    solution.day()
+                ))
+            }
+            Err(error) => {
+                events.push(Event::Failure(format!(
+                    "day {:02} part {selected_part} in {}: {error}",
+                    solution.day(),
+                    input_path.display()
+                )));
+            }
+        }
+
+        // a drawing of an input the part fails on helps finding what's wrong
+        // with it, unless the input can't even be parsed, which the part's
+        // failure already tells
+        if settings.visualize {
+            match solution.visualize(selected_part, &contents, &settings.painter) {
+                Ok(Some(drawing)) => events.push(Event::Drawing(drawing)),
+                Ok(None) => {}
+                Err(_) if solved.is_err() => {}
+                Err(error) => events.push(Event::Failure(format!(
+                    "drawing day {:02} part {selected_part}: {error}",
+                    solution.day()
+                ))),
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+fn generate(
+    calendar: &dyn Calendar,
+    day: DaySelection,
+    seed: Option<u64>,
+    size: usize,
+    output: Option<PathBuf>,
+) -> Result<usize, String> {
+    let seed = seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        eprintln!("seed {seed}");
+        seed
+    });
+    let write = |path: &Path, contents: &str| {
+        std::fs::write(path, contents)
+            .map_err(|error| format!("can't write {}: {error}", path.display()))
+    };
+
+    for solution in select(calendar, day)? {
+        let contents = solution
+            .generate(seed, size)
+            .ok_or(format!("day {} has no input generator", solution.day()))?;
+        match (&output, day) {
+            (None, DaySelection::All) => {
+                return Err("generating `all` days needs an --output directory".to_string())
+            }
+            (None, _) => println!("{contents}"),
+            (Some(file), DaySelection::Day(_)) => write(file, &contents)?,
+            (Some(directory), DaySelection::All) => {
+                std::fs::create_dir_all(directory)
+                    .map_err(|error| format!("can't create {}: {error}", directory.display()))?;
+                write(
+                    &directory.join(format!("{}.txt", solution.directory())),
+                    &contents,
+                )?
+            }
+        }
+    }
+
+    Ok(0)
+}
+
+fn verify(calendar: &dyn Calendar, day: DaySelection) -> Result<usize, String> {
+    let mut passed = 0;
+    let mut failures = 0;
+    let root = calendar.directory();
+    for checks in parallel::map(&select(calendar, day)?, |solution| {
+        verify_day(calendar.year(), *solution, &root)
+    }) {
+        for check in checks? {
+            println!("{check}");
+            if check.outcome.is_pass() {
+                passed += 1;
+            } else {
+                failures += 1;
+            }
+        }
+    }
+    println!("{passed} passed, {failures} failed");
+
+    Ok(failures)
+}
+
+fn run_bench(
+    day: DaySelection,
+    part: PartSelection,
+    runs: usize,
+    threshold: f64,
+    history_path: &Path,
+    save: bool,
+    source: &InputSource,
+) -> Result<usize, String> {
+    let history = bench::load_history(history_path)?;
+    let mut measurements: Vec<Measurement> = vec![];
+    let mut failures = 0;
+    let mut slowdowns = 0;
+
+    for solution in select(source.calendar, day)? {
+        let input_path = source.path(solution)?;
+        let contents = read_input(&input_path)
+            .map_err(|error| format!("can't read {}: {error}", input_path.display()))?;
+
+        for selected_part in part.parts() {
+            let measurement = match bench::measure(
+                source.calendar.year(),
+                solution,
+                selected_part,
+                &input_path,
+                &contents,
+                runs,
+            ) {
+                Ok(Some((_answer, measurement))) => measurement,
+                Ok(None) => continue,
+                Err(error) => {
+                    eprintln!(
+                        "error: day {:02} part {selected_part} in {}: {error}",
+                        solution.day(),
+                        input_path.display()
+                    );
+                    failures += 1;
+                    continue;
+                }
+            };
+
+            let comparison = match bench::previous(&history, &measurement) {
+                Some(previous) => {
+                    let change = measurement.change_since(previous) * 100.0;
+                    let flag = if change > threshold {
+                        slowdowns += 1;
+                        " SLOWER"
+                    } else {
+                        ""
+                    };
+                    format!(
+                        " (previously {:.1?}, {change:+.1}%){flag}",
+                        previous.parse() + previous.solve()
+                    )
+                }
+                None => String::new(),
+            };
+            println!(
+                "Day {:02} part {selected_part}: parse {:.1?}, solve {:.1?}{comparison}",
+                measurement.day,
+                measurement.parse(),
+                measurement.solve()
+            );
+            measurements.push(measurement);
+        }
+    }
+
+    if slowdowns > 0 {
+        println!("{slowdowns} part(s) slower by more than {threshold}%");
+    }
+    if save {
+        bench::append_history(history_path, &measurements)?;
+    }
+
+    Ok(failures)
+}
+
+fn list(calendar: &dyn Calendar) {
+    for solution in calendar.solutions() {
+        println!("Day {:02}: {}", solution.day(), solution.title());
+    }
+}
+
+fn select(
+    calendar: &dyn Calendar,
+    day: DaySelection,
+) -> Result<Vec<&'static dyn Solution>, String> {
+    match day {
+        DaySelection::All => Ok(calendar.solutions()),
+        DaySelection::Day(day) => Ok(vec![calendar.find(day).ok_or(format!(
+            "day {day} of {} is not registered",
+            calendar.year()
+        ))?]),
+    }
+}
+
+fn manage_inputs(command: InputsCommand, source: &InputSource) -> Result<usize, String> {
+    let cache = source.cache()?;
+    let year = source.calendar.year();
+    match command {
+        InputsCommand::List => {
+            println!("{}", cache.root().display());
+            for (profile, days) in cache.profiles(year)? {
+                let days: Vec<String> = days.iter().map(|day| format!("{day:02}")).collect();
+                println!("{year} {profile}: {}", days.join(" "));
+            }
+        }
+        InputsCommand::Import { directory } => {
+            let imported = cache.import(year, &source.profile, &directory)?;
+            if imported.is_empty() {
+                return Err(format!("no inputs found in {}", directory.display()));
+            }
+            for (day, path) in imported {
+                println!("day {day:02}: {}", path.display());
+            }
+        }
+        InputsCommand::Fetch { day } => {
+            let fetcher = fetcher(cache, &source.profile)?;
+            for solution in select(source.calendar, day)? {
+                let path = cache.fetch(fetcher.as_ref(), year, &source.profile, solution.day())?;
+                println!("day {:02}: {}", solution.day(), path.display());
+            }
+        }
+    }
+
+    Ok(0)
+}
+
+#[cfg(feature = "fetch")]
+fn fetcher(cache: &Cache, profile: &str) -> Result<Box<dyn inputs::Fetch>, String> {
+    let session = cache.session(profile)?;
+    Ok(Box::new(inputs::Http::new(inputs::Http::SITE, &session)))
+}
+
+#[cfg(not(feature = "fetch"))]
+fn fetcher(_cache: &Cache, _profile: &str) -> Result<Box<dyn inputs::Fetch>, String> {
+    Err("fetching inputs needs the `fetch` feature, rebuild with `--features fetch`".to_string())
+}
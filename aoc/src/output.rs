@@ -0,0 +1,119 @@
+use std::io::{self, Stdout};
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_core::{Answer, Part};
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+    pub answer_type: &'static str,
+    pub elapsed_ns: u128,
+}
+
+impl Record {
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: Part,
+        input: &Path,
+        answer: &Answer,
+        elapsed: Duration,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            part: part.number(),
+            input: input.display().to_string(),
+            answer: answer.to_string(),
+            answer_type: answer.type_name(),
+            elapsed_ns: elapsed.as_nanos(),
+        }
+    }
+}
+
+pub enum Reporter {
+    Text,
+    Json,
+    Csv(Box<csv::Writer<Stdout>>),
+}
+
+impl Reporter {
+    pub fn new(format: Format) -> Self {
+        match format {
+            Format::Text => Reporter::Text,
+            Format::Json => Reporter::Json,
+            Format::Csv => Reporter::Csv(Box::new(csv::Writer::from_writer(io::stdout()))),
+        }
+    }
+
+    pub fn report(&mut self, record: &Record) -> Result<(), String> {
+        match self {
+            Reporter::Text => {
+                println!(
+                    "Day {:02} part {}: {}",
+                    record.day, record.part, record.answer
+                );
+            }
+            Reporter::Json => {
+                let line = serde_json::to_string(record).map_err(|error| error.to_string())?;
+                println!("{line}");
+            }
+            Reporter::Csv(writer) => {
+                writer
+                    .serialize(record)
+                    .and_then(|_| writer.flush().map_err(csv::Error::from))
+                    .map_err(|error| error.to_string())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Record {
+        Record::new(
+            2023,
+            19,
+            Part::Two,
+            Path::new("day-19-rules/input.txt"),
+            &Answer::U128(167409079868000),
+            Duration::from_micros(1500),
+        )
+    }
+
+    #[test]
+    fn it_serializes_json() {
+        assert_eq!(
+            serde_json::to_string(&record()).unwrap(),
+            r#"{"year":2023,"day":19,"part":2,"input":"day-19-rules/input.txt","answer":"167409079868000","answer_type":"u128","elapsed_ns":1500000}"#
+        );
+    }
+
+    #[test]
+    fn it_serializes_csv() {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.serialize(record()).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+            "year,day,part,input,answer,answer_type,elapsed_ns\n2023,19,2,day-19-rules/input.txt,167409079868000,u128,1500000\n"
+        );
+    }
+}
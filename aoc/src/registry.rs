@@ -0,0 +1,47 @@
+use aoc_2023::Advent2023;
+use aoc_core::{Calendar, Solution};
+
+/// Every year the runner knows about, in year order
+pub fn calendars() -> Vec<&'static dyn Calendar> {
+    vec![&Advent2023]
+}
+
+pub fn calendar(year: u16) -> Option<&'static dyn Calendar> {
+    calendars()
+        .into_iter()
+        .find(|calendar| calendar.year() == year)
+}
+
+/// The most recent year, picked when none is asked for
+pub fn latest() -> &'static dyn Calendar {
+    calendars()
+        .into_iter()
+        .max_by_key(|calendar| calendar.year())
+        .expect("at least one year should be registered")
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static dyn Solution> {
+    calendar(year)?.find(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_registers_every_year_once() {
+        let years: Vec<u16> = calendars().iter().map(|calendar| calendar.year()).collect();
+        assert!(years.windows(2).all(|pair| pair[0] < pair[1]), "{years:?}");
+        assert_eq!(latest().year(), *years.last().unwrap());
+    }
+
+    #[test]
+    fn it_finds_days_by_year() {
+        assert_eq!(
+            find(2023, 7).map(|solution| solution.title()),
+            Some("camel-cards")
+        );
+        assert!(find(2023, 26).is_none());
+        assert!(find(1999, 7).is_none());
+    }
+}
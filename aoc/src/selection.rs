@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use aoc_core::Part;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DaySelection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "all" {
+            return Ok(DaySelection::All);
+        }
+
+        match value.parse::<u8>() {
+            Ok(day) if (1..=25).contains(&day) => Ok(DaySelection::Day(day)),
+            _ => Err(format!(
+                "expected a day between 1 and 25 or `all`, got `{value}`"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PartSelection {
+    All,
+    Part(Part),
+}
+
+impl PartSelection {
+    pub fn parts(&self) -> Vec<Part> {
+        match self {
+            PartSelection::All => Part::ALL.to_vec(),
+            PartSelection::Part(part) => vec![*part],
+        }
+    }
+}
+
+impl FromStr for PartSelection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "all" => Ok(PartSelection::All),
+            "1" => Ok(PartSelection::Part(Part::One)),
+            "2" => Ok(PartSelection::Part(Part::Two)),
+            _ => Err(format!("expected part 1, 2 or `all`, got `{value}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_days() {
+        assert_eq!("all".parse(), Ok(DaySelection::All));
+        assert_eq!("7".parse(), Ok(DaySelection::Day(7)));
+        assert_eq!("07".parse(), Ok(DaySelection::Day(7)));
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("seven".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn it_parses_parts() {
+        assert_eq!("all".parse::<PartSelection>().unwrap().parts(), Part::ALL);
+        assert_eq!("2".parse::<PartSelection>().unwrap().parts(), [Part::Two]);
+        assert!("3".parse::<PartSelection>().is_err());
+    }
+}
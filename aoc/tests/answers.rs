@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+use aoc::answers::{check_answer, load_answers, verify_input};
+use aoc::registry;
+use aoc_core::input::read_input;
+use aoc_core::{Part, Solution};
+
+// Directory of the year's day directories and the day's solution
+fn registered(year: u16, day: u8) -> (PathBuf, &'static dyn Solution) {
+    let calendar = registry::calendar(year).expect("year should be registered");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(calendar.directory());
+    (root, calendar.find(day).expect("day should be registered"))
+}
+
+fn verify(year: u16, day: u8, input: &str) {
+    let (root, solution) = registered(year, day);
+    let answers = load_answers(&root.join(solution.directory())).unwrap();
+    let failures: Vec<String> = verify_input(year, solution, &root, input, &answers[input])
+        .iter()
+        .filter(|check| !check.outcome.is_pass())
+        .map(ToString::to_string)
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+// Same answers with a byte order mark, CRLF line breaks and trailing newlines
+fn verify_crlf(year: u16, day: u8, input: &str) {
+    let (root, solution) = registered(year, day);
+    let directory = root.join(solution.directory());
+    let answers = load_answers(&directory).unwrap();
+    let contents = read_input(directory.join(format!("{input}.txt"))).unwrap();
+    let contents = format!("\u{feff}{}\r\n\r\n", contents.replace('\n', "\r\n"));
+
+    for part in Part::ALL {
+        if let Some(expected) = answers[input].get(part) {
+            let outcome = check_answer(solution, part, &contents, expected);
+            assert!(
+                outcome.is_pass(),
+                "{year} day {day} part {part}: {outcome:?}"
+            );
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/answers.rs"));
@@ -0,0 +1,52 @@
+// The HTTP backend is only built with the `fetch` feature
+#![cfg(feature = "fetch")]
+
+use std::thread;
+
+use aoc::inputs::{Fetch, Http};
+use tiny_http::{Header, Response, Server};
+
+const SESSION: &str = "53616c7465645f5f";
+
+// Stand-in for the puzzle site serving day 17 of 2023 to one session, for
+// as many requests as are expected
+fn serve(requests: usize) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    thread::spawn(move || {
+        for request in server.incoming_requests().take(requests) {
+            let signed_in = request.headers().iter().any(|header| {
+                header.field.equiv("Cookie")
+                    && header.value.as_str() == format!("session={SESSION}")
+            });
+            let response = match (signed_in, request.url()) {
+                (false, _) => {
+                    Response::from_string("Puzzle inputs differ by user.").with_status_code(400)
+                }
+                (true, "/2023/day/17/input") => {
+                    Response::from_string("2413432311323\n3215453535623\n")
+                        .with_header(Header::from_bytes("Content-Type", "text/plain").unwrap())
+                }
+                (true, _) => Response::from_string("Not found").with_status_code(404),
+            };
+            request.respond(response).unwrap();
+        }
+    });
+    url
+}
+
+#[test]
+fn it_fetches_inputs_with_the_session() {
+    let url = serve(1);
+    let input = Http::new(&url, SESSION).fetch(2023, 17).unwrap();
+    assert_eq!(input, "2413432311323\n3215453535623\n");
+}
+
+#[test]
+fn it_reports_failed_fetches() {
+    let url = serve(2);
+    let error = Http::new(&url, "expired").fetch(2023, 17).unwrap_err();
+    assert!(error.contains("failed with 400"), "{error}");
+    let error = Http::new(&url, SESSION).fetch(2023, 26).unwrap_err();
+    assert!(error.contains("failed with 404"), "{error}");
+}
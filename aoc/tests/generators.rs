@@ -0,0 +1,26 @@
+use aoc::registry;
+use aoc_core::Part;
+
+// Every generated input should parse and solve, whatever the seed
+#[test]
+fn generated_inputs_solve() {
+    for solution in registry::calendars()
+        .into_iter()
+        .flat_map(|calendar| calendar.solutions())
+    {
+        for seed in 0..3 {
+            let input = solution
+                .generate(seed, 10)
+                .unwrap_or_else(|| panic!("day {} has no generator", solution.day()));
+            assert_eq!(solution.generate(seed, 10).as_ref(), Some(&input));
+            for part in Part::ALL {
+                if let Err(error) = solution.solve(part, &input) {
+                    panic!(
+                        "day {} part {part} with seed {seed}: {error}\n{input}",
+                        solution.day()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::env;
+use std::fs;
+use std::process::Command;
+
+// Only day 03 is cached for the profile, the other days fail one by one
+#[test]
+fn runner_goes_on_after_a_day_fails() {
+    let cache = env::temp_dir().join(format!("aoc-run-all-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&cache);
+    fs::create_dir_all(cache.join("2023/alice")).unwrap();
+    fs::write(
+        cache.join("2023/alice/day-03.txt"),
+        "467..114..\n...*......\n..35..633.\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "all", "1", "--year", "2023", "--profile", "alice"])
+        .arg("--cache-dir")
+        .arg(&cache)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&cache).unwrap();
+
+    assert!(!output.status.success(), "{output:?}");
+    let answers = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(answers, "Day 03 part 1: 502\n");
+    let errors = String::from_utf8(output.stderr).unwrap();
+    assert!(errors.contains("day 1 has no input cached"), "{errors}");
+    assert!(errors.contains("day 25 has no input cached"), "{errors}");
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+use std::process::Command;
+
+use aoc::answers::load_answers;
+use aoc::registry;
+use aoc_core::input::open_input;
+use aoc_core::{Part, Solution, Stopwatch};
+
+const YEAR: u16 = 2023;
+const STREAMING_DAYS: [u8; 8] = [1, 2, 4, 7, 9, 12, 15, 18];
+
+// Streams `input` and compares each part with solving it whole, errors
+// only have to happen on the same parts
+fn assert_same_answers(solution: &dyn Solution, input: &str, origin: &str) -> bool {
+    let Some(streamed) = solution.stream(&mut input.as_bytes(), &mut Stopwatch::new(&Part::ALL))
+    else {
+        return false;
+    };
+    let day = solution.day();
+    match streamed {
+        Ok(streamed) => {
+            for part in Part::ALL {
+                let solved = solution.solve(part, input);
+                match (streamed.get(part), solved) {
+                    (Ok(streamed), Ok(solved)) => {
+                        assert_eq!(streamed, solved, "day {day} part {part} of {origin}")
+                    }
+                    (Err(_), Err(_)) => {}
+                    (streamed, solved) => panic!(
+                        "day {day} part {part} of {origin}: streamed {streamed:?}, solved {solved:?}"
+                    ),
+                }
+            }
+        }
+        Err(error) => {
+            for part in Part::ALL {
+                assert!(
+                    solution.solve(part, input).is_err(),
+                    "day {day} part {part} of {origin} only fails streamed: {error}"
+                );
+            }
+        }
+    }
+
+    true
+}
+
+#[test]
+fn line_oriented_days_stream() {
+    let streaming: Vec<u8> = registry::calendar(YEAR)
+        .unwrap()
+        .solutions()
+        .into_iter()
+        .filter(|solution| assert_same_answers(*solution, "", "empty input"))
+        .map(|solution| solution.day())
+        .collect();
+    assert_eq!(streaming, STREAMING_DAYS);
+}
+
+#[test]
+fn streamed_inputs_match_recorded_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(registry::calendar(YEAR).unwrap().directory());
+    for day in STREAMING_DAYS {
+        let solution = registry::find(YEAR, day).unwrap();
+        let directory = root.join(solution.directory());
+        for (input, expected) in load_answers(&directory).unwrap() {
+            let mut reader = open_input(directory.join(format!("{input}.txt"))).unwrap();
+            let streamed = solution
+                .stream(&mut reader, &mut Stopwatch::new(&Part::ALL))
+                .unwrap()
+                .unwrap();
+            for part in Part::ALL {
+                let Some(expected) = expected.get(part) else {
+                    continue;
+                };
+                let answer = streamed.get(part).unwrap().unwrap();
+                assert_eq!(
+                    answer.to_string(),
+                    expected,
+                    "day {day} part {part} of {input}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn streamed_generated_inputs_match_solved() {
+    for day in STREAMING_DAYS {
+        let solution = registry::find(YEAR, day).unwrap();
+        for seed in 0..3 {
+            let input = solution.generate(seed, 10).unwrap();
+            assert_same_answers(solution, &input, &format!("seed {seed}"));
+            let windows = format!("\u{feff}{}\r\n\r\n", input.replace('\n', "\r\n"));
+            assert_same_answers(solution, &windows, &format!("seed {seed} with CRLF"));
+            for broken in [format!("\n{input}"), input.replacen(' ', "", 1)] {
+                assert_same_answers(solution, &broken, &format!("broken seed {seed}"));
+            }
+        }
+    }
+}
+
+#[test]
+fn streaming_solves_the_selected_part_only() {
+    for day in STREAMING_DAYS {
+        let solution = registry::find(YEAR, day).unwrap();
+        let input = solution.generate(0, 10).unwrap();
+        for part in Part::ALL {
+            let streamed = solution
+                .stream(&mut input.as_bytes(), &mut Stopwatch::new(&[part]))
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                streamed.get(part).unwrap(),
+                solution.solve(part, &input).unwrap(),
+                "day {day} part {part}"
+            );
+            let other = match part {
+                Part::One => Part::Two,
+                Part::Two => Part::One,
+            };
+            assert_eq!(streamed.get(other).unwrap(), None, "day {day} part {other}");
+        }
+    }
+}
+
+// The runner streams day 12, whose part two counts the unfolded records on
+// the threads it's given
+#[test]
+fn runner_streams_day_12_on_threads() {
+    let sample = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(registry::calendar(YEAR).unwrap().directory())
+        .join(registry::find(YEAR, 12).unwrap().directory())
+        .join("sample.txt");
+    let threads = if cfg!(feature = "parallel") { "4" } else { "1" };
+    for (part, expected) in [("1", ",21,"), ("2", ",525152,"), ("all", ",525152,")] {
+        let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["run", "12", part])
+            .arg(&sample)
+            .args(["--format", "csv", "--threads", threads])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "part {part}: {output:?}");
+        let records = String::from_utf8(output.stdout).unwrap();
+        let parts = if part == "all" { 2 } else { 1 };
+        assert_eq!(records.lines().count(), parts + 1, "part {part}: {records}");
+        assert!(records.contains(expected), "part {part}: {records}");
+    }
+}
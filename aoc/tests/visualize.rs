@@ -0,0 +1,30 @@
+use std::env;
+use std::fs;
+use std::process::Command;
+
+// A document whose second line has no digit fails both parts, the report
+// still shows which line it is
+#[test]
+fn runner_draws_parts_that_fail() {
+    let input = env::temp_dir().join(format!("aoc-visualize-{}.txt", std::process::id()));
+    fs::write(&input, "1abc2\nabc\ntwo3\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "1", "all"])
+        .arg(&input)
+        .arg("--visualize")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    fs::remove_file(&input).unwrap();
+
+    assert!(!output.status.success(), "{output:?}");
+    let drawings = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        drawings.matches("line 2: no digit").count(),
+        2,
+        "{drawings}"
+    );
+    let errors = String::from_utf8(output.stderr).unwrap();
+    assert!(errors.contains("error: day 01 part 1"), "{errors}");
+    assert!(errors.contains("error: day 01 part 2"), "{errors}");
+}
@@ -0,0 +1,79 @@
+use aoc_core::Solution;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_01_trebuchet::calibrate::digits_and_text::digits_and_text;
+use day_01_trebuchet::Trebuchet;
+
+const NAMES: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+// The matcher before the trie, scanning the whole line once per word and
+// sorting every match, kept to compare with
+fn scan_per_word(line: &str) -> Option<u32> {
+    let mut digits: Vec<(usize, u32)> = NAMES
+        .iter()
+        .flat_map(|&(name, value)| {
+            line.match_indices(name)
+                .map(move |(index, _)| (index, value))
+        })
+        .collect();
+    digits.extend(
+        line.char_indices()
+            .filter_map(|(index, letter)| Some((index, letter.to_digit(10)?))),
+    );
+    digits.sort_by_key(|digit| digit.0);
+    Some(digits.first()?.1 * 10 + digits.last()?.1)
+}
+
+// Generated documents of many short lines, and of a few long lines where
+// stopping at the digit closest to each end matters most
+fn calibrate(c: &mut Criterion) {
+    let many_lines = Trebuchet.generate(1, 20_000).unwrap();
+    let long_lines = Trebuchet
+        .generate(2, 100)
+        .unwrap()
+        .lines()
+        .map(|line| line.repeat(500))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let mut group = c.benchmark_group("day-01 spelled out digits");
+    for (name, document) in [("many lines", &many_lines), ("long lines", &long_lines)] {
+        assert_eq!(
+            document.lines().map(scan_per_word).collect::<Vec<_>>(),
+            document.lines().map(digits_and_text).collect::<Vec<_>>()
+        );
+        group.bench_with_input(
+            BenchmarkId::new("scan per word", name),
+            document,
+            |b, document| {
+                b.iter(|| {
+                    black_box(document)
+                        .lines()
+                        .filter_map(scan_per_word)
+                        .sum::<u32>()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("trie", name), document, |b, document| {
+            b.iter(|| {
+                black_box(document)
+                    .lines()
+                    .filter_map(digits_and_text)
+                    .sum::<u32>()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, calibrate);
+criterion_main!(benches);
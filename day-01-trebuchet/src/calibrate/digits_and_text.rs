@@ -1,45 +1,20 @@
-use phf::phf_map;
+use lazy_static::lazy_static;
 
-static NUMBER_NAMES: phf::Map<&'static str, u32> = phf_map! {
-    "one" => 1,
-    "two" => 2,
-    "three" => 3,
-    "four" => 4,
-    "five" => 5,
-    "six" => 6,
-    "seven" => 7,
-    "eight" => 8,
-    "nine" => 9,
-};
+use crate::calibrate::vocabulary::Vocabulary;
 
-pub fn digits_and_text(line: &str) -> u32 {
-    let mut digits = NUMBER_NAMES
-        .entries()
-        .map(|(name, value)| {
-            line.match_indices(name)
-                .map(|item| (item.0, value.to_owned()))
-                .collect::<Vec<(usize, u32)>>()
-        })
-        .reduce(|acc, matches| acc.into_iter().chain(matches).collect())
-        .unwrap();
-
-    let literal_digits = line
-        .chars()
-        .enumerate()
-        .filter_map(|(index, letter)| {
-            letter
-                .is_digit(10)
-                .then(|| (index, letter.to_digit(10).unwrap()))
-        })
-        .collect::<Vec<(usize, u32)>>();
+lazy_static! {
+    pub(crate) static ref ENGLISH: Vocabulary = Vocabulary::default();
+}
 
-    digits.extend(literal_digits);
-    digits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+/// Calibration value of a line whose digits may also be spelled out, as in `two1nine`
+pub fn digits_and_text(line: &str) -> Option<u32> {
+    digits_in(&ENGLISH, line)
+}
 
-    let start = digits.first().unwrap().1;
-    let end = digits.last().unwrap().1;
-    let strnum = format!("{start}{end}");
-    strnum.to_string().parse().unwrap()
+/// Calibration value of a line whose digits may be spelled out with the
+/// words of a vocabulary
+pub fn digits_in(vocabulary: &Vocabulary, line: &str) -> Option<u32> {
+    vocabulary.matcher().calibration(line)
 }
 
 #[cfg(test)]
@@ -48,35 +23,48 @@ mod tests {
 
     #[test]
     fn start_and_end_has_digit() {
-        assert_eq!(digits_and_text("one2"), 12);
-        assert_eq!(digits_and_text("a1andtwo"), 12);
-        assert_eq!(digits_and_text("a1s2d"), 12);
-        assert_eq!(digits_and_text("as12d"), 12);
+        assert_eq!(digits_and_text("one2"), Some(12));
+        assert_eq!(digits_and_text("a1andtwo"), Some(12));
+        assert_eq!(digits_and_text("a1s2d"), Some(12));
+        assert_eq!(digits_and_text("as12d"), Some(12));
     }
 
     #[test]
     fn single_digit() {
-        assert_eq!(digits_and_text("1asd"), 11);
-        assert_eq!(digits_and_text("a2sd"), 22);
-        assert_eq!(digits_and_text("asd3"), 33);
+        assert_eq!(digits_and_text("1asd"), Some(11));
+        assert_eq!(digits_and_text("a2sd"), Some(22));
+        assert_eq!(digits_and_text("asd3"), Some(33));
     }
 
     #[test]
     fn single_text_digit() {
-        assert_eq!(digits_and_text("one"), 11);
-        assert_eq!(digits_and_text("two"), 22);
-        assert_eq!(digits_and_text("three"), 33);
-        assert_eq!(digits_and_text("four"), 44);
-        assert_eq!(digits_and_text("five"), 55);
-        assert_eq!(digits_and_text("six"), 66);
-        assert_eq!(digits_and_text("seven"), 77);
-        assert_eq!(digits_and_text("eight"), 88);
-        assert_eq!(digits_and_text("nine"), 99);
+        assert_eq!(digits_and_text("one"), Some(11));
+        assert_eq!(digits_and_text("two"), Some(22));
+        assert_eq!(digits_and_text("three"), Some(33));
+        assert_eq!(digits_and_text("four"), Some(44));
+        assert_eq!(digits_and_text("five"), Some(55));
+        assert_eq!(digits_and_text("six"), Some(66));
+        assert_eq!(digits_and_text("seven"), Some(77));
+        assert_eq!(digits_and_text("eight"), Some(88));
+        assert_eq!(digits_and_text("nine"), Some(99));
     }
 
     #[test]
     fn more_then_two_digits() {
-        assert_eq!(digits_and_text("onetwoasd3e"), 13);
-        assert_eq!(digits_and_text("atwothrees4fived"), 25);
+        assert_eq!(digits_and_text("onetwoasd3e"), Some(13));
+        assert_eq!(digits_and_text("atwothrees4fived"), Some(25));
+    }
+
+    #[test]
+    fn overlapping_text_digits() {
+        assert_eq!(digits_and_text("twone"), Some(21));
+        assert_eq!(digits_and_text("eightwo"), Some(82));
+        assert_eq!(digits_and_text("7pqrstsixteen"), Some(76));
+    }
+
+    #[test]
+    fn no_digits() {
+        assert_eq!(digits_and_text(""), None);
+        assert_eq!(digits_and_text("onx"), None);
     }
 }
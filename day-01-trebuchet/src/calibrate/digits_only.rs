@@ -1,9 +1,9 @@
-pub fn digits_only(line: &str) -> u32 {
-    let numbers = line.chars().filter(|c| c.is_digit(10));
-    let start = numbers.to_owned().next().unwrap();
-    let end = numbers.last().unwrap();
-    let strnum = format!("{start}{end}");
-    strnum.to_string().parse().unwrap()
+/// Calibration value of a line from its first and last digit, `None` without digits
+pub fn digits_only(line: &str) -> Option<u32> {
+    let mut numbers = line.chars().filter_map(|c| c.to_digit(10));
+    let start = numbers.to_owned().next()?;
+    let end = numbers.next_back()?;
+    Some(start * 10 + end)
 }
 
 #[cfg(test)]
@@ -12,22 +12,28 @@ mod tests {
 
     #[test]
     fn start_and_end_has_digit() {
-        assert_eq!(digits_only("1asd2"), 12);
-        assert_eq!(digits_only("a1sd2"), 12);
-        assert_eq!(digits_only("a1s2d"), 12);
-        assert_eq!(digits_only("as12d"), 12);
+        assert_eq!(digits_only("1asd2"), Some(12));
+        assert_eq!(digits_only("a1sd2"), Some(12));
+        assert_eq!(digits_only("a1s2d"), Some(12));
+        assert_eq!(digits_only("as12d"), Some(12));
     }
 
     #[test]
     fn single_digit() {
-        assert_eq!(digits_only("1asd"), 11);
-        assert_eq!(digits_only("a2sd"), 22);
-        assert_eq!(digits_only("asd3"), 33);
+        assert_eq!(digits_only("1asd"), Some(11));
+        assert_eq!(digits_only("a2sd"), Some(22));
+        assert_eq!(digits_only("asd3"), Some(33));
     }
 
     #[test]
     fn more_then_two_digits() {
-        assert_eq!(digits_only("12asd3e"), 13);
-        assert_eq!(digits_only("a23s45d"), 25);
+        assert_eq!(digits_only("12asd3e"), Some(13));
+        assert_eq!(digits_only("a23s45d"), Some(25));
+    }
+
+    #[test]
+    fn no_digits() {
+        assert_eq!(digits_only(""), None);
+        assert_eq!(digits_only("one"), None);
     }
 }
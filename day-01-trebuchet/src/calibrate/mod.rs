@@ -1,2 +1,72 @@
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use aoc_core::input::lines;
+use aoc_core::Span;
+use thiserror::Error;
+
 pub mod digits_and_text;
 pub mod digits_only;
+pub mod report;
+pub mod trie;
+pub mod vocabulary;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("no calibration digit {0}")]
+    MissingDigit(Span),
+}
+
+/// Lines of the calibration document
+#[derive(Debug, PartialEq)]
+pub struct Document(pub Vec<String>);
+
+// lines without digits only fail the parts that need them
+impl FromStr for Document {
+    type Err = Infallible;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(Document(lines(input).map(String::from).collect()))
+    }
+}
+
+/// Sum of the calibration values of all lines, failing on the first line
+/// without one, [`report::report`] lists every such line
+pub fn calibrate(
+    lines: &[String],
+    digits: impl Fn(&str) -> Option<u32>,
+) -> Result<u32, ParseError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| calibrate_line(index, line, &digits))
+        .sum()
+}
+
+/// Calibration value of a single line, the error points at the line
+pub fn calibrate_line(
+    index: usize,
+    line: &str,
+    digits: impl Fn(&str) -> Option<u32>,
+) -> Result<u32, ParseError> {
+    digits(line).ok_or_else(|| ParseError::MissingDigit(Span::line(index, line)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_line_without_digits() {
+        let lines = vec![
+            String::from("1abc2"),
+            String::from("treb7uchet"),
+            String::from("abc"),
+        ];
+        assert_eq!(calibrate(&lines[..2], digits_only::digits_only), Ok(89));
+        assert_eq!(
+            calibrate(&lines, digits_only::digits_only),
+            Err(ParseError::MissingDigit(Span::new(3, 1, "abc")))
+        );
+    }
+}
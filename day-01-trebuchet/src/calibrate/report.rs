@@ -0,0 +1,122 @@
+use std::fmt;
+
+use aoc_core::visualize::{Color, Painter};
+
+use crate::calibrate::trie::{DigitMatcher, Found};
+
+/// First and last digit of a line of the document, `None` without digits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineReport {
+    pub line: usize,
+    pub digits: Option<(Found, Found)>,
+}
+
+impl LineReport {
+    pub fn new(index: usize, line: &str, matcher: &DigitMatcher) -> Self {
+        let digits = matcher.find_first(line).zip(matcher.find_last(line));
+        LineReport {
+            line: index + 1,
+            digits,
+        }
+    }
+
+    /// Calibration value of the line
+    pub fn value(&self) -> Option<u32> {
+        let (first, last) = self.digits.as_ref()?;
+        Some(first.value * 10 + last.value)
+    }
+}
+
+impl fmt::Display for LineReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.digits {
+            Some((first, last)) => write!(
+                f,
+                "line {}: first {first}, last {last}, value {}",
+                self.line,
+                first.value * 10 + last.value
+            ),
+            None => write!(f, "line {}: no digit", self.line),
+        }
+    }
+}
+
+/// Report of every line, lines without digits are kept so they can be found
+pub fn report(lines: &[String], matcher: &DigitMatcher) -> Vec<LineReport> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| LineReport::new(index, line, matcher))
+        .collect()
+}
+
+/// Each line with its first digit in green and last digit in cyan, followed
+/// by its report, lines without digits are red
+pub fn draw_report(lines: &[String], matcher: &DigitMatcher, painter: &Painter) -> String {
+    let mut drawing = vec![];
+    for (line, report) in lines.iter().zip(report(lines, matcher)) {
+        let text = match &report.digits {
+            Some((first, last)) if first.position.end <= last.position.start => [
+                painter.paint(&line[..first.position.start], None),
+                painter.paint(&line[first.position.clone()], Some(Color::Green)),
+                painter.paint(&line[first.position.end..last.position.start], None),
+                painter.paint(&line[last.position.clone()], Some(Color::Cyan)),
+                painter.paint(&line[last.position.end..], None),
+            ]
+            .concat(),
+            // the same digit, or words sharing letters, at both ends
+            Some((first, last)) => [
+                painter.paint(&line[..first.position.start], None),
+                painter.paint(
+                    &line[first.position.start..last.position.end],
+                    Some(Color::Green),
+                ),
+                painter.paint(&line[last.position.end..], None),
+            ]
+            .concat(),
+            None => painter.paint(line, Some(Color::Red)),
+        };
+        drawing.push(format!("{text}\n  {report}"));
+    }
+
+    drawing.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_every_line() {
+        let lines: Vec<String> = ["two1nine", "", "abc", "eightwo"]
+            .map(String::from)
+            .to_vec();
+        let matcher = DigitMatcher::new([("one", 1), ("two", 2), ("eight", 8), ("nine", 9)]);
+        let reports = report(&lines, &matcher);
+        assert_eq!(
+            reports.iter().map(LineReport::value).collect::<Vec<_>>(),
+            [Some(29), None, None, Some(82)]
+        );
+        assert_eq!(
+            reports[0].to_string(),
+            "line 1: first 2 as a word at bytes 0..3, last 9 as a word at bytes 4..8, value 29"
+        );
+        assert_eq!(reports[2].to_string(), "line 3: no digit");
+
+        let literal = report(&lines, &DigitMatcher::new([]));
+        assert_eq!(
+            literal[0].to_string(),
+            "line 1: first 1 as a digit at bytes 3..4, last 1 as a digit at bytes 3..4, value 11"
+        );
+    }
+
+    #[test]
+    fn it_draws_reports() {
+        let lines = vec![String::from("eightwo")];
+        let matcher = DigitMatcher::new([("eight", 8), ("two", 2)]);
+        assert_eq!(
+            draw_report(&lines, &matcher, &Painter::new(false)),
+            "eightwo\n  line 1: first 8 as a word at bytes 0..5, last 2 as a word at bytes 4..7, value 82"
+        );
+    }
+}
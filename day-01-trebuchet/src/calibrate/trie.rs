@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::Range;
+
+// A node per prefix of the words, the digit is set on the nodes ending one.
+// `fail` is the node of the longest suffix of the prefix that is a prefix as
+// well, and `output` the node of the longest such suffix ending a word.
+#[derive(Debug, Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    digit: Option<(u32, Origin)>,
+    depth: usize,
+    fail: usize,
+    output: Option<usize>,
+}
+
+// Aho–Corasick automaton of the words
+#[derive(Debug)]
+struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    fn new() -> Self {
+        Trie {
+            nodes: vec![Node::default()],
+        }
+    }
+
+    fn insert(&mut self, word: impl Iterator<Item = u8>, value: u32, origin: Origin) {
+        let mut node = 0;
+        for byte in word {
+            node = match self.child(node, byte) {
+                Some(child) => child,
+                None => {
+                    let depth = self.nodes[node].depth + 1;
+                    self.nodes.push(Node {
+                        depth,
+                        ..Node::default()
+                    });
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((byte, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].digit = Some((value, origin));
+    }
+
+    // Sets the failure and output links once every word is inserted, parents
+    // before children
+    fn link(&mut self) {
+        let mut queue = VecDeque::from([0]);
+        while let Some(node) = queue.pop_front() {
+            for (byte, child) in self.nodes[node].children.clone() {
+                let fail = match node {
+                    0 => 0,
+                    _ => self.next(self.nodes[node].fail, byte),
+                };
+                self.nodes[child].fail = fail;
+                self.nodes[child].output = match self.nodes[fail].digit {
+                    Some(_) => Some(fail),
+                    None => self.nodes[fail].output,
+                };
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|&&(edge, _)| edge == byte)
+            .map(|&(_, child)| child)
+    }
+
+    // Node of the longest prefix the text read so far ends with
+    fn next(&self, mut node: usize, byte: u8) -> usize {
+        loop {
+            match self.child(node, byte) {
+                Some(child) => return child,
+                None if node == 0 => return 0,
+                None => node = self.nodes[node].fail,
+            }
+        }
+    }
+
+    // Offset, digit and length of the word starting the earliest in `bytes`,
+    // the longest of those starting there. The bytes are read once, and only
+    // until no word can start before the one found.
+    fn earliest(&self, bytes: impl Iterator<Item = u8>) -> Option<(usize, (u32, Origin), usize)> {
+        let mut found: Option<(usize, (u32, Origin), usize)> = None;
+        let mut node = 0;
+        for (offset, byte) in bytes.enumerate() {
+            node = self.next(node, byte);
+            // the words ending here, longest first
+            let mut ending = match self.nodes[node].digit {
+                Some(_) => Some(node),
+                None => self.nodes[node].output,
+            };
+            while let Some(word) = ending {
+                let (length, digit) = (self.nodes[word].depth, self.nodes[word].digit);
+                let start = offset + 1 - length;
+                if found.is_none_or(|(first, _, longest)| {
+                    start < first || (start == first && length > longest)
+                }) {
+                    found = digit.map(|digit| (start, digit, length));
+                }
+                ending = self.nodes[word].output;
+            }
+            // words still to come start within the longest prefix read
+            if let Some((first, _, _)) = found {
+                if offset + 1 - self.nodes[node].depth > first {
+                    break;
+                }
+            }
+        }
+        found
+    }
+}
+
+/// How a digit is written in a line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    Literal,
+    Word,
+}
+
+/// A digit found in a line, `position` is the byte range it's written in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Found {
+    pub value: u32,
+    pub position: Range<usize>,
+    pub origin: Origin,
+}
+
+impl fmt::Display for Found {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let origin = match self.origin {
+            Origin::Literal => "digit",
+            Origin::Word => "word",
+        };
+        write!(
+            f,
+            "{} as a {origin} at bytes {}..{}",
+            self.value, self.position.start, self.position.end
+        )
+    }
+}
+
+/// Finds the first and last digit of a line, written as a digit or spelled
+/// out, with an Aho–Corasick automaton of the words read forwards and one of
+/// the words read backwards. Each end is read once and only until its digit
+/// is found, words sharing letters such
+/// as `twone` count at both ends and the longest of the words starting (or
+/// ending) at the same place wins.
+#[derive(Debug)]
+pub struct DigitMatcher {
+    forwards: Trie,
+    backwards: Trie,
+}
+
+impl DigitMatcher {
+    // Matcher for the digits `0` to `9` and the given spelled out digits,
+    // checked by the vocabulary first
+    pub(crate) fn new<'a>(words: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        let mut forwards = Trie::new();
+        let mut backwards = Trie::new();
+        let digits = (b'0'..=b'9').map(|digit| (digit, u32::from(digit - b'0')));
+        for (digit, value) in digits {
+            forwards.insert([digit].into_iter(), value, Origin::Literal);
+            backwards.insert([digit].into_iter(), value, Origin::Literal);
+        }
+        for (word, value) in words {
+            forwards.insert(word.bytes(), value, Origin::Word);
+            backwards.insert(word.bytes().rev(), value, Origin::Word);
+        }
+        forwards.link();
+        backwards.link();
+        DigitMatcher {
+            forwards,
+            backwards,
+        }
+    }
+
+    pub fn find_first(&self, line: &str) -> Option<Found> {
+        let (start, (value, origin), length) = self.forwards.earliest(line.bytes())?;
+        Some(Found {
+            value,
+            position: start..start + length,
+            origin,
+        })
+    }
+
+    pub fn find_last(&self, line: &str) -> Option<Found> {
+        let (from_end, (value, origin), length) = self.backwards.earliest(line.bytes().rev())?;
+        let end = line.len() - from_end;
+        Some(Found {
+            value,
+            position: end - length..end,
+            origin,
+        })
+    }
+
+    pub fn first(&self, line: &str) -> Option<u32> {
+        self.find_first(line).map(|found| found.value)
+    }
+
+    pub fn last(&self, line: &str) -> Option<u32> {
+        self.find_last(line).map(|found| found.value)
+    }
+
+    /// Calibration value of a line from its first and last digit
+    pub fn calibration(&self, line: &str) -> Option<u32> {
+        Some(self.first(line)? * 10 + self.last(line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_matches_overlapping_words_at_both_ends() {
+        let matcher = DigitMatcher::new([("one", 1), ("two", 2), ("eight", 8)]);
+        assert_eq!(matcher.first("twone"), Some(2));
+        assert_eq!(matcher.last("twone"), Some(1));
+        assert_eq!(matcher.calibration("eightwo"), Some(82));
+        assert_eq!(matcher.calibration("xtwonex"), Some(21));
+        assert_eq!(matcher.calibration("oneight"), Some(18));
+        assert_eq!(matcher.calibration("ttwo0"), Some(20));
+        assert_eq!(matcher.calibration("tw1ne"), Some(11));
+        assert_eq!(matcher.calibration("on"), None);
+    }
+
+    #[test]
+    fn it_prefers_longer_words_at_the_same_place() {
+        let matcher = DigitMatcher::new([("i", 1), ("ii", 2), ("iii", 3), ("v", 5), ("vii", 7)]);
+        assert_eq!(matcher.calibration("iii"), Some(33));
+        assert_eq!(matcher.calibration("viii"), Some(73));
+        assert_eq!(matcher.calibration("xvii"), Some(77));
+    }
+
+    #[test]
+    fn it_prefers_earlier_words_ending_later() {
+        let matcher = DigitMatcher::new([("abcd", 1), ("c", 3), ("bcde", 4)]);
+        assert_eq!(matcher.first("xabcde"), Some(1));
+        assert_eq!(matcher.first("xabce"), Some(3));
+        assert_eq!(matcher.last("xabcde"), Some(4));
+        assert_eq!(matcher.last("xbcdx"), Some(3));
+        assert_eq!(matcher.find_last("xabcdx").unwrap().position, 1..5);
+    }
+
+    #[test]
+    fn it_locates_digits() {
+        let matcher = DigitMatcher::new([("one", 1), ("two", 2)]);
+        assert_eq!(
+            matcher.find_first("xtwone3"),
+            Some(Found {
+                value: 2,
+                position: 1..4,
+                origin: Origin::Word
+            })
+        );
+        assert_eq!(
+            matcher.find_last("xtwone3"),
+            Some(Found {
+                value: 3,
+                position: 6..7,
+                origin: Origin::Literal
+            })
+        );
+        assert_eq!(matcher.find_last("é1two").unwrap().position, 3..6);
+    }
+}
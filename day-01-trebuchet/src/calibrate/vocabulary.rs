@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use aoc_core::error::parse_at;
+use aoc_core::input::lines;
+use aoc_core::Span;
+use thiserror::Error;
+
+use crate::calibrate::trie::DigitMatcher;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum VocabularyError {
+    #[error("expected a word and the digit it spells {0}")]
+    Entry(Span),
+    #[error("`{0}` can't spell a digit, words can't be empty or hold digits or spaces")]
+    Word(String),
+    #[error("`{word}` spells {value}, only the digits 0 to 9 can be spelled out")]
+    Value { word: String, value: u32 },
+    #[error("`{word}` spells both {first} and {second}")]
+    Conflict {
+        word: String,
+        first: u32,
+        second: u32,
+    },
+}
+
+/// Languages with built-in number words
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    German,
+    Spanish,
+}
+
+impl Locale {
+    /// The words for zero to nine
+    pub fn names(&self) -> [&'static str; 10] {
+        match self {
+            Locale::English => [
+                "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Locale::German => [
+                "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+            Locale::Spanish => [
+                "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+            ],
+        }
+    }
+}
+
+/// Words spelling out digits, found in a line along with the digits `0` to
+/// `9` themselves.
+///
+/// Ambiguities are settled the same way for every vocabulary:
+/// - words are matched exactly, case included, and may overlap, so with the
+///   English words `twone` starts with 2 and ends with 1
+/// - the first digit is the one starting the earliest and the last digit the
+///   one ending the latest
+/// - of the words starting (or ending) at the same place the longest wins, so
+///   `iii` is 3 rather than 1 with the words `i`, `ii` and `iii`
+/// - a word spells a single digit, it can't be spelled twice with different
+///   digits, nor hold digits itself
+#[derive(Debug)]
+pub struct Vocabulary {
+    words: BTreeMap<String, u32>,
+    matcher: DigitMatcher,
+}
+
+impl Vocabulary {
+    pub fn new<'a>(
+        words: impl IntoIterator<Item = (&'a str, u32)>,
+    ) -> Result<Self, VocabularyError> {
+        let mut checked: BTreeMap<String, u32> = BTreeMap::new();
+        for (word, value) in words {
+            if word.is_empty() || word.contains(|c: char| c.is_ascii_digit() || c.is_whitespace()) {
+                return Err(VocabularyError::Word(word.to_string()));
+            }
+            if value > 9 {
+                return Err(VocabularyError::Value {
+                    word: word.to_string(),
+                    value,
+                });
+            }
+            match checked.insert(word.to_string(), value) {
+                Some(first) if first != value => {
+                    return Err(VocabularyError::Conflict {
+                        word: word.to_string(),
+                        first,
+                        second: value,
+                    })
+                }
+                _ => {}
+            }
+        }
+        let matcher =
+            DigitMatcher::new(checked.iter().map(|(word, value)| (word.as_str(), *value)));
+        Ok(Vocabulary {
+            words: checked,
+            matcher,
+        })
+    }
+
+    /// The words for one to nine, as in the puzzle
+    pub fn of(locale: Locale) -> Self {
+        Self::from_names(&locale.names()[1..], 1)
+    }
+
+    /// The words for zero to nine
+    pub fn with_zero(locale: Locale) -> Self {
+        Self::from_names(&locale.names(), 0)
+    }
+
+    // built-in names are valid, distinct and in digit order from `first`
+    fn from_names(names: &[&str], first: u32) -> Self {
+        Self::new(names.iter().copied().zip(first..)).unwrap()
+    }
+
+    /// Words of both vocabularies, failing when they spell a word differently
+    pub fn merge(&self, other: &Vocabulary) -> Result<Self, VocabularyError> {
+        Self::new(self.words().chain(other.words()))
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value))
+    }
+
+    pub fn matcher(&self) -> &DigitMatcher {
+        &self.matcher
+    }
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Self::of(Locale::English)
+    }
+}
+
+/// Vocabulary file with a word and the digit it spells on each line, as in
+/// `uno 1`, skipping empty lines and lines starting with `#`
+impl FromStr for Vocabulary {
+    type Err = VocabularyError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut words = vec![];
+        for (index, line) in lines(input).enumerate() {
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let mut fields = entry.split_whitespace();
+            let (Some(word), Some(value), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(VocabularyError::Entry(Span::line(index, line)));
+            };
+            let value = parse_at(index, line, value).map_err(VocabularyError::Entry)?;
+            words.push((word, value));
+        }
+        Self::new(words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_spells_digits_in_every_locale() {
+        let german = Vocabulary::of(Locale::German);
+        assert_eq!(german.matcher().calibration("fünfundvierzig"), Some(54));
+        assert_eq!(german.matcher().calibration("nullacht"), Some(88));
+        let german = Vocabulary::with_zero(Locale::German);
+        assert_eq!(german.matcher().calibration("nullacht"), Some(8));
+
+        let spanish = Vocabulary::of(Locale::Spanish);
+        assert_eq!(spanish.matcher().calibration("xdosiete"), Some(27));
+        assert_eq!(spanish.matcher().calibration("cuatrocientos"), Some(44));
+        assert_eq!(spanish.matcher().calibration("twone"), None);
+
+        let english = Vocabulary::default();
+        assert_eq!(english.matcher().calibration("zero7one"), Some(71));
+        let english = Vocabulary::with_zero(Locale::English);
+        assert_eq!(english.matcher().calibration("zero7one"), Some(1));
+    }
+
+    #[test]
+    fn it_merges_vocabularies() {
+        let both = Vocabulary::of(Locale::English)
+            .merge(&Vocabulary::of(Locale::Spanish))
+            .unwrap();
+        assert_eq!(both.matcher().calibration("unoxtwo"), Some(12));
+        assert_eq!(both.words().count(), 18);
+
+        let clashing: Vocabulary = "one 7".parse().unwrap();
+        assert_eq!(
+            Vocabulary::default().merge(&clashing).unwrap_err(),
+            VocabularyError::Conflict {
+                word: "one".to_string(),
+                first: 1,
+                second: 7
+            }
+        );
+    }
+
+    #[test]
+    fn it_reads_vocabulary_files() {
+        let roman: Vocabulary = "# roman numerals\ni 1\nii 2\niii 3\n\niv 4\nv 5\n"
+            .parse()
+            .unwrap();
+        assert_eq!(roman.matcher().calibration("xiv"), Some(44));
+        assert_eq!(roman.matcher().calibration("iiv"), Some(24));
+
+        assert_eq!(
+            "i 1\nii".parse::<Vocabulary>().unwrap_err(),
+            VocabularyError::Entry(Span::new(2, 1, "ii"))
+        );
+        assert_eq!(
+            "i one".parse::<Vocabulary>().unwrap_err(),
+            VocabularyError::Entry(Span::new(1, 3, "one"))
+        );
+        assert_eq!(
+            "ten 10".parse::<Vocabulary>().unwrap_err(),
+            VocabularyError::Value {
+                word: "ten".to_string(),
+                value: 10
+            }
+        );
+        assert_eq!(
+            Vocabulary::new([("4ever", 4)]).unwrap_err(),
+            VocabularyError::Word("4ever".to_string())
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use aoc_core::generate::{word, InputRng, Rng, SliceRandom};
+
+const NAMES: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// `size` lines of letters, spelled out digits and at least one digit
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut pieces: Vec<String> = vec![rng.gen_range(1..=9).to_string()];
+            for _ in 0..rng.gen_range(1..6) {
+                let piece = match rng.gen_range(0..3) {
+                    0 => rng.gen_range(1..=9).to_string(),
+                    1 => NAMES.choose(rng).unwrap().to_string(),
+                    _ => {
+                        let length = rng.gen_range(1..4);
+                        word(rng, length)
+                    }
+                };
+                pieces.push(piece);
+            }
+            pieces.shuffle(rng);
+            pieces.concat()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
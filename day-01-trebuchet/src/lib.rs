@@ -0,0 +1,95 @@
+//! Day 1: Trebuchet?!
+//!
+//! [`mod@calibrate`] reads calibration values, with [`calibrate::digits_only`] for part one
+//! and [`calibrate::digits_and_text`] for part two. Digits can also be spelled out in
+//! other languages or with custom words through a [`calibrate::vocabulary::Vocabulary`],
+//! and [`calibrate::report`] shows where each line's digits were found.
+//!
+//! Solving stops at the first line without a digit, and the error points at
+//! that line only. The runner draws the report, failing parts included, with
+//! `--visualize` in the text format, and [`calibrate::report::report`] lists
+//! every line without digits of a document.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+use aoc_core::visualize::Painter;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+use crate::calibrate::digits_and_text::{digits_and_text, ENGLISH};
+use crate::calibrate::digits_only::digits_only;
+use crate::calibrate::report::draw_report;
+use crate::calibrate::trie::DigitMatcher;
+use crate::calibrate::{calibrate, calibrate_line, Document};
+
+pub mod calibrate;
+mod generator;
+
+pub struct Trebuchet;
+
+impl Puzzle for Trebuchet {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "trebuchet";
+
+    type Input = Document;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(document: &Self::Input) -> Result<Answer, Error> {
+        Ok(calibrate(&document.0, digits_only)?.into())
+    }
+
+    fn part_two(document: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(
+            calibrate(&document.0, digits_and_text)
+                .map(Answer::from)
+                .map_err(Error::from),
+        )
+    }
+
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        // a part stops adding up at its first line without a digit
+        let digits: [fn(&str) -> Option<u32>; 2] = [digits_only, digits_and_text];
+        let mut sums = [Ok(0), Ok(0)];
+        let read = read_records(input, b'\n', |index, line| {
+            for ((part, sum), digits) in Part::ALL.into_iter().zip(&mut sums).zip(digits) {
+                if let Ok(total) = sum {
+                    match stopwatch.time(part, || calibrate_line(index, line, digits)) {
+                        Some(Ok(value)) => *total += value,
+                        Some(Err(error)) => *sum = Err(error),
+                        None => {}
+                    }
+                }
+            }
+            Ok(())
+        });
+        let [sum, sum_with_text] = sums.map(|sum| sum.map(Answer::from).map_err(Error::from));
+        Some(read.map(|()| Streamed {
+            part_one: stopwatch.is_selected(Part::One).then_some(sum),
+            part_two: stopwatch.is_selected(Part::Two).then_some(sum_with_text),
+        }))
+    }
+
+    // where the digits of every line are, to find the lines without any
+    fn visualize(
+        document: &Self::Input,
+        part: Part,
+        painter: &Painter,
+    ) -> Option<Result<String, Error>> {
+        let drawing = match part {
+            Part::One => draw_report(&document.0, &DigitMatcher::new([]), painter),
+            Part::Two => draw_report(&document.0, ENGLISH.matcher(), painter),
+        };
+        Some(Ok(drawing))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -0,0 +1,7 @@
+use std::process::ExitCode;
+
+use day_01_trebuchet::Trebuchet;
+
+fn main() -> ExitCode {
+    aoc_core::run(&Trebuchet)
+}
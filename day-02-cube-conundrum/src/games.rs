@@ -1,90 +1,460 @@
-use std::usize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
-use phf::phf_map;
+use aoc_core::input::lines;
+use aoc_core::Span;
+use lazy_static::lazy_static;
+use nom::bytes::complete::{tag, take_till1};
+use nom::character::complete::char;
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, separated_pair};
+use nom::IResult;
+use thiserror::Error;
 
-static MAX_CUBES: phf::Map<&'static str, usize> = phf_map! {
-    "red" => 12,
-    "green" => 13,
-    "blue" => 14,
-};
+/// Colours of the cubes in the puzzle's games
+pub const COLORS: [&str; 3] = ["red", "green", "blue"];
 
-pub fn process_game(line: &str) -> usize {
-    let (game, rounds) = line.split_once(": ").unwrap();
-    match is_game_possible(rounds) {
-        true => game.split_once(" ").unwrap().1.parse::<usize>().unwrap(),
-        false => 0,
+lazy_static! {
+    static ref PUZZLE_BAG: Bag = Bag::puzzle();
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `Game <id>: <rounds>` {0}")]
+    MalformedGame(Span),
+    #[error("invalid game id {0}")]
+    InvalidId(Span),
+    #[error("expected `<count> <color>` {0}")]
+    MalformedRoll(Span),
+    #[error("invalid cube count {0}")]
+    InvalidCount(Span),
+    #[error("unknown cube color {0}")]
+    UnknownColor(Span),
+    #[error("cube color rolled twice in a round {0}")]
+    RepeatedColor(Span),
+}
+
+// Cubes of each colour, counts given for the same colour add up
+fn tally<'a>(cubes: impl IntoIterator<Item = (&'a str, usize)>) -> BTreeMap<String, usize> {
+    let mut tally = BTreeMap::new();
+    for (color, count) in cubes {
+        *tally.entry(color.to_string()).or_default() += count;
+    }
+    tally
+}
+
+/// Cubes of each colour shown at once in a game
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Round {
+    cubes: BTreeMap<String, usize>,
+}
+
+impl Round {
+    pub fn new<'a>(cubes: impl IntoIterator<Item = (&'a str, usize)>) -> Self {
+        Round {
+            cubes: tally(cubes),
+        }
+    }
+
+    pub fn count(&self, color: &str) -> usize {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn cubes(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.cubes
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+}
+
+/// Cubes of each colour a bag is loaded with, it has none of the colours it
+/// doesn't list
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bag {
+    cubes: BTreeMap<String, usize>,
+}
+
+impl Bag {
+    pub fn new<'a>(cubes: impl IntoIterator<Item = (&'a str, usize)>) -> Self {
+        Bag {
+            cubes: tally(cubes),
+        }
+    }
+
+    /// The bag part one asks about, with 12 red, 13 green and 14 blue cubes
+    pub fn puzzle() -> Self {
+        Bag::new([("red", 12), ("green", 13), ("blue", 14)])
+    }
+
+    pub fn count(&self, color: &str) -> usize {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn cubes(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.cubes
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    /// Whether the cubes of the round could all be drawn from the bag
+    pub fn holds(&self, round: &Round) -> bool {
+        round
+            .cubes()
+            .all(|(color, count)| count <= self.count(color))
+    }
+
+    /// Product of the cubes of each colour in the bag
+    pub fn power(&self) -> usize {
+        self.cubes.values().product()
+    }
+
+    // Adds the cubes missing to hold the round too
+    fn fit(&mut self, round: &Round) {
+        for (color, count) in round.cubes() {
+            let held = self.cubes.entry(color.to_string()).or_default();
+            *held = (*held).max(count);
+        }
+    }
+}
+
+/// Cubes in a single line of rolls of any colour, as in `12 red, 13 green, 14 blue`
+impl FromStr for Bag {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let rolls = match round(input) {
+            Ok(("", rolls)) => rolls,
+            Ok((rest, _)) => return Err(malformed_roll(0, input, rest)),
+            Err(_) => return Err(malformed_roll(0, input, input)),
+        };
+        Ok(Bag::new(cubes(0, input, rolls, None)?))
+    }
+}
+
+/// A game and the rounds of cubes shown in it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    id: usize,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+    pub fn new(id: usize, rounds: Vec<Round>) -> Self {
+        Game { id, rounds }
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// Whether every round of the game could be drawn from the bag
+    pub fn possible_with(&self, bag: &Bag) -> bool {
+        self.rounds.iter().all(|round| bag.holds(round))
+    }
+
+    /// Fewest cubes of each colour the game is possible with
+    pub fn minimal_bag(&self) -> Bag {
+        minimal_bag(std::slice::from_ref(self))
+    }
+}
+
+/// Every game of the record
+#[derive(Debug, PartialEq)]
+pub struct Games(pub Vec<Game>);
+
+impl Games {
+    /// Reads a record whose cubes have the given colours
+    pub fn parse_with(input: &str, colors: &[&str]) -> Result<Self, ParseError> {
+        lines(input)
+            .enumerate()
+            .map(|(index, line)| parse_game_with(index, line, colors))
+            .collect::<Result<_, _>>()
+            .map(Games)
+    }
+}
+
+impl FromStr for Games {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Games::parse_with(input, &COLORS)
+    }
+}
+
+/// Reads a `Game <id>: <rounds>` line of the puzzle's colours
+pub fn parse_game(index: usize, line: &str) -> Result<Game, ParseError> {
+    parse_game_with(index, line, &COLORS)
+}
+
+/// Reads a `Game <id>: <rounds>` line whose cubes have the given colours.
+/// Rounds are separated by `; ` and their `<count> <color>` rolls by `, `,
+/// ids and counts are positive and a colour is rolled once per round.
+pub fn parse_game_with(index: usize, line: &str, colors: &[&str]) -> Result<Game, ParseError> {
+    let (rolls, id) =
+        header(line).map_err(|_| ParseError::MalformedGame(Span::line(index, line)))?;
+    let id = number(id).ok_or_else(|| ParseError::InvalidId(Span::at(index, line, id)))?;
+    let rounds = rounds(index, line, rolls)?
+        .into_iter()
+        .map(|round| cubes(index, line, round, Some(colors)).map(Round::new))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Game { id, rounds })
+}
+
+// The grammar only splits a line into tokens, which are checked afterwards
+// so that errors can tell what's wrong with them
+fn header(line: &str) -> IResult<&str, &str> {
+    delimited(tag("Game "), take_till1(|c| c == ':'), tag(": "))(line)
+}
+
+fn token(input: &str) -> IResult<&str, &str> {
+    take_till1(|c| matches!(c, ' ' | ',' | ';'))(input)
+}
+
+fn roll(input: &str) -> IResult<&str, (&str, &str)> {
+    separated_pair(token, char(' '), token)(input)
+}
+
+fn round(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
+    separated_list1(tag(", "), roll)(input)
+}
+
+// Rolls of every round, all of `rolls` has to be read
+fn rounds<'a>(
+    index: usize,
+    line: &str,
+    rolls: &'a str,
+) -> Result<Vec<Vec<(&'a str, &'a str)>>, ParseError> {
+    match separated_list1(tag("; "), round)(rolls) {
+        Ok(("", rounds)) => Ok(rounds),
+        Ok((rest, _)) => Err(malformed_roll(index, line, rest)),
+        Err(_) => Err(malformed_roll(index, line, rolls)),
+    }
+}
+
+// Error pointing at the roll `rest` starts with, after its separator
+fn malformed_roll(index: usize, line: &str, rest: &str) -> ParseError {
+    let roll = rest
+        .strip_prefix(", ")
+        .or_else(|| rest.strip_prefix("; "))
+        .unwrap_or(rest);
+    let end = roll
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| matches!(c, ',' | ';'))
+        .map_or(roll.len(), |(end, _)| end);
+    ParseError::MalformedRoll(Span::at(index, line, &roll[..end]))
+}
+
+fn number(token: &str) -> Option<usize> {
+    match token.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => token.parse().ok().filter(|&number| number > 0),
+        false => None,
     }
 }
 
-pub fn game_power(line: &str) -> usize {
-    let (_game, rounds) = line.split_once(": ").unwrap();
-    let mut max_red: usize = 1;
-    let mut max_green: usize = 1;
-    let mut max_blue: usize = 1;
-    for round in rounds.split("; ") {
-        for rolls in round.split(", ") {
-            for roll in rolls.split(", ") {
-                let (cubes, color) = roll.split_once(" ").unwrap();
-                let cubes_count = cubes.parse::<usize>().unwrap();
-                if color == "red" && cubes_count.cmp(&max_red).is_gt() {
-                    max_red = cubes_count
-                }
-                if color == "blue" && cubes_count.cmp(&max_blue).is_gt() {
-                    max_blue = cubes_count
-                }
-                if color == "green" && cubes_count.cmp(&max_green).is_gt() {
-                    max_green = cubes_count
-                }
-            }
+// Counts of the colours of a round, checking them against `colors` when
+// they are known
+fn cubes<'a>(
+    index: usize,
+    line: &str,
+    rolls: Vec<(&str, &'a str)>,
+    colors: Option<&[&str]>,
+) -> Result<Vec<(&'a str, usize)>, ParseError> {
+    let mut cubes: Vec<(&str, usize)> = vec![];
+    for (count, color) in rolls {
+        let count =
+            number(count).ok_or_else(|| ParseError::InvalidCount(Span::at(index, line, count)))?;
+        let known = match colors {
+            Some(colors) => colors.contains(&color),
+            None => color.chars().all(char::is_alphabetic),
+        };
+        if !known {
+            return Err(ParseError::UnknownColor(Span::at(index, line, color)));
+        }
+        if cubes.iter().any(|&(rolled, _)| rolled == color) {
+            return Err(ParseError::RepeatedColor(Span::at(index, line, color)));
         }
+        cubes.push((color, count));
     }
+    Ok(cubes)
+}
 
-    max_red * max_blue * max_green
+/// Fewest cubes of each colour every game of the record is possible with
+pub fn minimal_bag(games: &[Game]) -> Bag {
+    let mut bag = Bag::default();
+    for round in games.iter().flat_map(|game| &game.rounds) {
+        bag.fit(round);
+    }
+    bag
 }
 
-fn is_game_possible(rounds: &str) -> bool {
-    rounds.split("; ").all(|round| is_round_possible(round))
+/// Id of the game when it was possible with 12 red, 13 green and 14 blue cubes, 0 otherwise
+pub fn process_game(game: &Game) -> usize {
+    match game.possible_with(&PUZZLE_BAG) {
+        true => game.id,
+        false => 0,
+    }
 }
 
-fn is_round_possible(round: &str) -> bool {
-    round.split(", ").all(|roll| {
-        let (cubes, color) = roll.split_once(" ").unwrap();
-        cubes
-            .to_owned()
-            .parse::<usize>()
-            .unwrap()
-            .cmp(MAX_CUBES.get(color).unwrap())
-            .is_le()
-    })
+/// Product of the fewest cubes of each colour the game needs
+pub fn game_power(game: &Game) -> usize {
+    game.minimal_bag().power()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn round(line: &str) -> Round {
+        parse_game(0, &format!("Game 1: {line}")).unwrap().rounds[0].clone()
+    }
+
+    fn game(line: &str) -> Game {
+        parse_game(0, line).unwrap()
+    }
+
     #[test]
     fn possible_round() {
-        assert_eq!(is_round_possible("3 blue, 4 red"), true);
-        assert_eq!(is_round_possible("1 red, 2 green, 6 blue"), true);
-        assert_eq!(is_round_possible("2 green"), true);
+        let bag = Bag::puzzle();
+        assert!(bag.holds(&round("3 blue, 4 red")));
+        assert!(bag.holds(&round("1 red, 2 green, 6 blue")));
+        assert!(bag.holds(&round("2 green")));
     }
 
     #[test]
     fn impossible_round() {
-        assert_eq!(is_round_possible("13 red"), false);
-        assert_eq!(is_round_possible("15 blue, 4 red"), false);
-        assert_eq!(is_round_possible("1 red, 14 green, 6 blue"), false);
+        let bag = Bag::puzzle();
+        assert!(!bag.holds(&round("13 red")));
+        assert!(!bag.holds(&round("15 blue, 4 red")));
+        assert!(!bag.holds(&round("1 red, 14 green, 6 blue")));
+        assert!(!bag.holds(&Round::new([("red", 7), ("red", 6)])));
     }
 
     #[test]
     fn test_game_power() {
         assert_eq!(
-            game_power("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            game_power(&game(
+                "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            )),
             48
         );
         assert_eq!(
-            game_power("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
+            game_power(&game(
+                "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"
+            )),
             12
         );
     }
+
+    #[test]
+    fn games_with_other_bags() {
+        let games: Games = include_str!("../sample.txt").parse().unwrap();
+        let bag: Bag = "4 red, 3 green, 6 blue".parse().unwrap();
+        let possible: Vec<usize> = games
+            .0
+            .iter()
+            .filter(|game| game.possible_with(&bag))
+            .map(Game::id)
+            .collect();
+        assert_eq!(possible, [1, 2]);
+        assert_eq!(
+            minimal_bag(&games.0),
+            "20 red, 13 green, 15 blue".parse().unwrap()
+        );
+        assert_eq!(minimal_bag(&[]).cubes().count(), 0);
+
+        let rainbow =
+            Games::parse_with("Game 7: 2 red, 1 purple; 3 purple", &["red", "purple"]).unwrap();
+        let game = &rainbow.0[0];
+        assert_eq!(game.rounds()[1].count("purple"), 3);
+        assert_eq!(game.minimal_bag(), Bag::new([("purple", 3), ("red", 2)]));
+        assert!(!game.possible_with(&Bag::puzzle()));
+    }
+
+    #[test]
+    fn malformed_games() {
+        assert_eq!(
+            parse_game(1, "Game 3 3 blue"),
+            Err(ParseError::MalformedGame(Span::new(2, 1, "Game 3 3 blue")))
+        );
+        assert_eq!(
+            parse_game(0, "Game x: 3 blue"),
+            Err(ParseError::InvalidId(Span::new(1, 6, "x")))
+        );
+        assert_eq!(
+            parse_game(0, "Game 1: 3 blue; 2 purple"),
+            Err(ParseError::UnknownColor(Span::new(1, 19, "purple")))
+        );
+        assert_eq!(
+            parse_game(0, "Game 1: many blue"),
+            Err(ParseError::InvalidCount(Span::new(1, 9, "many")))
+        );
+    }
+
+    #[test]
+    fn strict_grammar() {
+        let error = |line| parse_game(0, line).unwrap_err();
+        assert_eq!(
+            error("Game 0: 3 blue"),
+            ParseError::InvalidId(Span::new(1, 6, "0"))
+        );
+        assert_eq!(
+            error("Game -1: 3 blue"),
+            ParseError::InvalidId(Span::new(1, 6, "-1"))
+        );
+        assert_eq!(
+            error("Game 1: 0 blue"),
+            ParseError::InvalidCount(Span::new(1, 9, "0"))
+        );
+        assert_eq!(
+            error("Game 1: +3 blue"),
+            ParseError::InvalidCount(Span::new(1, 9, "+3"))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue, 4 red, 1 blue"),
+            ParseError::RepeatedColor(Span::new(1, 26, "blue"))
+        );
+        assert_eq!(
+            error("Game 1: 3 Blue"),
+            ParseError::UnknownColor(Span::new(1, 11, "Blue"))
+        );
+        assert_eq!(
+            error("game 1: 3 blue"),
+            ParseError::MalformedGame(Span::new(1, 1, "game 1: 3 blue"))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue,4 red"),
+            ParseError::MalformedRoll(Span::new(1, 15, ",4 red"))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue; 4red; 1 green"),
+            ParseError::MalformedRoll(Span::new(1, 17, "4red"))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue, 4 red "),
+            ParseError::MalformedRoll(Span::new(1, 22, " "))
+        );
+        assert_eq!(
+            error("Game 1: "),
+            ParseError::MalformedRoll(Span::new(1, 9, ""))
+        );
+        assert_eq!(
+            error("Game 1: 3 blue;"),
+            ParseError::MalformedRoll(Span::new(1, 15, ";"))
+        );
+
+        assert_eq!(
+            "2 blue, 1 r3d".parse::<Bag>(),
+            Err(ParseError::UnknownColor(Span::new(1, 11, "r3d")))
+        );
+        assert_eq!(
+            "2 blue 1 red".parse::<Bag>(),
+            Err(ParseError::MalformedRoll(Span::new(1, 7, " 1 red")))
+        );
+    }
 }
@@ -0,0 +1,23 @@
+use aoc_core::generate::{InputRng, Rng, SliceRandom};
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+// `size` games of up to six rounds each
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|id| {
+            let rounds: Vec<String> = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let count = rng.gen_range(1..=3);
+                    COLORS
+                        .choose_multiple(rng, count)
+                        .map(|color| format!("{} {color}", rng.gen_range(1..=20)))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                })
+                .collect();
+            format!("Game {id}: {}", rounds.join("; "))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
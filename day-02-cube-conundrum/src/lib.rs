@@ -0,0 +1,64 @@
+//! Day 2: Cube Conundrum
+//!
+//! [`games::parse_game`] reads a [`games::Game`], [`games::process_game`] and
+//! [`games::game_power`] score it. Games can also be checked against any
+//! [`games::Bag`], and [`games::minimal_bag`] finds the bag a whole record needs.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+use crate::games::{game_power, parse_game, process_game, Games};
+
+pub mod games;
+mod generator;
+
+pub struct CubeConundrum;
+
+impl Puzzle for CubeConundrum {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "cube-conundrum";
+
+    type Input = Games;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(games: &Self::Input) -> Result<Answer, Error> {
+        let sum: usize = games.0.iter().map(process_game).sum();
+        Ok(sum.into())
+    }
+
+    fn part_two(games: &Self::Input) -> Option<Result<Answer, Error>> {
+        let sum_power: usize = games.0.iter().map(game_power).sum();
+        Some(Ok(sum_power.into()))
+    }
+
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        let (mut sum, mut sum_power) = (0, 0);
+        let read = read_records(input, b'\n', |index, line| {
+            let game = parse_game(index, line)?;
+            stopwatch.time(Part::One, || sum += process_game(&game));
+            stopwatch.time(Part::Two, || sum_power += game_power(&game));
+            Ok(())
+        });
+        Some(read.map(|()| {
+            Streamed {
+                part_one: stopwatch.is_selected(Part::One).then(|| Ok(sum.into())),
+                part_two: stopwatch
+                    .is_selected(Part::Two)
+                    .then(|| Ok(sum_power.into())),
+            }
+        }))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
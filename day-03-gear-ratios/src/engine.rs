@@ -1,12 +1,21 @@
-use lazy_static::lazy_static;
-use regex::{Match, Regex};
+use aoc_core::error::find_invalid_char;
+use aoc_core::input::lines;
+use aoc_core::numbers::NUM_RE;
+use aoc_core::Span;
+use regex::Match;
 use std::collections::HashSet;
+use std::str::FromStr;
+use thiserror::Error;
 
-lazy_static! {
-    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("unexpected character in schematic {0}")]
+    UnexpectedCharacter(Span),
+    #[error("part number is too large {0}")]
+    InvalidNumber(Span),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct Part {
     number: usize,
     start: usize,
@@ -14,23 +23,35 @@ struct Part {
     line: usize,
 }
 
+/// A schematic's part numbers and where its `*` symbols are
+#[derive(Debug, PartialEq)]
+pub struct Schematic {
+    parts: Vec<Part>,
+    stars: Vec<(usize, usize)>,
+}
+
+impl FromStr for Schematic {
+    type Err = ParseError;
+
+    fn from_str(schema: &str) -> Result<Self, Self::Err> {
+        parse_schema(schema)
+    }
+}
+
 fn get_symbols(schema: &str) -> HashSet<(usize, usize)> {
     let mut symbols: HashSet<(usize, usize)> = HashSet::new();
-    schema
-        .split("\n")
-        .enumerate()
-        .for_each(|(line_index, line)| {
-            line.chars().enumerate().for_each(|(char_index, c)| {
-                if is_symbol(c) {
-                    symbols.insert((line_index, char_index));
-                }
-            });
+    lines(schema).enumerate().for_each(|(line_index, line)| {
+        line.chars().enumerate().for_each(|(char_index, c)| {
+            if is_symbol(c) {
+                symbols.insert((line_index, char_index));
+            }
         });
+    });
 
     symbols.to_owned()
 }
 
-fn maybe_gear(x: usize, y: usize, parts: &Vec<Part>) -> Option<usize> {
+fn maybe_gear(x: usize, y: usize, parts: &[Part]) -> Option<usize> {
     let adjucent_parts = parts
         .iter()
         .filter(|part| {
@@ -43,64 +64,84 @@ fn maybe_gear(x: usize, y: usize, parts: &Vec<Part>) -> Option<usize> {
                     }
                 }
             }
-            return false;
+            false
         })
         .collect::<Vec<&Part>>();
 
     if adjucent_parts.len() == 2 {
         let raion = adjucent_parts.iter().fold(1, |acc, part| acc * part.number);
-        return Some(raion);
+        Some(raion)
     } else {
-        return None;
-    };
+        None
+    }
+}
+
+/// Numbers of the schematic next to a symbol
+pub fn get_part_numbers(schematic: &Schematic) -> Vec<usize> {
+    schematic.parts.iter().map(|part| part.number).collect()
 }
 
-pub fn get_part_numbers(schema: &str) -> Vec<usize> {
-    get_part_numbers_and_positions(schema)
+/// Products of the two numbers next to each `*` that has exactly two
+pub fn get_gear_ratios(schematic: &Schematic) -> Vec<usize> {
+    schematic
+        .stars
         .iter()
-        .map(|part| part.number)
+        .filter_map(|(line_index, char_index)| {
+            maybe_gear(*line_index, *char_index, &schematic.parts)
+        })
         .collect()
 }
 
-pub fn get_gear_ratios(schema: &str) -> Vec<usize> {
-    let parts = get_part_numbers_and_positions(schema);
-    let mut gears: Vec<usize> = vec![];
-
-    schema
-        .split("\n")
+fn get_stars(schema: &str) -> Vec<(usize, usize)> {
+    lines(schema)
         .enumerate()
-        .for_each(|(line_index, line)| {
-            line.chars().enumerate().for_each(|(char_index, c)| {
-                if c == '*' {
-                    maybe_gear(line_index, char_index, &parts)
-                        .and_then(|gear| Some(gears.push(gear)));
-                }
-            });
-        });
-
-    return gears;
+        .flat_map(|(line_index, line)| {
+            line.char_indices()
+                .filter(|(_, c)| *c == '*')
+                .map(move |(char_index, _)| (line_index, char_index))
+        })
+        .collect()
 }
 
 fn get_part_numbers_and_positions(schema: &str) -> Vec<Part> {
     let symbols = get_symbols(schema);
-    let lines = schema.split("\n").collect::<Vec<&str>>();
-    return lines
-        .iter()
+    lines(schema)
         .enumerate()
-        .filter_map(|(row_index, row)| {
-            let matches = NUM_RE
+        .flat_map(|(row_index, row)| {
+            NUM_RE
                 .find_iter(row)
                 .filter_map(|res| maybe_part(res, row_index, &symbols))
-                .collect::<Vec<Part>>();
-
-            return Some(matches);
+                .collect::<Vec<Part>>()
         })
-        .flatten()
-        .collect::<Vec<Part>>();
+        .collect::<Vec<Part>>()
+}
+
+/// Reads the part numbers and gear candidates of the schematic
+pub fn parse_schema(schema: &str) -> Result<Schematic, ParseError> {
+    for (index, line) in lines(schema).enumerate() {
+        if let Some(span) = find_invalid_char(index, line, |c| c.is_ascii_graphic()) {
+            return Err(ParseError::UnexpectedCharacter(span));
+        }
+        if let Some(number) = NUM_RE
+            .find_iter(line)
+            .find(|number| number.as_str().parse::<usize>().is_err())
+        {
+            return Err(ParseError::InvalidNumber(Span::at(
+                index,
+                line,
+                number.as_str(),
+            )));
+        }
+    }
+
+    Ok(Schematic {
+        parts: get_part_numbers_and_positions(schema),
+        stars: get_stars(schema),
+    })
 }
 
 fn is_symbol(c: char) -> bool {
-    !(c.is_digit(10) || c.eq(&'.'))
+    !(c.is_ascii_digit() || c.eq(&'.'))
 }
 
 fn maybe_part<'a>(
@@ -118,7 +159,7 @@ fn maybe_part<'a>(
         for j in col_min..(item.end() + 1) {
             if symbols.contains(&(i, j)) {
                 return Some(Part {
-                    number: item.as_str().parse::<usize>().unwrap(),
+                    number: item.as_str().parse::<usize>().ok()?,
                     start: item.start(),
                     end: item.end(),
                     line: row_index,
@@ -126,18 +167,22 @@ fn maybe_part<'a>(
             }
         }
     }
-    return None;
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn schematic(schema: &str) -> Schematic {
+        schema.parse().unwrap()
+    }
+
     #[test]
     fn symbol_recognition() {
-        assert_eq!(is_symbol('.'), false);
-        assert_eq!(is_symbol('*'), true);
-        assert_eq!(is_symbol('1'), false);
+        assert!(!is_symbol('.'));
+        assert!(is_symbol('*'));
+        assert!(!is_symbol('1'));
     }
 
     #[test]
@@ -147,16 +192,36 @@ mod tests {
         assert_eq!(get_symbols("1"), HashSet::new());
     }
 
+    #[test]
+    fn schema_validation() {
+        assert_eq!(
+            parse_schema("4.\n.$").map(|schematic| get_part_numbers(&schematic)),
+            Ok(vec![4])
+        );
+        assert_eq!(
+            parse_schema("4.\n.é"),
+            Err(ParseError::UnexpectedCharacter(Span::new(2, 2, "é")))
+        );
+        assert_eq!(
+            parse_schema("..99999999999999999999999*"),
+            Err(ParseError::InvalidNumber(Span::new(
+                1,
+                3,
+                "99999999999999999999999"
+            )))
+        );
+    }
+
     #[test]
     fn small_parts() {
-        assert_eq!(get_part_numbers("4.\n.$"), [4]);
-        assert_eq!(get_part_numbers("1..\n..$"), []);
+        assert_eq!(get_part_numbers(&schematic("4.\n.$")), [4]);
+        assert_eq!(get_part_numbers(&schematic("1..\n..$")), []);
     }
 
     #[test]
     fn test_gear_ratios() {
         assert_eq!(
-            get_gear_ratios(
+            get_gear_ratios(&schematic(
                 "467..114..
 ...*......
 ..35..633.
@@ -167,7 +232,7 @@ mod tests {
 ......755.
 ...$.*....
 .664.598.."
-            ),
+            )),
             [16345, 451490]
         );
     }
@@ -175,7 +240,7 @@ mod tests {
     #[test]
     fn collects_parts() {
         assert_eq!(
-            get_part_numbers(
+            get_part_numbers(&schematic(
                 "467..114..
 ...*......
 ..35..633.
@@ -186,11 +251,11 @@ mod tests {
 ......755.
 ...$.*....
 .664.598.."
-            ),
+            )),
             [467, 35, 633, 617, 592, 755, 664, 598]
         );
         assert_eq!(
-            get_part_numbers(
+            get_part_numbers(&schematic(
                 "467..114..
 ...*......
 ..35..633.
@@ -201,7 +266,7 @@ mod tests {
 ......755.
 ...$.*....
 .664.598.."
-            )
+            ))
             .iter()
             .sum::<usize>(),
             4361
@@ -0,0 +1,28 @@
+use aoc_core::generate::{InputRng, Rng, SliceRandom};
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&";
+
+// `size` by `size` schematic, numbers are kept apart by at least one dot
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let mut row = String::new();
+            while row.len() < size {
+                let room = size - row.len();
+                match rng.gen_range(0..10) {
+                    0..=1 if room > 1 => {
+                        let digits = rng.gen_range(1..=3.min(room - 1));
+                        let number =
+                            rng.gen_range(10_u32.pow(digits as u32 - 1)..10_u32.pow(digits as u32));
+                        row.push_str(&format!("{number}."));
+                    }
+                    2 => row.push(*SYMBOLS.choose(rng).unwrap() as char),
+                    _ => row.push('.'),
+                }
+            }
+            row
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,41 @@
+//! Day 3: Gear Ratios
+//!
+//! [`engine::parse_schema`] reads the [`engine::Schematic`], [`engine::get_part_numbers`]
+//! and [`engine::get_gear_ratios`] read it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+use crate::engine::get_gear_ratios;
+use crate::engine::get_part_numbers;
+use crate::engine::Schematic;
+
+pub mod engine;
+mod generator;
+
+pub struct GearRatios;
+
+impl Puzzle for GearRatios {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "gear-ratios";
+
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(schema: &Self::Input) -> Result<Answer, Error> {
+        let sum: usize = get_part_numbers(schema).iter().sum();
+        Ok(sum.into())
+    }
+
+    fn part_two(schema: &Self::Input) -> Option<Result<Answer, Error>> {
+        let gear_sum: usize = get_gear_ratios(schema).iter().sum();
+        Some(Ok(gear_sum.into()))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
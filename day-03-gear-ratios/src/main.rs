@@ -1,20 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-use crate::engine::get_gear_ratios;
-use crate::engine::get_part_numbers;
+use day_03_gear_ratios::GearRatios;
 
-mod engine;
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum: usize = get_part_numbers(contents.as_str()).iter().sum();
-    println!("Sum:\n{sum}");
-    let gear_sum: usize = get_gear_ratios(contents.as_str()).iter().sum();
-    println!("Gear sum:\n{gear_sum}");
+fn main() -> ExitCode {
+    aoc_core::run(&GearRatios)
 }
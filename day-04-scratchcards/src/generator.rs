@@ -0,0 +1,23 @@
+use aoc_core::generate::{InputRng, SliceRandom};
+
+const WINNING: usize = 5;
+const NUMBERS: usize = 8;
+
+// `size` cards, few enough numbers match that the copies stay countable
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let pool: Vec<u32> = (1..100).collect();
+    let numbers = |rng: &mut InputRng, count| {
+        pool.choose_multiple(rng, count)
+            .map(|number| format!("{number:>2}"))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+
+    (1..=size.max(1))
+        .map(|id| {
+            let winning = numbers(rng, WINNING);
+            format!("Card {id:>3}: {winning} | {}", numbers(rng, NUMBERS))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
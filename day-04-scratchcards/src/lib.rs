@@ -0,0 +1,77 @@
+//! Day 4: Scratchcards
+//!
+//! [`lottery::parse_card`] reads a [`lottery::Card`], [`lottery::card_value`] scores it
+//! and [`lottery::CardCounter`] counts the copies won.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+use crate::lottery::card_value;
+use crate::lottery::parse_card;
+use crate::lottery::total_cards;
+use crate::lottery::CardCounter;
+use crate::lottery::SolveError;
+use crate::lottery::Table;
+
+mod generator;
+pub mod lottery;
+
+pub struct Scratchcards;
+
+impl Puzzle for Scratchcards {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "scratchcards";
+
+    type Input = Table;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(table: &Self::Input) -> Result<Answer, Error> {
+        let sum = table
+            .0
+            .iter()
+            .map(|card| card_value(card).map(u64::from))
+            .sum::<Result<u64, _>>()?;
+        Ok(sum.into())
+    }
+
+    fn part_two(table: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(total_cards(&table.0).into()))
+    }
+
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        let mut sum: Result<u64, SolveError> = Ok(0);
+        let mut counter = CardCounter::default();
+        let read = read_records(input, b'\n', |index, line| {
+            let card = parse_card(index, line)?;
+            // part one stops adding up at its first card out of range
+            stopwatch.time(Part::One, || {
+                if let Ok(total) = &mut sum {
+                    match card_value(&card) {
+                        Ok(value) => *total += u64::from(value),
+                        Err(error) => sum = Err(error),
+                    }
+                }
+            });
+            stopwatch.time(Part::Two, || counter.add(&card));
+            Ok(())
+        });
+        let sum = sum.map(Answer::from).map_err(Error::from);
+        Some(read.map(|()| Streamed {
+            part_one: stopwatch.is_selected(Part::One).then_some(sum),
+            part_two: stopwatch.time(Part::Two, || Ok(counter.total().into())),
+        }))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,92 +1,199 @@
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::collections::VecDeque;
+use std::str::FromStr;
 
-lazy_static! {
-    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
+use aoc_core::input::lines;
+use aoc_core::numbers::parse_numbers;
+use aoc_core::Span;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `Card <id>: <winning> | <numbers>` {0}")]
+    MalformedCard(Span),
+    #[error("invalid number {0}")]
+    InvalidNumber(Span),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("a card with {0} matches is worth more points than fit in 32 bits")]
+    TooManyMatches(usize),
+}
+
+/// A scratchcard with its winning numbers and the numbers on it
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    winning_numbers: Vec<u32>,
+    numbers: Vec<u32>,
+}
+
+/// The scratchcards of the pile
+#[derive(Debug, PartialEq)]
+pub struct Table(pub Vec<Card>);
+
+impl FromStr for Table {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        lines(input)
+            .enumerate()
+            .map(|(index, line)| parse_card(index, line))
+            .collect::<Result<_, _>>()
+            .map(Table)
+    }
 }
 
-fn card_matches(card: &str) -> usize {
-    let (start, end) = card.split_once(" | ").unwrap();
-    let winning_numbers: Vec<u32> = NUM_RE
-        .find_iter(start.split_once(": ").unwrap().1)
-        .filter_map(|s| s.as_str().parse::<u32>().ok())
-        .collect();
-    return NUM_RE
-        .find_iter(end)
-        .filter_map(|s| s.as_str().parse::<u32>().ok())
-        .filter(|num| winning_numbers.contains(num))
-        .count();
+/// Reads a `Card <id>: <winning> | <numbers>` line
+pub fn parse_card(index: usize, card: &str) -> Result<Card, ParseError> {
+    let malformed = || ParseError::MalformedCard(Span::line(index, card));
+    let (start, end) = card.split_once(" | ").ok_or_else(malformed)?;
+    let (_id, winning) = start.split_once(": ").ok_or_else(malformed)?;
+    let invalid_number = |number| ParseError::InvalidNumber(Span::at(index, card, number));
+
+    Ok(Card {
+        winning_numbers: parse_numbers(winning).map_err(invalid_number)?,
+        numbers: parse_numbers(end).map_err(invalid_number)?,
+    })
+}
+
+fn card_matches(card: &Card) -> usize {
+    card.numbers
+        .iter()
+        .filter(|num| card.winning_numbers.contains(num))
+        .count()
 }
 
-pub fn card_value(card: &str) -> u32 {
+/// Points of a card, doubling for every match after the first
+pub fn card_value(card: &Card) -> Result<u32, SolveError> {
     let num_win = card_matches(card);
     if num_win == 0 {
-        return 0;
-    } else {
-        return 2_u32.pow(num_win as u32 - 1);
+        return Ok(0);
+    }
+    u32::try_from(num_win - 1)
+        .ok()
+        .and_then(|doublings| 1_u32.checked_shl(doublings))
+        .ok_or(SolveError::TooManyMatches(num_win))
+}
+
+/// Cards held at the end once every match wins copies of the following cards
+pub fn total_cards(table: &[Card]) -> usize {
+    let mut counter = CardCounter::default();
+    for card in table {
+        counter.add(card);
     }
+
+    counter.total()
 }
 
-pub fn total_cards(table: &str) -> usize {
-    let cards = table.split("\n").map(card_matches);
-    let mut copies: Vec<usize> = vec![1; cards.clone().count()];
-
-    for (index, card) in cards.enumerate() {
-        for i in 1..(card + 1) {
-            let id = index + i;
-            if id >= copies.len() {
-                break;
-            }
-            copies[id] += 1 * copies[index];
+/// Counts cards one at a time, keeping the copies won for the cards still to
+/// come. Copies of cards past the end of the table are never counted.
+#[derive(Debug, Default)]
+pub struct CardCounter {
+    won: VecDeque<usize>,
+    total: usize,
+}
+
+impl CardCounter {
+    pub fn add(&mut self, card: &Card) {
+        let copies = 1 + self.won.pop_front().unwrap_or(0);
+        self.total += copies;
+
+        let matches = card_matches(card);
+        if self.won.len() < matches {
+            self.won.resize(matches, 0);
+        }
+        for won in self.won.iter_mut().take(matches) {
+            *won += copies;
         }
     }
 
-    return copies.iter().sum();
+    pub fn total(&self) -> usize {
+        self.total
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn card(line: &str) -> Card {
+        parse_card(0, line).unwrap()
+    }
+
+    fn cards(table: &str) -> Vec<Card> {
+        table.split("\n").map(card).collect()
+    }
+
+    #[test]
+    fn malformed_cards() {
+        assert_eq!(
+            parse_card(4, "Card 5: 87 83 26"),
+            Err(ParseError::MalformedCard(Span::new(
+                5,
+                1,
+                "Card 5: 87 83 26"
+            )))
+        );
+        assert_eq!(
+            parse_card(0, "Card 1: 41 | 83 99999999999"),
+            Err(ParseError::InvalidNumber(Span::new(1, 17, "99999999999")))
+        );
+    }
+
     #[test]
     fn calculations() {
         assert_eq!(
-            card_value("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
-            8
+            card_value(&card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")),
+            Ok(8)
+        );
+        assert_eq!(
+            card_value(&card("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19")),
+            Ok(2)
         );
         assert_eq!(
-            card_value("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"),
-            2
+            card_value(&card("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1")),
+            Ok(2)
         );
         assert_eq!(
-            card_value("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"),
-            2
+            card_value(&card("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83")),
+            Ok(1)
         );
         assert_eq!(
-            card_value("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"),
-            1
+            card_value(&card("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36")),
+            Ok(0)
         );
         assert_eq!(
-            card_value("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"),
-            0
+            card_value(&card("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11")),
+            Ok(0)
         );
+    }
+
+    // a card whose numbers all win
+    fn winning(matches: usize) -> Card {
+        let numbers = (1..=matches).map(|n| n.to_string()).collect::<Vec<_>>();
+        card(&format!("Card 1: {0} | {0}", numbers.join(" ")))
+    }
+
+    #[test]
+    fn it_reports_values_out_of_range() {
+        assert_eq!(card_value(&winning(32)), Ok(1 << 31));
         assert_eq!(
-            card_value("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
-            0
+            card_value(&winning(33)),
+            Err(SolveError::TooManyMatches(33))
         );
     }
 
     #[test]
     fn total_calculation() {
         assert_eq!(
-            total_cards(
+            total_cards(&cards(
                 "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
-            ),
+            )),
             30
         );
     }
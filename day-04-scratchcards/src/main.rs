@@ -1,20 +1,7 @@
-mod lottery;
+use std::process::ExitCode;
 
-use std::env;
-use std::fs;
+use day_04_scratchcards::Scratchcards;
 
-use crate::lottery::card_value;
-use crate::lottery::total_cards;
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum: u32 = contents.split("\n").map(|line| card_value(line)).sum();
-    println!("Sum:\n{sum}");
-    let total = total_cards(&contents.as_str());
-    println!("Total cards:\n{total}");
+fn main() -> ExitCode {
+    aoc_core::run(&Scratchcards)
 }
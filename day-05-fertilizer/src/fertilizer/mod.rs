@@ -1,28 +1,41 @@
 use std::vec;
 
-use self::parse::{MapRange, PlantMap};
+use thiserror::Error;
+
+pub use self::parse::{parse_map, MapRange, PlantMap};
 
 mod parse;
 mod planter;
 
-pub fn plant_location(map: &str) -> u64 {
-    let plant_map = parse::parse_map(map);
-    return plant_map
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("no seeds to plant")]
+    NoSeeds,
+    #[error("seeds have to come in pairs of a start and a length, {0} numbers given")]
+    UnpairedSeeds(usize),
+}
+
+/// Lowest location of any of the seeds
+pub fn plant_location(plant_map: &PlantMap) -> Result<u64, SolveError> {
+    plant_map
         .seeds
         .clone()
         .iter()
         .map(|seed| find_location_for_seed(*seed, plant_map.maps.clone()))
         .min()
-        .unwrap();
+        .ok_or(SolveError::NoSeeds)
 }
 
-pub fn plant_ranged_location(map: &str) -> u64 {
-    let plant_map = parse::parse_map(map);
-    return get_all_seeds_from_map(plant_map.clone())
+/// Lowest location when the seeds are pairs of range starts and lengths
+pub fn plant_ranged_location(plant_map: &PlantMap) -> Result<u64, SolveError> {
+    if !plant_map.seeds.len().is_multiple_of(2) {
+        return Err(SolveError::UnpairedSeeds(plant_map.seeds.len()));
+    }
+    get_all_seeds_from_map(plant_map.clone())
         .iter()
-        .map(|seed_range| find_location_for_seed_range(*seed_range, plant_map.maps.clone()))
+        .filter_map(|seed_range| find_location_for_seed_range(*seed_range, plant_map.maps.clone()))
         .min()
-        .unwrap();
+        .ok_or(SolveError::NoSeeds)
 }
 
 fn get_all_seeds_from_map(plant_map: PlantMap) -> Vec<(u64, u64)> {
@@ -32,33 +45,59 @@ fn get_all_seeds_from_map(plant_map: PlantMap) -> Vec<(u64, u64)> {
         let seed_range = plant_map.seeds[i * 2 + 1];
         seeds.push((seed, seed_range))
     }
-    return seeds;
+    seeds
 }
 
 fn find_location_for_seed(seed: u64, maps: Vec<Vec<MapRange>>) -> u64 {
-    return maps.iter().fold(seed, |source, ranges| {
+    maps.iter().fold(seed, |source, ranges| {
         ranges
             .iter()
             .find(|map_range| {
                 source >= map_range.source_start
                     && source < map_range.source_start + map_range.range
             })
-            .and_then(|map_range| {
-                Some(map_range.destination_start + source - map_range.source_start)
-            })
+            .map(|map_range| map_range.destination_start + source - map_range.source_start)
             .unwrap_or(source)
-    });
+    })
 }
 
-fn find_location_for_seed_range(seed_range: (u64, u64), maps: Vec<Vec<MapRange>>) -> u64 {
+// `None` for a range of no seeds
+fn find_location_for_seed_range(seed_range: (u64, u64), maps: Vec<Vec<MapRange>>) -> Option<u64> {
     let plant_ranges: Vec<(u64, u64)> =
         maps.iter().fold(vec![seed_range], planter::get_dest_ranges);
-    return *plant_ranges.iter().map(|r| &r.0).min().unwrap();
+    plant_ranges.iter().filter(|r| r.1 > 0).map(|r| r.0).min()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    // Mappings with disjoint sources in any order, like the almanac's
+    fn map_ranges() -> impl Strategy<Value = Vec<MapRange>> {
+        prop::collection::vec((0_u64..8, 1_u64..8, 0_u64..64), 0..5)
+            .prop_map(|ranges| {
+                let mut source_start = 0;
+                ranges
+                    .into_iter()
+                    .map(|(gap, range, destination_start)| {
+                        source_start += gap;
+                        let map_range = make_map_range(destination_start, source_start, range);
+                        source_start += range;
+                        map_range
+                    })
+                    .collect::<Vec<MapRange>>()
+            })
+            .prop_shuffle()
+    }
+
+    fn values(ranges: &[(u64, u64)]) -> BTreeSet<u64> {
+        ranges
+            .iter()
+            .flat_map(|(start, length)| *start..start + length)
+            .collect()
+    }
 
     #[test]
     fn use_seed_location() {
@@ -83,7 +122,7 @@ mod tests {
                 (77, 11),
                 vec![vec![make_map_range(0, 69, 1), make_map_range(1, 0, 69),]]
             ),
-            77
+            Some(77)
         );
 
         assert_eq!(
@@ -91,27 +130,27 @@ mod tests {
                 (45, 3),
                 vec![vec![make_map_range(0, 69, 1), make_map_range(1, 0, 69),]]
             ),
-            46
+            Some(46)
         );
 
         assert_eq!(
             find_location_for_seed_range((74, 14), vec![vec![make_map_range(45, 77, 23),]]),
-            45
+            Some(45)
         );
 
         assert_eq!(
             find_location_for_seed_range((10, 5), vec![vec![make_map_range(0, 8, 5)]]),
-            2
+            Some(2)
         );
 
         assert_eq!(
             find_location_for_seed_range((10, 5), vec![vec![make_map_range(0, 20, 5)]]),
-            10
+            Some(10)
         );
 
         assert_eq!(
             find_location_for_seed_range((10, 5), vec![vec![make_map_range(0, 10, 5)]]),
-            0
+            Some(0)
         );
 
         assert_eq!(
@@ -122,12 +161,12 @@ mod tests {
                     vec![make_map_range(10, 0, 5)]
                 ]
             ),
-            10
+            Some(10)
         );
 
         assert_eq!(
             find_location_for_seed_range((13, 5), vec![vec![make_map_range(0, 10, 5)]]),
-            3
+            Some(3)
         );
     }
 
@@ -167,7 +206,8 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4",
-        );
+        )
+        .unwrap();
 
         assert_eq!(
             find_location_for_seed(plant_map.seeds[0], plant_map.clone().maps),
@@ -190,6 +230,36 @@ humidity-to-location map:
         );
     }
 
+    #[test]
+    fn it_reports_missing_seeds() {
+        let no_seeds = PlantMap {
+            seeds: vec![],
+            maps: vec![],
+        };
+        assert_eq!(plant_location(&no_seeds), Err(SolveError::NoSeeds));
+        assert_eq!(plant_ranged_location(&no_seeds), Err(SolveError::NoSeeds));
+
+        let unpaired = PlantMap {
+            seeds: vec![79, 14, 55],
+            maps: vec![],
+        };
+        assert_eq!(plant_location(&unpaired), Ok(14));
+        assert_eq!(
+            plant_ranged_location(&unpaired),
+            Err(SolveError::UnpairedSeeds(3))
+        );
+
+        let empty_range = PlantMap {
+            seeds: vec![79, 0],
+            maps: vec![vec![make_map_range(0, 10, 5)]],
+        };
+        assert_eq!(plant_location(&empty_range), Ok(0));
+        assert_eq!(
+            plant_ranged_location(&empty_range),
+            Err(SolveError::NoSeeds)
+        );
+    }
+
     fn make_map_range(destination_start: u64, source_start: u64, range: u64) -> MapRange {
         MapRange {
             destination_start,
@@ -197,4 +267,31 @@ humidity-to-location map:
             range,
         }
     }
+
+    proptest! {
+        #[test]
+        fn it_maps_ranges_like_every_seed(
+            start in 0_u64..64,
+            length in 1_u64..16,
+            ranges in map_ranges(),
+        ) {
+            let mapped = planter::get_dest_ranges(vec![(start, length)], &ranges);
+            let seeds: BTreeSet<u64> = (start..start + length)
+                .map(|seed| find_location_for_seed(seed, vec![ranges.clone()]))
+                .collect();
+            prop_assert_eq!(values(&mapped), seeds);
+        }
+
+        #[test]
+        fn it_finds_ranged_location_like_every_seed(
+            start in 0_u64..64,
+            length in 1_u64..16,
+            maps in prop::collection::vec(map_ranges(), 1..4),
+        ) {
+            let lowest = (start..start + length)
+                .map(|seed| find_location_for_seed(seed, maps.clone()))
+                .min();
+            prop_assert_eq!(find_location_for_seed_range((start, length), maps), lowest);
+        }
+    }
 }
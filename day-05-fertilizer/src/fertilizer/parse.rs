@@ -1,51 +1,95 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-
-lazy_static! {
-    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
+use std::str::FromStr;
+
+use aoc_core::error::parse_at;
+use aoc_core::input::lines;
+use aoc_core::input::paragraphs;
+use aoc_core::numbers::parse_numbers;
+use aoc_core::Span;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `seeds: <numbers>` {0}")]
+    MissingSeeds(Span),
+    #[error("range outside of any `<source>-to-<destination> map:` section {0}")]
+    MissingHeader(Span),
+    #[error("expected `<destination> <source> <length>` {0}")]
+    MalformedRange(Span),
+    #[error("invalid number {0}")]
+    InvalidNumber(Span),
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A line of a map, sending `source_start..source_start + range` to `destination_start`
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MapRange {
     pub destination_start: u64,
     pub source_start: u64,
     pub range: u64,
 }
 
-#[derive(Debug, Clone)]
+/// The almanac, its seeds and the maps from seed to location in order
+#[derive(Debug, Clone, PartialEq)]
 pub struct PlantMap {
     pub seeds: Vec<u64>,
     pub maps: Vec<Vec<MapRange>>,
 }
 
-pub fn parse_map(map: &str) -> PlantMap {
-    let (seeds_str, maps_str) = map.split_once("\n\n").unwrap();
+impl FromStr for PlantMap {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_map(input)
+    }
+}
+
+/// Reads the almanac, its maps have to come in the seed to location order
+pub fn parse_map(map: &str) -> Result<PlantMap, ParseError> {
+    // sections are told apart by their headers, blank lines only separate them
+    let mut lines = paragraphs(map)
+        .into_iter()
+        .flat_map(|(first_line, section)| {
+            lines(section)
+                .enumerate()
+                .map(move |(index, line)| (first_line + index, line))
+        });
+    let (index, seeds_line) = lines.next().unwrap_or((0, map));
+    let seeds_str = seeds_line
+        .strip_prefix("seeds:")
+        .ok_or_else(|| ParseError::MissingSeeds(Span::line(index, seeds_line)))?;
+    let seeds: Vec<u64> = parse_numbers(seeds_str)
+        .map_err(|number| ParseError::InvalidNumber(Span::at(index, seeds_line, number)))?;
+    if seeds.is_empty() {
+        return Err(ParseError::MissingSeeds(Span::line(index, seeds_line)));
+    }
 
-    PlantMap {
-        seeds: NUM_RE
-            .find_iter(seeds_str)
-            .map(|x| x.as_str().parse::<u64>().unwrap())
-            .collect(),
-        maps: maps_str.split("\n\n").map(parse_mapping).collect(),
+    let mut maps: Vec<Vec<MapRange>> = vec![];
+    for (index, line) in lines {
+        if line.ends_with(" map:") {
+            maps.push(vec![]);
+            continue;
+        }
+        maps.last_mut()
+            .ok_or_else(|| ParseError::MissingHeader(Span::line(index, line)))?
+            .push(parse_mapping(index, line)?);
     }
+
+    Ok(PlantMap { seeds, maps })
 }
 
-fn parse_mapping(input: &str) -> Vec<MapRange> {
-    return input
-        .split("\n")
-        .skip(1)
-        .map(|numbers| {
-            let [destination_start, source_start, range] = NUM_RE
-            .find_iter(&numbers)
-            .map(|x| x.as_str().parse::<u64>().unwrap())
-            .collect::<Vec<u64>>()[..] else {panic!("Can't parse input:\n{input}")};
-            return MapRange {
-                destination_start,
-                source_start,
-                range,
-            };
-        })
-        .collect();
+fn parse_mapping(index: usize, line: &str) -> Result<MapRange, ParseError> {
+    let numbers = line
+        .split_whitespace()
+        .map(|number| parse_at::<u64>(index, line, number).map_err(ParseError::InvalidNumber))
+        .collect::<Result<Vec<u64>, _>>()?;
+    let [destination_start, source_start, range] = numbers[..] else {
+        return Err(ParseError::MalformedRange(Span::line(index, line)));
+    };
+
+    Ok(MapRange {
+        destination_start,
+        source_start,
+        range,
+    })
 }
 
 #[cfg(test)]
@@ -88,9 +132,30 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4",
-        );
+        )
+        .unwrap();
 
         assert_eq!(plant_map.seeds.len(), 4);
         assert_eq!(plant_map.maps.len(), 7);
     }
+
+    #[test]
+    fn reports_malformed_almanac() {
+        assert_eq!(
+            parse_map("seed: 79 14"),
+            Err(ParseError::MissingSeeds(Span::new(1, 1, "seed: 79 14")))
+        );
+        assert_eq!(
+            parse_map("seeds: 79 14\n\n50 98 2"),
+            Err(ParseError::MissingHeader(Span::new(3, 1, "50 98 2")))
+        );
+        assert_eq!(
+            parse_map("seeds: 79 14\n\nseed-to-soil map:\n50 98"),
+            Err(ParseError::MalformedRange(Span::new(4, 1, "50 98")))
+        );
+        assert_eq!(
+            parse_map("seeds: 79 14\n\nseed-to-soil map:\n50 x 2"),
+            Err(ParseError::InvalidNumber(Span::new(4, 4, "x")))
+        );
+    }
 }
@@ -12,44 +12,31 @@ pub fn get_dest_ranges(
         let mut delete_source_ranges: Vec<(u64, u64)> = vec![];
         let mut add_source_ranges: Vec<(u64, u64)> = vec![];
         for source_range in source_ranges_tacker.iter() {
-            // source is fully below the mapping
-            if source_range.0 + source_range.1 <= map_range.source_start {
-                continue;
-            }
+            let (start, end) = (source_range.0, source_range.0 + source_range.1);
+            let map_end = map_range.source_start + map_range.range;
 
-            // mapping is fully below the source
-            if map_range.source_start + map_range.range <= source_range.0 {
+            // source and mapping don't overlap
+            if end <= map_range.source_start || map_end <= start {
                 continue;
             }
 
-            // source is fully within the mapping
-            if source_range.0 >= map_range.source_start
-                && source_range.0 + source_range.1 <= map_range.source_start + map_range.range
-            {
-                let offset = source_range.0 - map_range.source_start;
-                dest_ranges.push((map_range.destination_start + offset, source_range.1));
-                delete_source_ranges.push(*source_range);
-                continue;
-            }
+            let overlap_start = cmp::max(start, map_range.source_start);
+            let overlap_end = cmp::min(end, map_end);
+            let offset = overlap_start - map_range.source_start;
+            dest_ranges.push((
+                map_range.destination_start + offset,
+                overlap_end - overlap_start,
+            ));
+            delete_source_ranges.push(*source_range);
 
-            // source is partially below the mapping
-            if source_range.0 < map_range.source_start {
-                let before_range = map_range.source_start - source_range.0;
-                dest_ranges.push((map_range.destination_start, source_range.1 - before_range));
-                delete_source_ranges.push(*source_range);
-                add_source_ranges.push((source_range.0, before_range));
-                continue;
+            // parts of the source below and above the mapping are left for
+            // the other mappings
+            if start < overlap_start {
+                add_source_ranges.push((start, overlap_start - start));
+            }
+            if overlap_end < end {
+                add_source_ranges.push((overlap_end, end - overlap_end));
             }
-
-            // partially above
-            let offset = source_range.0 - map_range.source_start;
-            let inclusive_range = map_range.source_start + map_range.range - source_range.0;
-            dest_ranges.push((map_range.destination_start + offset, inclusive_range));
-            delete_source_ranges.push(*source_range);
-            add_source_ranges.push((
-                source_range.0 + inclusive_range,
-                source_range.1 - inclusive_range,
-            ));
         }
 
         source_ranges_tacker.retain(|x| !delete_source_ranges.contains(x));
@@ -58,15 +45,15 @@ pub fn get_dest_ranges(
         }
     }
     dest_ranges.append(&mut source_ranges_tacker);
-    return merge_ranges(dest_ranges);
+    merge_ranges(dest_ranges)
 }
 
 fn merge_ranges(original: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
     let mut source_ranges = original.clone();
-    source_ranges.sort_by(|a, b| a.0.cmp(&b.0));
+    source_ranges.sort_by_key(|a| a.0);
     let mut merged: Vec<(u64, u64)> = vec![];
     for range in source_ranges.iter() {
-        if merged.len() == 0 {
+        if merged.is_empty() {
             merged.push(*range);
             continue;
         }
@@ -82,7 +69,7 @@ fn merge_ranges(original: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
             merged.push(*range);
         }
     }
-    return merged;
+    merged
 }
 
 #[cfg(test)]
@@ -136,6 +123,12 @@ mod tests {
             vec![(5, 5), (15, 5)]
         );
 
+        // mapping within the source
+        assert_eq!(
+            get_dest_ranges(vec![(8, 14)], &vec![make_map_range(0, 9, 1)]),
+            vec![(0, 1), (8, 1), (10, 12)]
+        );
+
         assert_eq!(
             get_dest_ranges(
                 vec![(55, 13)],
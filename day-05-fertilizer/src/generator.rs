@@ -0,0 +1,45 @@
+use aoc_core::generate::{InputRng, Rng};
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+const LIMIT: u64 = 1 << 32;
+
+// `size` seed ranges, every map moves up to `size` disjoint slices of the
+// numbers below 2^32
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    let seeds: Vec<String> = (0..size)
+        .flat_map(|_| [rng.gen_range(0..LIMIT / 2), rng.gen_range(1..LIMIT / 64)])
+        .map(|number| number.to_string())
+        .collect();
+
+    let mut almanac = vec![format!("seeds: {}", seeds.join(" "))];
+    for pair in CATEGORIES.windows(2) {
+        let mut cuts: Vec<u64> = (0..size).map(|_| rng.gen_range(1..LIMIT)).collect();
+        cuts.extend([0, LIMIT]);
+        cuts.sort();
+        cuts.dedup();
+
+        almanac.push(String::new());
+        almanac.push(format!("{}-to-{} map:", pair[0], pair[1]));
+        for cut in cuts.windows(2) {
+            // numbers outside every slice keep their value
+            if rng.gen_bool(0.2) {
+                continue;
+            }
+            let (source, length) = (cut[0], cut[1] - cut[0]);
+            let destination = rng.gen_range(0..=LIMIT - length);
+            almanac.push(format!("{destination} {source} {length}"));
+        }
+    }
+
+    almanac.join("\n")
+}
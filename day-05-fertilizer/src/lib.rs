@@ -0,0 +1,43 @@
+//! Day 5: If You Give A Seed A Fertilizer
+//!
+//! [`fertilizer::parse_map`] reads the almanac into a [`fertilizer::PlantMap`],
+//! [`fertilizer::plant_location`] and [`fertilizer::plant_ranged_location`] follow it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+use crate::fertilizer::plant_location;
+use crate::fertilizer::plant_ranged_location;
+use crate::fertilizer::PlantMap;
+
+pub mod fertilizer;
+mod generator;
+
+pub struct Fertilizer;
+
+impl Puzzle for Fertilizer {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "fertilizer";
+
+    type Input = PlantMap;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(plant_map: &Self::Input) -> Result<Answer, Error> {
+        Ok(plant_location(plant_map)?.into())
+    }
+
+    fn part_two(plant_map: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(
+            plant_ranged_location(plant_map)
+                .map(Answer::from)
+                .map_err(Error::from),
+        )
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -0,0 +1,21 @@
+use aoc_core::generate::{InputRng, Rng};
+
+// the single race reading joins every time, keep it countable
+const MAX_RACES: usize = 4;
+
+// `size` races, up to four, each with a record that can be beaten
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let races: Vec<(u64, u64)> = (0..size.clamp(1, MAX_RACES))
+        .map(|_| {
+            let time = rng.gen_range(10..100);
+            (time, rng.gen_range(1..time * time / 4))
+        })
+        .collect();
+
+    let times: String = races.iter().map(|(time, _)| format!("{time:>5}")).collect();
+    let distances: String = races
+        .iter()
+        .map(|(_, distance)| format!("{distance:>5}"))
+        .collect();
+    format!("Time:    {times}\nDistance:{distances}")
+}
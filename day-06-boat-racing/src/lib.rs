@@ -0,0 +1,39 @@
+//! Day 6: Wait For It
+//!
+//! [`race::parse_records`] reads the [`race::Records`], [`race::num_ways_to_win`] and
+//! [`race::num_ways_to_win_single_race`] count the ways to beat them.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+use crate::race::num_ways_to_win;
+use crate::race::num_ways_to_win_single_race;
+use crate::race::Records;
+
+mod generator;
+pub mod race;
+
+pub struct BoatRacing;
+
+impl Puzzle for BoatRacing {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "boat-racing";
+
+    type Input = Records;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(records: &Self::Input) -> Result<Answer, Error> {
+        Ok(num_ways_to_win(records).into())
+    }
+
+    fn part_two(records: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(num_ways_to_win_single_race(records).into()))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
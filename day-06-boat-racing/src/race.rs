@@ -1,29 +1,41 @@
-use lazy_static::lazy_static;
-use regex::{Match, Regex};
-
-lazy_static! {
-    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
+use std::str::FromStr;
+
+use aoc_core::input::lines;
+use aoc_core::numbers::parse_numbers;
+use aoc_core::Span;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `{0}` line {1}")]
+    MissingLine(&'static str, Span),
+    #[error("invalid number {0}")]
+    InvalidNumber(Span),
+    #[error("number of distances doesn't match number of times {0}")]
+    MismatchedRaces(Span),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct RaceData {
     time: usize,
     distance: usize,
 }
 
-pub fn num_ways_to_win(records: &str) -> usize {
-    let leaderboard = parse_race(records);
-
-    return leaderboard
-        .iter()
-        .map(num_ways_to_win_race)
-        .fold(1, |acc, res| acc * res);
+/// Times and record distances of the races
+#[derive(Debug, PartialEq)]
+pub struct Records {
+    races: Vec<RaceData>,
+    single_race: RaceData,
 }
 
-pub fn num_ways_to_win_single_race(race_card: &str) -> usize {
-    let race_data = &parse_race_card(race_card);
+/// Product of the number of ways to beat each record
+pub fn num_ways_to_win(records: &Records) -> usize {
+    records.races.iter().map(num_ways_to_win_race).product()
+}
 
-    return num_ways_to_win_race(race_data);
+/// Ways to beat the record when the digits of all races make one race
+pub fn num_ways_to_win_single_race(records: &Records) -> usize {
+    num_ways_to_win_race(&records.single_race)
 }
 
 fn num_ways_to_win_race(race_data: &RaceData) -> usize {
@@ -33,43 +45,57 @@ fn num_ways_to_win_race(race_data: &RaceData) -> usize {
             counter += 1;
         }
     }
-    return counter;
+    counter
 }
 
-fn parse_race(records: &str) -> Vec<RaceData> {
-    let (time_str, distance_str) = records.split_once("\n").unwrap();
-    let mut res: Vec<RaceData> = vec![];
+impl FromStr for Records {
+    type Err = ParseError;
 
-    let time_data: Vec<usize> = NUM_RE.find_iter(time_str).map(match_tom_num).collect();
-    let distance_data: Vec<usize> = NUM_RE.find_iter(distance_str).map(match_tom_num).collect();
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_records(input)
+    }
+}
 
-    for index in 0..time_data.len() {
-        res.push(RaceData {
-            time: time_data[index],
-            distance: distance_data[index],
-        });
+/// Reads the `Time:` and `Distance:` lines
+pub fn parse_records(records: &str) -> Result<Records, ParseError> {
+    let mut lines = lines(records);
+    let time_str = lines.next().unwrap_or_default();
+    let distance_str = lines.next().unwrap_or_default();
+    let time_data = parse_line(0, time_str, "Time:")?;
+    let distance_data = parse_line(1, distance_str, "Distance:")?;
+    if time_data.len() != distance_data.len() {
+        return Err(ParseError::MismatchedRaces(Span::line(1, distance_str)));
     }
 
-    return res;
-}
+    let races = time_data
+        .iter()
+        .zip(distance_data.iter())
+        .map(|(&time, &distance)| RaceData { time, distance })
+        .collect();
 
-fn parse_race_card(race_card: &str) -> RaceData {
-    let (time_str, distance_str) = race_card.split_once("\n").unwrap();
+    let single_race = RaceData {
+        time: parse_joined(0, time_str)?,
+        distance: parse_joined(1, distance_str)?,
+    };
+
+    Ok(Records { races, single_race })
+}
 
-    let time: usize = NUM_RE
-        .find(time_str.to_string().replace(" ", "").as_str())
-        .map(match_tom_num)
-        .unwrap();
-    let distance: usize = NUM_RE
-        .find(distance_str.to_string().replace(" ", "").as_str())
-        .map(match_tom_num)
-        .unwrap();
+fn parse_line(index: usize, line: &str, prefix: &'static str) -> Result<Vec<usize>, ParseError> {
+    let numbers = line
+        .strip_prefix(prefix)
+        .ok_or_else(|| ParseError::MissingLine(prefix, Span::line(index, line)))?;
 
-    return RaceData { time, distance };
+    parse_numbers(numbers)
+        .map_err(|number| ParseError::InvalidNumber(Span::at(index, line, number)))
 }
 
-fn match_tom_num(x: Match) -> usize {
-    return x.as_str().parse::<usize>().unwrap();
+// The kerning-free reading of a line, where all digits form a single number
+fn parse_joined(index: usize, line: &str) -> Result<usize, ParseError> {
+    let digits: String = line.chars().filter(|c| c.is_ascii_digit()).collect();
+    digits
+        .parse()
+        .map_err(|_| ParseError::InvalidNumber(Span::line(index, line)))
 }
 
 #[cfg(test)]
@@ -102,4 +128,23 @@ mod tests {
             9
         );
     }
+
+    #[test]
+    fn it_parses_records() {
+        let records = parse_records("Time:      7  15   30\nDistance:  9  40  200").unwrap();
+        assert_eq!(num_ways_to_win(&records), 288);
+        assert_eq!(num_ways_to_win_single_race(&records), 71503);
+    }
+
+    #[test]
+    fn it_reports_malformed_records() {
+        assert_eq!(
+            parse_records("Time: 7 15\nDistance: 9"),
+            Err(ParseError::MismatchedRaces(Span::new(2, 1, "Distance: 9")))
+        );
+        assert_eq!(
+            parse_records("Time: 7"),
+            Err(ParseError::MissingLine("Distance:", Span::new(2, 1, "")))
+        );
+    }
 }
@@ -1,15 +1,108 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 
-static CARDS: &str = "AKQJT987654321";
+use aoc_core::error::{find_invalid_char, parse_at};
+use aoc_core::input::lines;
+use aoc_core::Span;
+use thiserror::Error;
 
-#[derive(Debug, Clone)]
-struct Hand {
+// Cards from the weakest
+static CARDS: &str = "23456789TJQKA";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<cards> <bid>` {0}")]
+    MalformedLine(Span),
+    #[error("hand must have 5 cards {0}")]
+    InvalidHandSize(Span),
+    #[error("unknown card {0}")]
+    UnknownCard(Span),
+    #[error("invalid bid {0}")]
+    InvalidBid(Span),
+}
+
+/// A hand of cards and what was bid on it
+#[derive(Debug, PartialEq)]
+pub struct Bet {
     cards: String,
+    bid: usize,
+}
+
+impl Bet {
+    /// Bet of `bid` on five cards, as in `KTJJT`
+    pub fn new(cards: &str, bid: usize) -> Result<Bet, ParseError> {
+        check_cards(0, cards, cards)?;
+        Ok(Bet {
+            cards: cards.to_string(),
+            bid,
+        })
+    }
+
+    pub fn cards(&self) -> &str {
+        &self.cards
+    }
+
+    pub fn bid(&self) -> usize {
+        self.bid
+    }
+
+    /// The hand the cards make, with `J` as jokers instead of jacks
+    pub fn hand(&self, joker: bool) -> Hand {
+        // the cards of a bet are already checked
+        Hand::from_checked(&self.cards, joker)
+    }
+}
+
+/// Every bet of the game
+#[derive(Debug, PartialEq)]
+pub struct Bets(pub Vec<Bet>);
+
+impl FromStr for Bets {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        lines(input)
+            .enumerate()
+            .map(|(index, line)| parse_bet(index, line))
+            .collect::<Result<_, _>>()
+            .map(Bets)
+    }
+}
+
+/// Reads a `<cards> <bid>` line
+pub fn parse_bet(index: usize, line: &str) -> Result<Bet, ParseError> {
+    let (cards, bid) = line
+        .split_once(" ")
+        .ok_or_else(|| ParseError::MalformedLine(Span::line(index, line)))?;
+    check_cards(index, line, cards)?;
+
+    Ok(Bet {
+        cards: cards.to_string(),
+        bid: parse_at(index, line, bid).map_err(ParseError::InvalidBid)?,
+    })
+}
+
+// Five known cards, `cards` being a slice of `line`
+fn check_cards(index: usize, line: &str, cards: &str) -> Result<(), ParseError> {
+    if let Some(span) = find_invalid_char(index, cards, |card| CARDS.contains(card)) {
+        return Err(ParseError::UnknownCard(span));
+    }
+    if cards.len() != 5 {
+        return Err(ParseError::InvalidHandSize(Span::at(index, line, cards)));
+    }
+    Ok(())
+}
+
+/// Hands order by strength first, then card by card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    // values of the cards, from 1 for a two up to 13 for an ace and 0 for a joker
+    cards: Vec<u8>,
     strength: Strength,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Strength {
+/// Types of hands, from the strongest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strength {
     FiveOfKind = 0,
     FourOfKind = 1,
     FullHouse = 2,
@@ -20,225 +113,246 @@ enum Strength {
 }
 
 impl Hand {
-    fn new(cards: &str) -> Hand {
-        return Hand {
-            cards: cards.to_string(),
-            strength: Hand::strength(cards),
-        };
+    /// Hand of five cards, as in `KTJJT`
+    pub fn new(cards: &str) -> Result<Hand, ParseError> {
+        check_cards(0, cards, cards)?;
+        Ok(Hand::from_checked(cards, false))
     }
 
-    fn new_with_joker(cards: &str) -> Hand {
-        return Hand {
-            cards: cards.replace("J", "1"),
-            strength: Hand::strength_with_joker(cards),
-        };
+    /// Hand of five cards with `J` as jokers, that count as whichever card
+    /// makes the hand strongest but are the weakest card on their own
+    pub fn new_with_joker(cards: &str) -> Result<Hand, ParseError> {
+        check_cards(0, cards, cards)?;
+        Ok(Hand::from_checked(cards, true))
     }
 
-    fn strength(cards: &str) -> Strength {
-        let mut groups: HashMap<char, usize> = HashMap::new();
-        for c in cards.chars() {
-            *groups.entry(c).or_insert(0) += 1;
-        }
+    fn from_checked(cards: &str, joker: bool) -> Hand {
+        let cards: Vec<u8> = cards
+            .chars()
+            .map(|card| match (card, joker) {
+                ('J', true) => 0,
+                _ => CARDS.find(card).map_or(0, |value| value as u8 + 1),
+            })
+            .collect();
+        let strength = Hand::strength_of(&cards);
+        Hand { cards, strength }
+    }
 
-        return match groups.len() {
-            1 => Strength::FiveOfKind,
-            2 => match groups.iter().any(|group| group.1.eq(&4)) {
-                true => Strength::FourOfKind,
-                false => Strength::FullHouse,
-            },
-            3 => match groups.iter().any(|group| group.1.eq(&3)) {
-                true => Strength::ThreeOfKind,
-                false => Strength::TwoPairs,
-            },
-            4 => Strength::OnePair,
-            5 => Strength::HighCard,
-            x => panic!("Not allowed group count {x}, cards: {cards}"),
-        };
+    pub fn strength(&self) -> Strength {
+        self.strength
     }
 
-    fn strength_with_joker(cards: &str) -> Strength {
-        let jokers = cards.chars().filter(|card| card.eq(&'J')).count();
-        if jokers == 5 {
-            return Strength::FiveOfKind;
+    // Jokers join the largest group of other cards
+    fn strength_of(cards: &[u8]) -> Strength {
+        let mut groups: HashMap<u8, usize> = HashMap::new();
+        for card in cards.iter().filter(|card| **card != 0) {
+            *groups.entry(*card).or_insert(0) += 1;
         }
-        let strength = Hand::strength(
-            cards
-                .replacen("J", "X", 1)
-                .replacen("J", "Y", 1)
-                .replacen("J", "Z", 1)
-                .replacen("J", "*", 1)
-                .as_str(),
-        );
-        if strength.eq(&Strength::FiveOfKind) {
-            return strength;
+        let mut sizes: Vec<usize> = groups.into_values().collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        let jokers = cards.len() - sizes.iter().sum::<usize>();
+        match sizes.first_mut() {
+            Some(largest) => *largest += jokers,
+            None => sizes.push(jokers),
         }
-        return match jokers {
-            0 => strength,
-            1 => match strength {
-                Strength::FourOfKind => Strength::FiveOfKind,
-                Strength::ThreeOfKind => Strength::FourOfKind,
-                Strength::TwoPairs => Strength::FullHouse,
-                Strength::OnePair => Strength::ThreeOfKind,
-                Strength::HighCard => Strength::OnePair,
-                _ => panic!(
-                    "Can't map strength {:?} with single joker in {cards}",
-                    strength
-                ),
-            },
-            2 => match strength {
-                Strength::ThreeOfKind => Strength::FiveOfKind,
-                Strength::OnePair => Strength::FourOfKind,
-                Strength::HighCard => Strength::ThreeOfKind,
-                _ => {
-                    panic!(
-                        "Can't map strength {:?} with single 2 jokers in {cards}",
-                        strength
-                    )
-                }
-            },
-            3 => match strength {
-                Strength::OnePair => Strength::FiveOfKind,
-                Strength::HighCard => Strength::FourOfKind,
-                _ => panic!("Can't map strength with 3 jokers {:?}", strength),
-            },
-            4 => Strength::FiveOfKind,
-            x => panic!("Too much jokers: {x}, max allowed 5"),
-        };
-    }
-
-    fn cmp(&self, other: &Hand) -> Ordering {
-        if self.strength.eq(&other.strength) {
-            return self.cmp_start(other);
-        } else if (self.strength as usize) < other.strength as usize {
-            return Ordering::Greater;
-        } else {
-            return Ordering::Less;
+
+        match sizes[..] {
+            [5, ..] => Strength::FiveOfKind,
+            [4, ..] => Strength::FourOfKind,
+            [3, 2] => Strength::FullHouse,
+            [3, ..] => Strength::ThreeOfKind,
+            [2, 2, ..] => Strength::TwoPairs,
+            [2, ..] => Strength::OnePair,
+            _ => Strength::HighCard,
         }
     }
+}
 
-    fn cmp_start(&self, other: &Hand) -> Ordering {
-        for (index, card) in self.cards.char_indices() {
-            let other_card = other.cards.chars().nth(index).unwrap();
-            if card == other_card {
-                continue;
-            }
-
-            if CARDS.find(card).unwrap() < CARDS.find(other_card).unwrap() {
-                return Ordering::Greater;
-            }
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // strengths are numbered from the strongest
+        (other.strength as u8)
+            .cmp(&(self.strength as u8))
+            .then_with(|| self.cards.cmp(&other.cards))
+    }
+}
 
-            return Ordering::Less;
-        }
-        return Ordering::Equal;
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-pub fn winnings(game: &str, joker: bool) -> Vec<usize> {
-    let mut hands: Vec<(Hand, usize)> = game
-        .split("\n")
-        .map(|line| {
-            let (cards, bid) = line.split_once(" ").unwrap();
-            let hand = if joker {
-                Hand::new_with_joker(cards)
-            } else {
-                Hand::new(cards)
-            };
-            return (hand, bid.parse::<usize>().unwrap());
-        })
-        .collect();
+/// Winnings of each bet, its bid times the rank of its hand
+pub fn winnings(game: &[Bet], joker: bool) -> Vec<usize> {
+    let mut hands: Vec<(Hand, usize)> = game.iter().map(|bet| (bet.hand(joker), bet.bid)).collect();
     hands.sort_by(|a, b| a.0.cmp(&b.0));
-    return hands
+    hands
         .iter()
         .enumerate()
         .map(|(rank, (_hand, bid))| (rank + 1) * bid)
-        .collect::<Vec<usize>>();
+        .collect::<Vec<usize>>()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn bets(game: &str) -> Vec<Bet> {
+        game.split("\n")
+            .enumerate()
+            .map(|(index, line)| parse_bet(index, line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn check_malformed_bets() {
+        assert_eq!(
+            parse_bet(0, "AAAAA"),
+            Err(ParseError::MalformedLine(Span::new(1, 1, "AAAAA")))
+        );
+        assert_eq!(
+            parse_bet(2, "AAXAA 10"),
+            Err(ParseError::UnknownCard(Span::new(3, 3, "X")))
+        );
+        assert_eq!(
+            parse_bet(0, "AAAAAA 10"),
+            Err(ParseError::InvalidHandSize(Span::new(1, 1, "AAAAAA")))
+        );
+        assert_eq!(
+            parse_bet(0, "AAAAA -3"),
+            Err(ParseError::InvalidBid(Span::new(1, 7, "-3")))
+        );
+    }
+
+    fn strength(cards: &str) -> Strength {
+        Hand::new(cards).unwrap().strength()
+    }
+
+    fn strength_with_joker(cards: &str) -> Strength {
+        Hand::new_with_joker(cards).unwrap().strength()
+    }
+
+    fn hand(cards: &str) -> Hand {
+        Hand::new(cards).unwrap()
+    }
+
+    #[test]
+    fn check_bets_built_in_code() {
+        let bet = Bet::new("KTJJT", 220).unwrap();
+        assert_eq!(bet, parse_bet(0, "KTJJT 220").unwrap());
+        assert_eq!(bet.hand(true).strength(), Strength::FourOfKind);
+        assert_eq!(
+            Bet::new("KTJ", 220),
+            Err(ParseError::InvalidHandSize(Span::new(1, 1, "KTJ")))
+        );
+        assert_eq!(
+            Bet::new("KTjJT", 220),
+            Err(ParseError::UnknownCard(Span::new(1, 3, "j")))
+        );
+    }
+
+    #[test]
+    fn check_malformed_hands() {
+        assert_eq!(
+            Hand::new(""),
+            Err(ParseError::InvalidHandSize(Span::new(1, 1, "")))
+        );
+        assert_eq!(
+            Hand::new("AKQJT9"),
+            Err(ParseError::InvalidHandSize(Span::new(1, 1, "AKQJT9")))
+        );
+        assert_eq!(
+            Hand::new_with_joker("ZZZZZ"),
+            Err(ParseError::UnknownCard(Span::new(1, 1, "Z")))
+        );
+        assert_eq!(
+            Hand::new_with_joker("AA1AA"),
+            Err(ParseError::UnknownCard(Span::new(1, 3, "1")))
+        );
+    }
+
     #[test]
     fn check_strength_with_no_jokers() {
-        assert_eq!(Hand::strength("AAAAA"), Strength::FiveOfKind);
-        assert_eq!(Hand::strength("AA8AA"), Strength::FourOfKind);
-        assert_eq!(Hand::strength("A88AA"), Strength::FullHouse);
-        assert_eq!(Hand::strength("A28AA"), Strength::ThreeOfKind);
-        assert_eq!(Hand::strength("228AA"), Strength::TwoPairs);
-        assert_eq!(Hand::strength("228JA"), Strength::OnePair);
-        assert_eq!(Hand::strength("2K8JA"), Strength::HighCard);
+        assert_eq!(strength("AAAAA"), Strength::FiveOfKind);
+        assert_eq!(strength("AA8AA"), Strength::FourOfKind);
+        assert_eq!(strength("A88AA"), Strength::FullHouse);
+        assert_eq!(strength("A28AA"), Strength::ThreeOfKind);
+        assert_eq!(strength("228AA"), Strength::TwoPairs);
+        assert_eq!(strength("228JA"), Strength::OnePair);
+        assert_eq!(strength("2K8JA"), Strength::HighCard);
     }
 
     #[test]
     fn check_strength_with_one_joker() {
-        assert_eq!(Hand::strength_with_joker("AAAAA"), Strength::FiveOfKind);
-        assert_eq!(Hand::strength_with_joker("JJJJJ"), Strength::FiveOfKind);
-        assert_eq!(Hand::strength_with_joker("AJAAA"), Strength::FiveOfKind);
+        assert_eq!(strength_with_joker("AAAAA"), Strength::FiveOfKind);
+        assert_eq!(strength_with_joker("JJJJJ"), Strength::FiveOfKind);
+        assert_eq!(strength_with_joker("AJAAA"), Strength::FiveOfKind);
     }
 
     #[test]
     fn check_strength_with_two_jokers() {
-        assert_eq!(Hand::strength_with_joker("AAA8J"), Strength::FourOfKind);
-        assert_eq!(Hand::strength_with_joker("AA88J"), Strength::FullHouse);
-        assert_eq!(Hand::strength_with_joker("AA87J"), Strength::ThreeOfKind);
-        assert_eq!(Hand::strength_with_joker("A872J"), Strength::OnePair);
+        assert_eq!(strength_with_joker("AAA8J"), Strength::FourOfKind);
+        assert_eq!(strength_with_joker("AA88J"), Strength::FullHouse);
+        assert_eq!(strength_with_joker("AA87J"), Strength::ThreeOfKind);
+        assert_eq!(strength_with_joker("A872J"), Strength::OnePair);
     }
 
     #[test]
     fn check_strength_with_three_jokers() {
-        assert_eq!(Hand::strength_with_joker("AAA8J"), Strength::FourOfKind);
-        assert_eq!(Hand::strength_with_joker("AA88J"), Strength::FullHouse);
-        assert_eq!(Hand::strength_with_joker("AA87J"), Strength::ThreeOfKind);
-        assert_eq!(Hand::strength_with_joker("A872J"), Strength::OnePair);
+        assert_eq!(strength_with_joker("AAA8J"), Strength::FourOfKind);
+        assert_eq!(strength_with_joker("AA88J"), Strength::FullHouse);
+        assert_eq!(strength_with_joker("AA87J"), Strength::ThreeOfKind);
+        assert_eq!(strength_with_joker("A872J"), Strength::OnePair);
     }
 
     #[test]
     fn check_strength_with_four_jokers() {
-        assert_eq!(Hand::strength_with_joker("AJJJJ"), Strength::FiveOfKind);
+        assert_eq!(strength_with_joker("AJJJJ"), Strength::FiveOfKind);
     }
 
     #[test]
     fn check_strength_with_five_jokers() {
-        assert_eq!(Hand::strength_with_joker("JJJJJ"), Strength::FiveOfKind);
+        assert_eq!(strength_with_joker("JJJJJ"), Strength::FiveOfKind);
     }
 
     #[test]
     fn compare_hands() {
-        assert_eq!(Hand::new("AA8AA").cmp(&Hand::new("AAAAA")), Ordering::Less);
-        assert_eq!(Hand::new("AA8AA").cmp(&Hand::new("AAAAA")), Ordering::Less);
-        assert_eq!(Hand::new("A88AA").cmp(&Hand::new("AA8AA")), Ordering::Less);
-        assert_eq!(Hand::new("A28AA").cmp(&Hand::new("A88AA")), Ordering::Less);
-        assert_eq!(Hand::new("228AA").cmp(&Hand::new("A28AA")), Ordering::Less);
-        assert_eq!(Hand::new("228JA").cmp(&Hand::new("228AA")), Ordering::Less);
-        assert_eq!(Hand::new("2K8JA").cmp(&Hand::new("228JA")), Ordering::Less);
-        assert_eq!(Hand::new("2Q8JA").cmp(&Hand::new("2K8JA")), Ordering::Less);
-        assert_eq!(Hand::new("KTJJT").cmp(&Hand::new("KK677")), Ordering::Less);
+        assert_eq!(hand("AA8AA").cmp(&hand("AAAAA")), Ordering::Less);
+        assert_eq!(hand("AA8AA").cmp(&hand("AAAAA")), Ordering::Less);
+        assert_eq!(hand("A88AA").cmp(&hand("AA8AA")), Ordering::Less);
+        assert_eq!(hand("A28AA").cmp(&hand("A88AA")), Ordering::Less);
+        assert_eq!(hand("228AA").cmp(&hand("A28AA")), Ordering::Less);
+        assert_eq!(hand("228JA").cmp(&hand("228AA")), Ordering::Less);
+        assert_eq!(hand("2K8JA").cmp(&hand("228JA")), Ordering::Less);
+        assert_eq!(hand("2Q8JA").cmp(&hand("2K8JA")), Ordering::Less);
+        assert_eq!(hand("KTJJT").cmp(&hand("KK677")), Ordering::Less);
     }
 
     #[test]
     fn compare_hands_with_joker() {
-        assert_eq!(Hand::new("AA8AA").cmp(&Hand::new("AAAAA")), Ordering::Less);
-        assert_eq!(Hand::new("AA8AA").cmp(&Hand::new("AAAAA")), Ordering::Less);
-        assert_eq!(Hand::new("A88AA").cmp(&Hand::new("AA8AA")), Ordering::Less);
-        assert_eq!(Hand::new("A28AA").cmp(&Hand::new("A88AA")), Ordering::Less);
-        assert_eq!(Hand::new("228AA").cmp(&Hand::new("A28AA")), Ordering::Less);
-        assert_eq!(Hand::new("228JA").cmp(&Hand::new("228AA")), Ordering::Less);
-        assert_eq!(Hand::new("2K8JA").cmp(&Hand::new("228JA")), Ordering::Less);
-        assert_eq!(Hand::new("2Q8JA").cmp(&Hand::new("2K8JA")), Ordering::Less);
-        assert_eq!(Hand::new("KTJJT").cmp(&Hand::new("KK677")), Ordering::Less);
+        assert_eq!(hand("AA8AA").cmp(&hand("AAAAA")), Ordering::Less);
+        assert_eq!(hand("AA8AA").cmp(&hand("AAAAA")), Ordering::Less);
+        assert_eq!(hand("A88AA").cmp(&hand("AA8AA")), Ordering::Less);
+        assert_eq!(hand("A28AA").cmp(&hand("A88AA")), Ordering::Less);
+        assert_eq!(hand("228AA").cmp(&hand("A28AA")), Ordering::Less);
+        assert_eq!(hand("228JA").cmp(&hand("228AA")), Ordering::Less);
+        assert_eq!(hand("2K8JA").cmp(&hand("228JA")), Ordering::Less);
+        assert_eq!(hand("2Q8JA").cmp(&hand("2K8JA")), Ordering::Less);
+        assert_eq!(hand("KTJJT").cmp(&hand("KK677")), Ordering::Less);
     }
 
     #[test]
     fn check_winnings() {
-        assert_eq!(winnings("AAAAA 10", false), vec![10]);
-        assert_eq!(winnings("AAAAA 3\nJAJJJ 15", false), vec![15, 6]);
-        assert_eq!(winnings("QQQJA 4\nT55J5 10", false), vec![10, 8]);
+        assert_eq!(winnings(&bets("AAAAA 10"), false), vec![10]);
+        assert_eq!(winnings(&bets("AAAAA 3\nJAJJJ 15"), false), vec![15, 6]);
+        assert_eq!(winnings(&bets("QQQJA 4\nT55J5 10"), false), vec![10, 8]);
     }
 
     #[test]
     fn check_winnings_with_joker() {
-        assert_eq!(winnings("AAAAA 10", true), vec![10]);
-        assert_eq!(winnings("AAAAA 3\nJAJJJ 15", true), vec![15, 6]);
-        assert_eq!(winnings("QQQJA 4\nT55J5 10", true), vec![10, 8]);
+        assert_eq!(winnings(&bets("AAAAA 10"), true), vec![10]);
+        assert_eq!(winnings(&bets("AAAAA 3\nJAJJJ 15"), true), vec![15, 6]);
+        assert_eq!(winnings(&bets("QQQJA 4\nT55J5 10"), true), vec![10, 8]);
     }
 }
@@ -0,0 +1,21 @@
+use std::collections::HashSet;
+
+use aoc_core::generate::{InputRng, Rng, SliceRandom};
+
+const CARDS: &[u8] = b"AKQJT98765432";
+
+// `size` distinct hands with their bids
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let mut hands = HashSet::new();
+    let mut bets = vec![];
+    while bets.len() < size.max(1) {
+        let hand: String = (0..5)
+            .map(|_| *CARDS.choose(rng).unwrap() as char)
+            .collect();
+        if hands.insert(hand.clone()) {
+            bets.push(format!("{hand} {}", rng.gen_range(1..1000)));
+        }
+    }
+
+    bets.join("\n")
+}
@@ -0,0 +1,63 @@
+//! Day 7: Camel Cards
+//!
+//! [`cards::parse_bet`] reads a [`cards::Bet`], its [`cards::Hand`] orders by strength and
+//! [`cards::winnings`] ranks all of them.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+use crate::cards::{parse_bet, Bets};
+
+pub mod cards;
+mod generator;
+
+pub struct CamelCards;
+
+impl Puzzle for CamelCards {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "camel-cards";
+
+    type Input = Bets;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(bets: &Self::Input) -> Result<Answer, Error> {
+        let sum: usize = cards::winnings(&bets.0, false).iter().sum();
+        Ok(sum.into())
+    }
+
+    fn part_two(bets: &Self::Input) -> Option<Result<Answer, Error>> {
+        let sum_with_joker: usize = cards::winnings(&bets.0, true).iter().sum();
+        Some(Ok(sum_with_joker.into()))
+    }
+
+    // hands are ranked against each other so they are kept, but not the text
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        let mut bets = vec![];
+        let read = read_records(input, b'\n', |index, line| {
+            bets.push(parse_bet(index, line)?);
+            Ok(())
+        });
+        let bets = Bets(bets);
+        Some(read.map(|()| {
+            Streamed {
+                part_one: stopwatch.time(Part::One, || Self::part_one(&bets)),
+                part_two: stopwatch
+                    .time(Part::Two, || Self::part_two(&bets))
+                    .flatten(),
+            }
+        }))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
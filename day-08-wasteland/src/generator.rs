@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use aoc_core::generate::{InputRng, Rng, SliceRandom};
+
+const PERIODS: [usize; 10] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
+const MAX_INSTRUCTIONS: usize = 60;
+const MAX_GHOSTS: usize = 6;
+
+// Name of three uppercase letters ending in `last`
+fn name(rng: &mut InputRng, last: char, taken: &mut HashSet<String>) -> String {
+    loop {
+        let name = format!(
+            "{}{}{last}",
+            rng.gen_range('A'..='Z'),
+            rng.gen_range('A'..='Z')
+        );
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+// `size` instructions, up to 60, and a ghost for every ten of them, up to
+// six. Every ghost walks a cycle whose length is a multiple of the
+// instructions, ending on its `Z` node, the first one from AAA to ZZZ
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let instructions: Vec<char> = (0..size.clamp(1, MAX_INSTRUCTIONS))
+        .map(|_| *['L', 'R'].choose(rng).unwrap())
+        .collect();
+    let ghosts = (size / 10).clamp(1, MAX_GHOSTS);
+
+    let mut taken = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut cycles: Vec<Vec<String>> = vec![];
+    for (ghost, period) in PERIODS.choose_multiple(rng, ghosts).enumerate() {
+        let (start, finish) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (name(rng, 'A', &mut taken), name(rng, 'Z', &mut taken)),
+        };
+        let mut nodes = vec![start];
+        for _ in 1..period * instructions.len() {
+            let last = *b"BCDEFGHIJKLMNOPQRSTUVWXY".choose(rng).unwrap() as char;
+            nodes.push(name(rng, last, &mut taken));
+        }
+        nodes.push(finish);
+        cycles.push(nodes);
+    }
+
+    let all: Vec<String> = cycles.iter().flatten().cloned().collect();
+    let mut network = vec![];
+    for nodes in &cycles {
+        for (step, node) in nodes.iter().enumerate() {
+            // from the finish the ghost walks its cycle again, skipping the start
+            let next = match step + 1 < nodes.len() {
+                true => &nodes[step + 1],
+                false => &nodes[1],
+            };
+            let other = all.choose(rng).unwrap();
+            let (left, right) = match instructions[step % instructions.len()] {
+                'L' => (next, other),
+                _ => (other, next),
+            };
+            network.push(format!("{node} = ({left}, {right})"));
+        }
+    }
+    network.shuffle(rng);
+
+    format!(
+        "{}\n\n{}",
+        String::from_iter(instructions),
+        network.join("\n")
+    )
+}
@@ -0,0 +1,41 @@
+//! Day 8: Haunted Wasteland
+//!
+//! [`navigator::parse_map`] reads the [`navigator::Map`], [`navigator::calculate_steps`]
+//! and [`navigator::calculate_ghost_steps`] walk it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+use crate::navigator::Map;
+
+mod generator;
+pub mod navigator;
+
+pub struct Wasteland;
+
+impl Puzzle for Wasteland {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "wasteland";
+
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(map: &Self::Input) -> Result<Answer, Error> {
+        Ok(navigator::calculate_steps(map)?.into())
+    }
+
+    fn part_two(map: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(
+            navigator::calculate_ghost_steps(map)
+                .map(Answer::from)
+                .map_err(Error::from),
+        )
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,17 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-mod navigator;
+use day_08_wasteland::Wasteland;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let steps: usize = navigator::calculate_steps(contents.as_str());
-    println!("Steps:\n{steps}");
-    let ghost_steps = navigator::calculate_ghost_steps(contents.as_str());
-    println!("Ghost steps:\n{ghost_steps}");
+fn main() -> ExitCode {
+    aoc_core::run(&Wasteland)
 }
@@ -1,5 +1,41 @@
-use prime_factorization::Factorization;
-use std::{cmp, collections::HashMap};
+use aoc_core::error::find_invalid_char;
+use aoc_core::input::lines;
+use aoc_core::input::paragraphs;
+use aoc_core::parallel;
+use aoc_core::Span;
+use num::integer::lcm;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected instructions and network separated by an empty line")]
+    MissingNetwork,
+    #[error("instruction must be `L` or `R` {0}")]
+    InvalidInstruction(Span),
+    #[error("expected `<node> = (<left>, <right>)` {0}")]
+    MalformedNode(Span),
+    #[error("reference to undefined node {0}")]
+    UnknownNode(Span),
+    #[error("node `{node}` leads to undefined node `{next}`")]
+    UndefinedNode { node: String, next: String },
+    #[error("expected at least one `L` or `R` instruction")]
+    MissingInstructions,
+    #[error("network has no node ending with `A`")]
+    MissingStart,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("network has no `{0}` node")]
+    MissingNode(&'static str),
+    #[error("following the instructions from `{0}` never reaches the finish")]
+    FinishUnreachable(String),
+}
+
+const START: &str = "AAA";
+const FINISH: &str = "ZZZ";
 
 #[derive(Debug, Clone)]
 struct Node {
@@ -8,109 +44,281 @@ struct Node {
     right: String,
 }
 
-pub fn calculate_steps(map: &str) -> usize {
-    let (instructions, network_str) = map.split_once("\n\n").unwrap();
-
-    let network = network_str.split("\n").fold(
-        HashMap::new() as HashMap<String, Node>,
-        |mut nodes, line| {
-            let id = line[..3].to_string();
-            let left = line[7..10].to_string();
-            let right = line[12..15].to_string();
-            let node = Node {
-                id: line[..3].to_string(),
-                left,
-                right,
-            };
-            nodes.insert(id, node);
-            return nodes;
-        },
-    );
-
-    let mut current_node = network.values().find(node_ends_with('A')).unwrap();
-    let mut steps: Vec<char> = vec![];
-    while !node_ends_with('Z')(&current_node) {
-        for c in instructions.chars() {
-            current_node = if c == 'R' {
-                network.get(&current_node.right).unwrap()
-            } else {
-                network.get(&current_node.left).unwrap()
-            };
-            steps.push(c);
+/// The left/right instructions and the network of nodes they are followed through
+#[derive(Debug)]
+pub struct Map {
+    instructions: String,
+    network: HashMap<String, Node>,
+}
+
+impl Map {
+    /// Instructions followed through nodes given as `(node, left, right)`
+    pub fn new<'a>(
+        instructions: &str,
+        nodes: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>,
+    ) -> Result<Map, ParseError> {
+        check_instructions(instructions)?;
+        let network: HashMap<String, Node> = nodes
+            .into_iter()
+            .map(|(id, left, right)| {
+                let node = Node {
+                    id: id.to_string(),
+                    left: left.to_string(),
+                    right: right.to_string(),
+                };
+                (node.id.clone(), node)
+            })
+            .collect();
+
+        let mut nodes: Vec<&Node> = network.values().collect();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        for node in nodes {
+            for next in [&node.left, &node.right] {
+                if !network.contains_key(next) {
+                    return Err(ParseError::UndefinedNode {
+                        node: node.id.clone(),
+                        next: next.clone(),
+                    });
+                }
+            }
         }
+        check_start(&network)?;
+
+        Ok(Map {
+            instructions: instructions.to_string(),
+            network,
+        })
     }
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
 
-    return steps.len();
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_map(input)
+    }
 }
 
-pub fn calculate_ghost_steps(map: &str) -> u64 {
-    let (instructions, network_str) = map.split_once("\n\n").unwrap();
-
-    let network = network_str.split("\n").fold(
-        HashMap::new() as HashMap<String, Node>,
-        |mut nodes, line| {
-            let id = line[..3].to_string();
-            let left = line[7..10].to_string();
-            let right = line[12..15].to_string();
-            let node = Node {
-                id: line[..3].to_string(),
-                left,
-                right,
-            };
-            nodes.insert(id, node);
-            return nodes;
-        },
-    );
-
-    let mut nodes_steps: Vec<usize> = vec![];
-    for node in network.values().filter(node_ends_with('A')) {
-        let mut current_node = node;
-        let mut steps: usize = 0;
-        while !node_ends_with('Z')(&current_node) {
-            for c in instructions.chars() {
-                if c == 'R' {
-                    current_node = network.get(&current_node.right).unwrap();
-                } else {
-                    current_node = network.get(&current_node.left).unwrap();
-                }
+/// Reads the instructions and the `<node> = (<left>, <right>)` lines
+pub fn parse_map(map: &str) -> Result<Map, ParseError> {
+    let [(_, instructions), (first_line, network_str)] = paragraphs(map)[..] else {
+        return Err(ParseError::MissingNetwork);
+    };
+    check_instructions(instructions)?;
+
+    let nodes = lines(network_str)
+        .enumerate()
+        .map(|(index, line)| {
+            Ok((
+                first_line + index,
+                line,
+                parse_node(first_line + index, line)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    let network: HashMap<String, Node> = nodes
+        .iter()
+        .map(|(_, _, node)| (node.id.clone(), node.clone()))
+        .collect();
+
+    for (index, line, node) in nodes.iter() {
+        for next in [&node.left, &node.right] {
+            if !network.contains_key(next) {
+                let position = line.rfind(next.as_str()).unwrap_or_default();
+                return Err(ParseError::UnknownNode(Span::at(
+                    *index,
+                    line,
+                    &line[position..position + next.len()],
+                )));
             }
-            steps += instructions.len();
         }
-        nodes_steps.push(steps);
     }
 
-    let fac = nodes_steps
-        .iter()
-        .map(|num| Factorization::run(*num as u64))
-        .collect::<Vec<Factorization<u64>>>();
+    check_start(&network)?;
 
-    return least_common_multiple(fac) as u64;
+    Ok(Map {
+        instructions: instructions.to_string(),
+        network,
+    })
 }
 
-fn node_ends_with(c: char) -> Box<dyn Fn(&&Node) -> bool> {
-    Box::new(move |node: &&Node| node.id.chars().nth(2).unwrap() == c)
+fn check_instructions(instructions: &str) -> Result<(), ParseError> {
+    if instructions.is_empty() {
+        return Err(ParseError::MissingInstructions);
+    }
+    if let Some(span) = find_invalid_char(0, instructions, |c| c == 'L' || c == 'R') {
+        return Err(ParseError::InvalidInstruction(span));
+    }
+    Ok(())
 }
 
-fn least_common_multiple(nums: Vec<Factorization<u64>>) -> u64 {
-    let mut factors: HashMap<u64, usize> = HashMap::new();
-    for num in nums {
-        let mut num_factors: HashMap<u64, usize> = HashMap::new();
-        for factor in num.factors {
-            if num_factors.contains_key(&factor) {
-                *num_factors.get_mut(&factor).unwrap() += 1;
-            } else {
-                num_factors.insert(factor, 1);
-            }
+fn check_start(network: &HashMap<String, Node>) -> Result<(), ParseError> {
+    match network.values().find(node_ends_with('A')) {
+        Some(_) => Ok(()),
+        None => Err(ParseError::MissingStart),
+    }
+}
+
+fn parse_node(index: usize, line: &str) -> Result<Node, ParseError> {
+    let malformed = || ParseError::MalformedNode(Span::line(index, line));
+    let (id, edges) = line.split_once(" = ").ok_or_else(malformed)?;
+    let (left, right) = edges
+        .strip_prefix("(")
+        .and_then(|edges| edges.strip_suffix(")"))
+        .and_then(|edges| edges.split_once(", "))
+        .ok_or_else(malformed)?;
+
+    Ok(Node {
+        id: id.to_string(),
+        left: left.to_string(),
+        right: right.to_string(),
+    })
+}
+
+/// Steps from `AAA` to `ZZZ`
+pub fn calculate_steps(map: &Map) -> Result<usize, SolveError> {
+    let Map {
+        instructions,
+        network,
+    } = map;
+
+    let start = network.get(START).ok_or(SolveError::MissingNode(START))?;
+    if !network.contains_key(FINISH) {
+        return Err(SolveError::MissingNode(FINISH));
+    }
+    steps_until(instructions, network, start, |node| node.id == FINISH)
+}
+
+/// Steps until every ghost starting on a node ending in `A` is on a node ending in `Z`
+pub fn calculate_ghost_steps(map: &Map) -> Result<u64, SolveError> {
+    let Map {
+        instructions,
+        network,
+    } = map;
+
+    let ghosts: Vec<&Node> = network.values().filter(node_ends_with('A')).collect();
+    let nodes_steps = parallel::map(&ghosts, |node| {
+        steps_until(instructions, network, node, |node| {
+            node_ends_with('Z')(&node)
+        })
+    });
+
+    nodes_steps
+        .into_iter()
+        .try_fold(1_u64, |acc, steps| Ok(lcm(acc, steps? as u64)))
+}
+
+// Steps from `start` to the first node that is an end, the walk can't reach
+// one once it's back on a node at the same instruction
+fn steps_until(
+    instructions: &str,
+    network: &HashMap<String, Node>,
+    start: &Node,
+    is_end: impl Fn(&Node) -> bool,
+) -> Result<usize, SolveError> {
+    let instructions = instructions.as_bytes();
+    let mut seen = HashSet::new();
+    let mut node = start;
+    let mut steps = 0;
+    while !is_end(node) {
+        let index = steps % instructions.len();
+        if !seen.insert((&node.id, index)) {
+            return Err(SolveError::FinishUnreachable(start.id.clone()));
         }
-        for (factor, multiple) in num_factors {
-            if factors.contains_key(&factor) {
-                *factors.get_mut(&factor).unwrap() = cmp::max(factors[&factor], multiple);
-            } else {
-                factors.insert(factor, multiple);
+        node = match instructions[index] {
+            b'R' => &network[&node.right],
+            _ => &network[&node.left],
+        };
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+fn node_ends_with(c: char) -> Box<dyn Fn(&&Node) -> bool> {
+    Box::new(move |node: &&Node| node.id.ends_with(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_network() {
+        let map = parse_map("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(calculate_steps(&map), Ok(6));
+        let map = parse_map("L\n\nBBA = (BBZ, BBZ)\nBBZ = (BBZ, BBZ)").unwrap();
+        assert_eq!(calculate_steps(&map), Err(SolveError::MissingNode("AAA")));
+        assert_eq!(calculate_ghost_steps(&map), Ok(1));
+    }
+
+    #[test]
+    fn it_reports_unreachable_finishes() {
+        let map = parse_map("LR\n\nAAA = (BBB, AAA)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, AAA)").unwrap();
+        assert_eq!(
+            calculate_steps(&map),
+            Err(SolveError::FinishUnreachable("AAA".to_string()))
+        );
+        assert_eq!(
+            calculate_ghost_steps(&map),
+            Err(SolveError::FinishUnreachable("AAA".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_builds_maps_in_code() {
+        let map = Map::new(
+            "LLR",
+            [
+                ("AAA", "BBB", "BBB"),
+                ("BBB", "AAA", "ZZZ"),
+                ("ZZZ", "ZZZ", "ZZZ"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(calculate_steps(&map), Ok(6));
+
+        assert_eq!(
+            Map::new("", [("AAA", "AAA", "AAA")]).unwrap_err(),
+            ParseError::MissingInstructions
+        );
+        assert_eq!(
+            Map::new("LRx", [("AAA", "AAA", "AAA")]).unwrap_err(),
+            ParseError::InvalidInstruction(Span::new(1, 3, "x"))
+        );
+        assert_eq!(
+            Map::new("L", [("AAA", "BBB", "AAA")]).unwrap_err(),
+            ParseError::UndefinedNode {
+                node: "AAA".to_string(),
+                next: "BBB".to_string()
             }
-        }
+        );
+        assert_eq!(
+            Map::new("L", [("ZZZ", "ZZZ", "ZZZ")]).unwrap_err(),
+            ParseError::MissingStart
+        );
+    }
+
+    #[test]
+    fn it_reports_malformed_network() {
+        assert_eq!(
+            parse_map("LLR\nAAA = (BBB, BBB)").unwrap_err(),
+            ParseError::MissingNetwork
+        );
+        assert_eq!(
+            parse_map("LXR\n\nAAA = (AAA, AAA)").unwrap_err(),
+            ParseError::InvalidInstruction(Span::new(1, 2, "X"))
+        );
+        assert_eq!(
+            parse_map("LR\n\nAAA = (AAA, AAA)\nBBB = AAA, AAA").unwrap_err(),
+            ParseError::MalformedNode(Span::new(4, 1, "BBB = AAA, AAA"))
+        );
+        assert_eq!(
+            parse_map("LR\n\nAAA = (AAA, ZZZ)").unwrap_err(),
+            ParseError::UnknownNode(Span::new(3, 13, "ZZZ"))
+        );
+        assert_eq!(
+            parse_map("LR\n\nBBB = (BBB, BBB)").unwrap_err(),
+            ParseError::MissingStart
+        );
     }
-    return factors.iter().fold(1_u64, |acc, (key, multiple)| {
-        acc * key.pow(*multiple as u32)
-    });
 }
@@ -0,0 +1,25 @@
+use aoc_core::generate::{InputRng, Rng};
+
+const VALUES: i64 = 21;
+
+// `size` histories, each a polynomial of degree at most 5 sampled at 0..21
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let coefficients: Vec<i64> = (0..rng.gen_range(1..=6))
+                .map(|_| rng.gen_range(-10..=10))
+                .collect();
+            (0..VALUES)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, coefficient| value * x + coefficient)
+                        .to_string()
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -1,54 +1,90 @@
-pub fn extrapolate_next(data: &str) -> i64 {
+use std::str::FromStr;
+
+use aoc_core::error::parse_at;
+use aoc_core::input::lines;
+use aoc_core::Span;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("history has no values {0}")]
+    EmptyHistory(Span),
+    #[error("invalid value {0}")]
+    InvalidValue(Span),
+}
+
+/// The histories of the OASIS report
+#[derive(Debug, PartialEq)]
+pub struct Report(pub Vec<Vec<i64>>);
+
+impl FromStr for Report {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        lines(input)
+            .enumerate()
+            .map(|(index, line)| parse_history(index, line))
+            .collect::<Result<_, _>>()
+            .map(Report)
+    }
+}
+
+/// Reads the values of a history line
+pub fn parse_history(index: usize, data: &str) -> Result<Vec<i64>, ParseError> {
     let values = data
         .split_whitespace()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<i64>>();
+        .map(|x| parse_at::<i64>(index, data, x).map_err(ParseError::InvalidValue))
+        .collect::<Result<Vec<i64>, _>>()?;
+    if values.is_empty() {
+        return Err(ParseError::EmptyHistory(Span::line(index, data)));
+    }
 
-    let mut recordings = vec![values.clone()];
+    Ok(values)
+}
+
+/// The value after the last one
+pub fn extrapolate_next(values: &[i64]) -> i64 {
+    let mut recordings = vec![values.to_vec()];
     let mut index = 0;
-    while !is_final_history(recordings[index].clone()) {
-        recordings.push(next_history(recordings[index].clone()));
+    while !is_final_history(&recordings[index]) {
+        recordings.push(next_history(&recordings[index]));
         index += 1;
     }
     recordings.reverse();
-    return recordings
+    recordings
         .iter()
-        .fold(0, |acc, history| *history.last().unwrap() + acc);
+        .fold(0, |acc, history| history[history.len() - 1] + acc)
 }
 
-pub fn extrapolate_previous(data: &str) -> i64 {
-    let values = data
-        .split_whitespace()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<i64>>();
-
-    let mut recordings = vec![values.clone()];
+/// The value before the first one
+pub fn extrapolate_previous(values: &[i64]) -> i64 {
+    let mut recordings = vec![values.to_vec()];
     let mut index = 0;
-    while !is_final_history(recordings[index].clone()) {
-        recordings.push(previous_history(recordings[index].clone()));
+    while !is_final_history(&recordings[index]) {
+        recordings.push(previous_history(&recordings[index]));
         index += 1;
     }
     recordings.reverse();
-    return recordings.iter().fold(0, |acc, history| history[0] - acc);
+    recordings.iter().fold(0, |acc, history| history[0] - acc)
 }
 
-fn next_history(history: Vec<i64>) -> Vec<i64> {
-    return history[1..]
+fn next_history(history: &[i64]) -> Vec<i64> {
+    history[1..]
         .iter()
         .enumerate()
         .map(|(index, value)| *value - history[index])
-        .collect();
+        .collect()
 }
 
-fn previous_history(history: Vec<i64>) -> Vec<i64> {
-    return history[0..history.len() - 1]
+fn previous_history(history: &[i64]) -> Vec<i64> {
+    history[0..history.len() - 1]
         .iter()
         .enumerate()
         .map(|(index, value)| history[index + 1] - *value)
-        .collect();
+        .collect()
 }
 
-fn is_final_history(history: Vec<i64>) -> bool {
+fn is_final_history(history: &[i64]) -> bool {
     history.iter().all(|x| *x == history[0])
 }
 
@@ -58,13 +94,32 @@ mod tests {
 
     #[test]
     fn it_computes_next_history() {
-        assert_eq!(next_history(vec![2, 4, 6]), vec![2, 2]);
-        assert_eq!(next_history(vec![0, 2, 8]), vec![2, 6]);
+        assert_eq!(next_history(&[2, 4, 6]), vec![2, 2]);
+        assert_eq!(next_history(&[0, 2, 8]), vec![2, 6]);
+    }
+
+    #[test]
+    fn it_extrapolates() {
+        let values = parse_history(0, "10 13 16 21 30 45").unwrap();
+        assert_eq!(extrapolate_next(&values), 68);
+        assert_eq!(extrapolate_previous(&values), 5);
+    }
+
+    #[test]
+    fn it_reports_invalid_history() {
+        assert_eq!(
+            parse_history(3, ""),
+            Err(ParseError::EmptyHistory(Span::new(4, 1, "")))
+        );
+        assert_eq!(
+            parse_history(0, "1 2 x3"),
+            Err(ParseError::InvalidValue(Span::new(1, 5, "x3")))
+        );
     }
 
     #[test]
     fn it_computes_previous_history() {
-        assert_eq!(previous_history(vec![2, 4, 6]), vec![2, 2]);
-        assert_eq!(previous_history(vec![0, 2, 8]), vec![2, 6]);
+        assert_eq!(previous_history(&[2, 4, 6]), vec![2, 2]);
+        assert_eq!(previous_history(&[0, 2, 8]), vec![2, 6]);
     }
 }
@@ -0,0 +1,73 @@
+//! Day 9: Mirage Maintenance
+//!
+//! [`history::parse_history`] reads a history, [`history::extrapolate_next`] and
+//! [`history::extrapolate_previous`] extend it.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+mod generator;
+pub mod history;
+
+pub struct Oasis;
+
+impl Puzzle for Oasis {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "oasis";
+
+    type Input = history::Report;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(report: &Self::Input) -> Result<Answer, Error> {
+        let sum: i64 = report
+            .0
+            .iter()
+            .map(Vec::as_slice)
+            .map(history::extrapolate_next)
+            .sum();
+        Ok(sum.into())
+    }
+
+    fn part_two(report: &Self::Input) -> Option<Result<Answer, Error>> {
+        let sum_previous: i64 = report
+            .0
+            .iter()
+            .map(Vec::as_slice)
+            .map(history::extrapolate_previous)
+            .sum();
+        Some(Ok(sum_previous.into()))
+    }
+
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        let (mut sum, mut sum_previous) = (0, 0);
+        let read = read_records(input, b'\n', |index, line| {
+            let history = history::parse_history(index, line)?;
+            stopwatch.time(Part::One, || sum += history::extrapolate_next(&history));
+            stopwatch.time(Part::Two, || {
+                sum_previous += history::extrapolate_previous(&history)
+            });
+            Ok(())
+        });
+        Some(read.map(|()| {
+            Streamed {
+                part_one: stopwatch.is_selected(Part::One).then(|| Ok(sum.into())),
+                part_two: stopwatch
+                    .is_selected(Part::Two)
+                    .then(|| Ok(sum_previous.into())),
+            }
+        }))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -0,0 +1,63 @@
+use aoc_core::generate::{closed_loop, grid, InputRng, SliceRandom};
+
+const JUNK: &[u8] = b"|-LJ7F..";
+
+// Pipe joining the two neighbours of `tile` along the loop
+fn pipe(tile: (usize, usize), ends: [(usize, usize); 2]) -> char {
+    let [north, east, south, west] = [(-1, 0), (0, 1), (1, 0), (0, -1)].map(|(dr, dc)| {
+        ends.iter().any(|&(row, col)| {
+            row as isize - tile.0 as isize == dr && col as isize - tile.1 as isize == dc
+        })
+    });
+    match (north, east, south, west) {
+        (true, _, true, _) => '|',
+        (_, true, _, true) => '-',
+        (true, true, _, _) => 'L',
+        (true, _, _, true) => 'J',
+        (_, _, true, true) => '7',
+        _ => 'F',
+    }
+}
+
+// `size` by `size` maze, at least three tiles wide, with a random loop among
+// junk pipes. Tiles next to the start that aren't on the loop are ground so
+// that nothing else connects to it
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(3);
+    let path = closed_loop(rng, size);
+    let mut maze: Vec<Vec<char>> = grid(rng, size, size, |rng| *JUNK.choose(rng).unwrap() as char)
+        .split("\n")
+        .map(|row| row.chars().collect())
+        .collect();
+
+    for (index, &tile) in path.iter().enumerate() {
+        let ends = [
+            path[(index + path.len() - 1) % path.len()],
+            path[(index + 1) % path.len()],
+        ];
+        maze[tile.0][tile.1] = pipe(tile, ends);
+    }
+
+    let start = *path.choose(rng).unwrap();
+    maze[start.0][start.1] = 'S';
+    let (row, col) = (start.0 as isize, start.1 as isize);
+    for (r, c) in [
+        (row - 1, col),
+        (row + 1, col),
+        (row, col - 1),
+        (row, col + 1),
+    ] {
+        if r < 0 || c < 0 || r as usize >= size || c as usize >= size {
+            continue;
+        }
+        let tile = (r as usize, c as usize);
+        if !path.contains(&tile) {
+            maze[tile.0][tile.1] = '.';
+        }
+    }
+
+    maze.iter()
+        .map(String::from_iter)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,48 @@
+//! Day 10: Pipe Maze
+//!
+//! [`maze::parse_maze`] reads the sketch, [`maze::longest_path`] and
+//! [`maze::enclosed_tiles`] follow its loop.
+
+use aoc_core::generate::InputRng;
+use aoc_core::visualize::Painter;
+use aoc_core::{Answer, Error, Part, Puzzle};
+
+mod generator;
+pub mod maze;
+
+pub struct Maze;
+
+impl Puzzle for Maze {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "maze";
+
+    type Input = maze::Sketch;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(sketch: &Self::Input) -> Result<Answer, Error> {
+        Ok(maze::longest_path(&sketch.0)?.into())
+    }
+
+    fn part_two(sketch: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(
+            maze::enclosed_tiles(&sketch.0)
+                .map(Answer::from)
+                .map_err(Error::from),
+        )
+    }
+
+    fn visualize(
+        sketch: &Self::Input,
+        _part: Part,
+        painter: &Painter,
+    ) -> Option<Result<String, Error>> {
+        Some(maze::draw_loop(&sketch.0, painter).map_err(Error::from))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
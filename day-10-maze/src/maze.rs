@@ -1,325 +1,312 @@
-use std::collections::HashSet;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
+use aoc_core::grid::{Direction, Grid, GridError, Point};
+use aoc_core::input::lines;
+use aoc_core::search::bfs;
+use aoc_core::visualize::{Color, Painter};
+use aoc_core::Span;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use thiserror::Error;
 
-impl Direction {
-    fn next(&self, c: char) -> Option<Direction> {
-        match c {
-            '|' => {
-                if self.eq(&Direction::North) {
-                    Some(Direction::North)
-                } else if self.eq(&Direction::South) {
-                    Some(Direction::South)
-                } else {
-                    None
-                }
-            }
-            '-' => {
-                if self.eq(&Direction::East) {
-                    Some(Direction::East)
-                } else if self.eq(&Direction::West) {
-                    Some(Direction::West)
-                } else {
-                    None
-                }
-            }
-            'L' => {
-                if self.eq(&Direction::South) {
-                    Some(Direction::East)
-                } else if self.eq(&Direction::West) {
-                    Some(Direction::North)
-                } else {
-                    None
-                }
-            }
-            'J' => {
-                if self.eq(&Direction::South) {
-                    Some(Direction::West)
-                } else if self.eq(&Direction::East) {
-                    Some(Direction::North)
-                } else {
-                    None
-                }
-            }
-            '7' => {
-                if self.eq(&Direction::North) {
-                    Some(Direction::West)
-                } else if self.eq(&Direction::East) {
-                    Some(Direction::South)
-                } else {
-                    None
-                }
-            }
-            'F' => {
-                if self.eq(&Direction::North) {
-                    Some(Direction::East)
-                } else if self.eq(&Direction::West) {
-                    Some(Direction::South)
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        }
-    }
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("maze should have animal starting position symbol 'S'")]
+    MissingStart,
+    #[error("second starting position {0}")]
+    DuplicateStart(Span),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-struct Position {
-    row: usize,
-    column: usize,
-    direction: Direction,
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("pipes don't form a loop through the starting position")]
+    BrokenLoop,
 }
 
-#[derive(Debug, Clone)]
-struct Maze<'a> {
-    rows: Vec<&'a str>,
-}
-
-impl Maze<'_> {
-    fn from(map: &str) -> Maze {
-        Maze {
-            rows: map.split("\n").collect(),
-        }
-    }
+const PIPES: &str = "|-LJ7F";
 
-    fn start(&self) -> (Position, Position) {
-        for (row, line) in self.rows.iter().enumerate() {
-            for (column, symbol) in line.chars().enumerate() {
-                if symbol == 'S' {
-                    let positions = [
-                        Direction::North,
-                        Direction::South,
-                        Direction::East,
-                        Direction::West,
-                    ]
-                    .map(|direction| Position {
-                        row,
-                        column,
-                        direction,
-                    });
-                    let s: Vec<&Position> = positions
-                        .iter()
-                        .filter(|position| position.clone().next(&self).is_some())
-                        .collect();
-                    return (*s[0], *s[1]);
-                }
-            }
-        }
-        panic!("Maze should have animal starting position symbol 'S'")
+// Directions a tile connects to in `Direction::ALL` order, the start may
+// connect anywhere
+fn connections(tile: char) -> &'static [Direction] {
+    match tile {
+        '|' => &[Direction::North, Direction::South],
+        '-' => &[Direction::East, Direction::West],
+        'L' => &[Direction::North, Direction::East],
+        'J' => &[Direction::North, Direction::West],
+        '7' => &[Direction::South, Direction::West],
+        'F' => &[Direction::East, Direction::South],
+        'S' => &Direction::ALL,
+        _ => &[],
     }
 }
 
-impl Position {
-    fn from(row: usize, column: usize, direction: Direction) -> Position {
-        Position {
-            row,
-            column,
-            direction,
-        }
-    }
+// Neighbours joined to `point` by pipes connecting from both sides
+fn connected(maze: &Grid<char>, point: Point) -> Vec<(Direction, Point)> {
+    connections(maze[point])
+        .iter()
+        .filter_map(|&direction| {
+            let next = maze.step(point, direction)?;
+            connections(maze[next])
+                .contains(&direction.reverse())
+                .then_some((direction, next))
+        })
+        .collect()
+}
 
-    fn next(&self, maze: &Maze) -> Option<Position> {
-        match self.direction {
-            Direction::North => {
-                if self.row == 0 {
-                    None
-                } else {
-                    match maze.rows[self.row - 1].chars().nth(self.column) {
-                        Some(c) => match self.direction.next(c) {
-                            Some(next_direction) => {
-                                Some(Position::from(self.row - 1, self.column, next_direction))
-                            }
-                            None => None,
-                        },
-                        None => None,
-                    }
-                }
-            }
-            Direction::South => {
-                if self.row + 1 == maze.rows.len() {
-                    None
-                } else {
-                    match maze.rows[self.row + 1].chars().nth(self.column) {
-                        Some(c) => match self.direction.next(c) {
-                            Some(next_direction) => {
-                                Some(Position::from(self.row + 1, self.column, next_direction))
-                            }
-                            None => None,
-                        },
-                        None => None,
-                    }
-                }
-            }
-            Direction::East => {
-                if self.column + 1 == maze.rows[self.row].len() {
-                    None
-                } else {
-                    match maze.rows[self.row].chars().nth(self.column + 1) {
-                        Some(c) => match self.direction.next(c) {
-                            Some(next_direction) => {
-                                Some(Position::from(self.row, self.column + 1, next_direction))
-                            }
-                            None => None,
-                        },
-                        None => None,
-                    }
-                }
-            }
-            Direction::West => {
-                if self.column == 0 {
-                    None
-                } else {
-                    match maze.rows[self.row].chars().nth(self.column - 1) {
-                        Some(c) => match self.direction.next(c) {
-                            Some(next_direction) => {
-                                Some(Position::from(self.row, self.column - 1, next_direction))
-                            }
-                            None => None,
-                        },
-                        None => None,
-                    }
-                }
+// Distance of every loop tile from the start. Stray pipes may point at the
+// start too, so each pair of the pipes joined to it is tried as the two ends
+// of the loop until one closes
+fn find_loop(maze: &Grid<char>) -> Result<HashMap<Point, usize>, SolveError> {
+    let start = maze
+        .find(|tile| *tile == 'S')
+        .ok_or(SolveError::BrokenLoop)?;
+    let ends: Vec<Point> = connected(maze, start)
+        .into_iter()
+        .map(|(_, next)| next)
+        .collect();
+    for (index, &first) in ends.iter().enumerate() {
+        for &second in &ends[index + 1..] {
+            let distances = bfs(start, |point| match *point == start {
+                true => vec![first, second],
+                false => connected(maze, *point)
+                    .into_iter()
+                    .map(|(_, next)| next)
+                    .collect(),
+            });
+            let closed = distances
+                .keys()
+                .all(|point| *point == start || connected(maze, *point).len() == 2);
+            let reached = ends.iter().filter(|end| distances.contains_key(end));
+            if closed && reached.count() == 2 {
+                return Ok(distances);
             }
         }
     }
+
+    Err(SolveError::BrokenLoop)
 }
 
-pub fn longest_path(map: &str) -> usize {
-    let maze = Maze::from(map);
-    let (mut first, mut second) = maze.clone().start();
-    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+// Pipe hidden under the start tile, joining the two ends of the loop
+fn start_pipe(maze: &Grid<char>, start: Point, loop_path: &HashSet<Point>) -> Option<char> {
+    let directions: Vec<Direction> = connected(maze, start)
+        .into_iter()
+        .filter(|(_, next)| loop_path.contains(next))
+        .map(|(direction, _)| direction)
+        .collect();
+    PIPES.chars().find(|pipe| connections(*pipe) == directions)
+}
 
-    loop {
-        first = first.clone().next(&maze).unwrap();
-        second = second.clone().next(&maze).unwrap();
-        let first_pos = (first.row, first.column);
-        let second_pos = (second.row, second.column);
+/// The sketch of the pipes
+#[derive(Debug, PartialEq)]
+pub struct Sketch(pub Grid<char>);
 
-        if visited.contains(&first_pos) || visited.contains(&second_pos) {
-            break;
-        }
+impl FromStr for Sketch {
+    type Err = ParseError;
 
-        if first_pos.eq(&second_pos) {
-            return ((visited.len() / 2) as f64).ceil() as usize + 1;
-        }
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_maze(input).map(Sketch)
+    }
+}
+
+/// Reads the pipe sketch, which has to have one `S`
+pub fn parse_maze(map: &str) -> Result<Grid<char>, ParseError> {
+    let maze = Grid::parse(map, 0, |c| "|-LJ7F.S".contains(c).then_some(c))?;
 
-        visited.insert(first_pos);
-        visited.insert(second_pos);
+    let mut start: Option<Span> = None;
+    for (index, line) in lines(map).enumerate() {
+        for (position, _) in line.match_indices('S') {
+            let span = Span::at(index, line, &line[position..position + 1]);
+            if start.is_some() {
+                return Err(ParseError::DuplicateStart(span));
+            }
+            start = Some(span);
+        }
+    }
+    if start.is_none() {
+        return Err(ParseError::MissingStart);
     }
 
-    return ((visited.len() / 2) as f64).ceil() as usize;
+    Ok(maze)
 }
 
-pub fn enclosed_tiles(map: &str) -> usize {
-    let maze = Maze::from(map);
-    let (mut first, mut second) = maze.clone().start();
-    let mut loop_path: HashSet<(usize, usize)> = HashSet::new();
+/// Steps to the point of the loop farthest from the start
+pub fn longest_path(maze: &Grid<char>) -> Result<usize, SolveError> {
+    let distances = find_loop(maze)?;
 
-    loop {
-        first = first.clone().next(&maze).unwrap();
-        second = second.clone().next(&maze).unwrap();
-        let first_pos = (first.row, first.column);
-        let second_pos = (second.row, second.column);
+    Ok(distances.values().max().copied().unwrap_or_default())
+}
 
-        if loop_path.contains(&first_pos) || loop_path.contains(&second_pos) {
-            break;
-        }
+/// Tiles enclosed by the loop
+pub fn enclosed_tiles(maze: &Grid<char>) -> Result<usize, SolveError> {
+    let loop_path: HashSet<Point> = find_loop(maze)?.into_keys().collect();
+    Ok(enclosed_points(maze, &loop_path)?.len())
+}
 
-        loop_path.insert(first_pos);
-        loop_path.insert(second_pos);
+fn enclosed_points(
+    maze: &Grid<char>,
+    loop_path: &HashSet<Point>,
+) -> Result<HashSet<Point>, SolveError> {
+    let mut pipes = maze.clone();
+    if let Some(start) = maze.find(|tile| *tile == 'S') {
+        pipes[start] = start_pipe(maze, start, loop_path).ok_or(SolveError::BrokenLoop)?;
     }
 
-    let mut tiles: usize = 0;
-    for (row, line) in maze.rows.iter().enumerate() {
-        let mut row_tiles: Vec<(usize, usize)> = vec![];
+    let mut tiles = HashSet::new();
+    for (row, line) in pipes.rows().enumerate() {
         for column in 0..line.len() {
-            if is_enclosed_in_line(row, column, line, &loop_path) {
-                row_tiles.push((row, column))
+            if is_enclosed_in_line(row, column, line, loop_path) {
+                tiles.insert(Point::new(row, column));
             }
         }
-        tiles += row_tiles.len();
     }
 
-    return tiles;
+    Ok(tiles)
+}
+
+/// Loop drawn with box characters, the farthest pipe highlighted and the
+/// enclosed tiles marked `I`
+pub fn draw_loop(maze: &Grid<char>, painter: &Painter) -> Result<String, SolveError> {
+    let distances = find_loop(maze)?;
+    let loop_path: HashSet<Point> = distances.keys().copied().collect();
+    let enclosed = enclosed_points(maze, &loop_path)?;
+    let farthest = distances.values().max().copied().unwrap_or_default();
+
+    Ok(
+        painter.grid(maze, |point, tile| match distances.get(&point) {
+            Some(_) if *tile == 'S' => ('S', Some(Color::Red)),
+            Some(distance) => {
+                let color = match *distance == farthest {
+                    true => Color::Magenta,
+                    false => Color::Yellow,
+                };
+                (box_glyph(*tile), Some(color))
+            }
+            None if enclosed.contains(&point) => ('I', Some(Color::Green)),
+            None => ('.', Some(Color::Dim)),
+        }),
+    )
+}
+
+fn box_glyph(pipe: char) -> char {
+    match pipe {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => pipe,
+    }
 }
 
 fn is_enclosed_in_line(
     row: usize,
     column: usize,
-    line: &str,
-    loop_path: &HashSet<(usize, usize)>,
+    line: &[char],
+    loop_path: &HashSet<Point>,
 ) -> bool {
-    if loop_path.contains(&(row, column)) {
+    if loop_path.contains(&Point::new(row, column)) {
         return false;
     }
 
     let mut north: usize = 0;
-    for i in 0..column {
-        if !loop_path.contains(&(row, i)) {
+    for (i, &tile) in line.iter().enumerate().take(column) {
+        if !loop_path.contains(&Point::new(row, i)) {
             continue;
         }
-        let c = line.chars().nth(i).unwrap();
-        if "|JSL".contains(c) {
+        if connections(tile).contains(&Direction::North) {
             north += 1;
         }
     }
 
-    return north % 2 == 1;
+    north % 2 == 1
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn maze(map: &str) -> Grid<char> {
+        parse_maze(map).unwrap()
+    }
+
     #[test]
-    fn it_can_find_starting_position() {
+    fn it_can_find_connected_pipes() {
         assert_eq!(
-            Maze::from("FS7").start(),
-            (
-                Position::from(0, 1, Direction::East),
-                Position::from(0, 1, Direction::West),
-            )
+            connected(&maze("FS7"), Point::new(0, 1)),
+            [
+                (Direction::East, Point::new(0, 2)),
+                (Direction::West, Point::new(0, 0)),
+            ]
         );
         assert_eq!(
-            Maze::from(".|.\n.S-").start(),
-            (
-                Position::from(1, 1, Direction::North),
-                Position::from(1, 1, Direction::East),
-            )
+            connected(&maze(".|.\n.S-"), Point::new(1, 1)),
+            [
+                (Direction::North, Point::new(0, 1)),
+                (Direction::East, Point::new(1, 2)),
+            ]
         );
         assert_eq!(
-            Maze::from(".|.\n.SJ").start(),
-            (
-                Position::from(1, 1, Direction::North),
-                Position::from(1, 1, Direction::East),
-            )
+            connected(&maze(".|.\n.SJ"), Point::new(1, 1)),
+            [
+                (Direction::North, Point::new(0, 1)),
+                (Direction::East, Point::new(1, 2)),
+            ]
         );
         assert_eq!(
-            Maze::from(".SJ\n.|.").start(),
-            (
-                Position::from(0, 1, Direction::South),
-                Position::from(0, 1, Direction::East),
-            )
+            connected(&maze(".SJ\n.|."), Point::new(0, 1)),
+            [
+                (Direction::East, Point::new(0, 2)),
+                (Direction::South, Point::new(1, 1)),
+            ]
+        );
+        let ends = HashSet::from([Point::new(0, 2), Point::new(1, 1)]);
+        assert_eq!(
+            start_pipe(&maze(".SJ\n.|."), Point::new(0, 1), &ends),
+            Some('F')
         );
     }
 
     #[test]
-    fn it_detects_enclosed() {
+    fn it_walks_the_loop() {
+        let square = maze("S-7\n|.|\nL-J");
+        assert_eq!(longest_path(&square), Ok(4));
+        assert_eq!(enclosed_tiles(&square), Ok(1));
         assert_eq!(
-            is_enclosed_in_line(0, 1, ".|...|.", &HashSet::from([(0, 1), (0, 5)])),
-            false
+            draw_loop(&square, &Painter::new(false)),
+            Ok(String::from("S─┐\n│I│\n└─┘"))
         );
+    }
 
+    #[test]
+    fn it_ignores_stray_pipes_joined_to_the_start() {
+        let stray = maze(".....\n-S-7.\n.|.|.\n.L-J.\n.....");
+        assert_eq!(longest_path(&stray), Ok(4));
+        assert_eq!(enclosed_tiles(&stray), Ok(1));
+    }
+
+    #[test]
+    fn it_reports_invalid_maze() {
+        assert_eq!(parse_maze("F-7\n|.|"), Err(ParseError::MissingStart));
         assert_eq!(
-            is_enclosed_in_line(0, 3, ".|...|.", &HashSet::from([(0, 1), (0, 5)])),
-            true
+            parse_maze("S-7\n|X|"),
+            Err(ParseError::Grid(GridError::UnknownTile(Span::new(
+                2, 2, "X"
+            ))))
         );
+        assert_eq!(
+            parse_maze("S-7\n|.S"),
+            Err(ParseError::DuplicateStart(Span::new(2, 3, "S")))
+        );
+        assert_eq!(longest_path(&maze("S-7\n|..")), Err(SolveError::BrokenLoop));
+    }
+
+    #[test]
+    fn it_detects_enclosed() {
+        let line = |text: &str| text.chars().collect::<Vec<char>>();
+        let loop_path = HashSet::from([Point::new(0, 1), Point::new(0, 5)]);
+        assert!(!is_enclosed_in_line(0, 1, &line(".|...|."), &loop_path));
+
+        assert!(is_enclosed_in_line(0, 3, &line(".|...|."), &loop_path));
     }
 }
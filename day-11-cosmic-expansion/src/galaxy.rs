@@ -1,25 +1,80 @@
+use aoc_core::grid::{check_grid, GridError};
+use aoc_core::input::lines;
+#[cfg(feature = "render")]
+use aoc_core::render::{Color, Scene, Shape, Style};
 use itertools::Itertools;
+use std::str::FromStr;
+use thiserror::Error;
 
-pub fn shortest_paths(space: &str, expansion: usize) -> Vec<usize> {
-    let galaxies: Vec<(usize, usize)> = space
-        .split("\n")
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
+
+/// Galaxies of the image as `(row, column)`, and the size of the image
+#[derive(Debug, PartialEq)]
+pub struct Space {
+    galaxies: Vec<(usize, usize)>,
+    height: usize,
+    width: usize,
+}
+
+impl FromStr for Space {
+    type Err = ParseError;
+
+    fn from_str(space: &str) -> Result<Self, Self::Err> {
+        parse_space(space)
+    }
+}
+
+/// Reads the galaxies of an image of `.` and `#`
+pub fn parse_space(space: &str) -> Result<Space, ParseError> {
+    check_grid(space, 0, |c| c == '.' || c == '#')?;
+
+    let galaxies = lines(space)
         .enumerate()
-        .map(|(row, line)| {
-            line.chars()
-                .enumerate()
-                .filter_map(|(col, c)| if c.eq(&'#') { Some((row, col)) } else { None })
-                .collect::<Vec<(usize, usize)>>()
+        .flat_map(|(row, line)| {
+            line.char_indices()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(col, _)| (row, col))
         })
-        .flatten()
         .collect();
+    Ok(Space {
+        galaxies,
+        height: lines(space).count(),
+        width: lines(space).next().map_or(0, |line| line.len()),
+    })
+}
+
+/// Distances between every pair of galaxies once each empty row and column is `expansion` times larger
+pub fn shortest_paths(space: &Space, expansion: usize) -> Vec<usize> {
+    let (expanded_galaxies, _, _) = expand(space, expansion);
+
+    expanded_galaxies
+        .iter()
+        .combinations(2)
+        .map(|pair| {
+            let start = pair[0];
+            let end = pair[1];
+
+            distance(start, end)
+        })
+        .collect::<Vec<usize>>()
+}
+
+// Galaxy positions once every empty row and column is `expansion` wide,
+// along with the empty rows and columns
+fn expand(space: &Space, expansion: usize) -> (Vec<(usize, usize)>, Vec<usize>, Vec<usize>) {
+    let galaxies = &space.galaxies;
 
     let galaxies_rows = galaxies.iter().map(|x| x.0).collect::<Vec<usize>>();
     let galaxies_cols = galaxies.iter().map(|x| x.1).collect::<Vec<usize>>();
 
-    let empty_rows = (0..space.split("\n").count())
+    let empty_rows = (0..space.height)
         .filter(|v| !galaxies_rows.contains(v))
         .collect::<Vec<usize>>();
-    let empty_cols = (0..space.split("\n").count())
+    let empty_cols = (0..space.width)
         .filter(|v| !galaxies_cols.contains(v))
         .collect::<Vec<usize>>();
 
@@ -29,36 +84,81 @@ pub fn shortest_paths(space: &str, expansion: usize) -> Vec<usize> {
             let expand_row = empty_rows.iter().filter(|x| x.lt(&row)).count();
             let expand_col = empty_cols.iter().filter(|x| x.lt(&col)).count();
 
-            return (
+            (
                 row + expand_row * (expansion - 1),
                 col + expand_col * (expansion - 1),
-            );
+            )
         })
         .collect::<Vec<(usize, usize)>>();
 
-    return expanded_galaxies
-        .iter()
-        .combinations(2)
-        .map(|pair| {
-            let start = pair[0];
-            let end = pair[1];
+    (expanded_galaxies, empty_rows, empty_cols)
+}
 
-            return distance(start, end);
-        })
-        .collect::<Vec<usize>>();
+/// Expanded galaxy map, the empty rows and columns shaded
+#[cfg(feature = "render")]
+pub fn space_scene(space: &Space, expansion: usize) -> Scene {
+    let (galaxies, empty_rows, empty_cols) = expand(space, expansion);
+    let (height, width) = (space.height, space.width);
+    let grown = |size: usize, empty: usize| (size + empty * (expansion - 1)) as f64;
+    let (height, width) = (
+        grown(height, empty_rows.len()),
+        grown(width, empty_cols.len()),
+    );
+    // where an empty line starts once the ones before it have grown
+    let band = |index: usize, line: usize| {
+        let start = (line + index * (expansion - 1)) as f64;
+        (start, start + expansion as f64)
+    };
+
+    let mut scene = Scene::new(Color::BLACK);
+    scene.add(
+        Shape::Rect((0.0, 0.0), (width, height)),
+        Style::fill(Color(10, 10, 40)),
+    );
+    let shade = Style::fill(Color(40, 40, 80));
+    for (index, row) in empty_rows.into_iter().enumerate() {
+        let (top, bottom) = band(index, row);
+        scene.add(Shape::Rect((0.0, top), (width, bottom)), shade);
+    }
+    for (index, col) in empty_cols.into_iter().enumerate() {
+        let (left, right) = band(index, col);
+        scene.add(Shape::Rect((left, 0.0), (right, height)), shade);
+    }
+    for (row, col) in galaxies {
+        scene.add(
+            Shape::Dot((col as f64 + 0.5, row as f64 + 0.5)),
+            Style::fill(Color(255, 220, 120)),
+        );
+    }
+    scene
 }
 
 fn distance(start: &(usize, usize), end: &(usize, usize)) -> usize {
     let steps = (start.0 as i32 - end.0 as i32).abs() + (start.1 as i32 - end.1 as i32).abs();
-    return steps as usize;
+    steps as usize
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::Span;
 
     #[test]
     fn it_can_find_distance() {
         assert_eq!(distance(&(0, 4), &(10, 9)), 15);
+        assert_eq!(
+            expand(&"#..\n...\n..#".parse().unwrap(), 10),
+            (vec![(0, 0), (11, 11)], vec![1], vec![1])
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_tiles() {
+        assert_eq!(
+            parse_space("#..\n.*."),
+            Err(ParseError::Grid(GridError::UnknownTile(Span::new(
+                2, 2, "*"
+            ))))
+        );
     }
 }
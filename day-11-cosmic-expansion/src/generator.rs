@@ -0,0 +1,10 @@
+use aoc_core::generate::{grid, InputRng, Rng};
+
+// `size` by `size` image with about one galaxy in twenty tiles
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    grid(rng, size, size, |rng| match rng.gen_ratio(1, 20) {
+        true => '#',
+        false => '.',
+    })
+}
@@ -0,0 +1,53 @@
+//! Day 11: Cosmic Expansion
+//!
+//! [`galaxy::parse_space`] reads the [`galaxy::Space`] and [`galaxy::shortest_paths`]
+//! measures it for any expansion.
+
+use aoc_core::generate::InputRng;
+#[cfg(feature = "render")]
+use aoc_core::render::Scene;
+#[cfg(feature = "render")]
+use aoc_core::Part;
+use aoc_core::{Answer, Error, Puzzle};
+
+pub mod galaxy;
+mod generator;
+
+const EXPANSION: usize = 2;
+const EXPANSION_LARGE: usize = 1000000;
+
+pub struct CosmicExpansion;
+
+impl Puzzle for CosmicExpansion {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "cosmic-expansion";
+
+    type Input = galaxy::Space;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(space: &Self::Input) -> Result<Answer, Error> {
+        let distance: usize = galaxy::shortest_paths(space, EXPANSION).iter().sum();
+        Ok(distance.into())
+    }
+
+    fn part_two(space: &Self::Input) -> Option<Result<Answer, Error>> {
+        let distance_large: usize = galaxy::shortest_paths(space, EXPANSION_LARGE).iter().sum();
+        Some(Ok(distance_large.into()))
+    }
+
+    #[cfg(feature = "render")]
+    fn render(space: &Self::Input, part: Part) -> Option<Result<Scene, Error>> {
+        let expansion = match part {
+            Part::One => EXPANSION,
+            Part::Two => EXPANSION_LARGE,
+        };
+        Some(Ok(galaxy::space_scene(space, expansion)))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -0,0 +1,29 @@
+use aoc_core::generate::{InputRng, Rng};
+
+// `size` records of up to twenty springs, each known one hidden behind `?`
+// at even odds
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let length = rng.gen_range(4..=20);
+            let mut springs = ".".repeat(rng.gen_range(0..3));
+            let mut groups = vec![];
+            while springs.len() < length || groups.is_empty() {
+                let group = rng.gen_range(1..=4);
+                springs.push_str(&"#".repeat(group));
+                springs.push_str(&".".repeat(rng.gen_range(1..=3)));
+                groups.push(group.to_string());
+            }
+            let springs: String = springs
+                .trim_end_matches('.')
+                .chars()
+                .map(|spring| match rng.gen_bool(0.5) {
+                    true => '?',
+                    false => spring,
+                })
+                .collect();
+            format!("{springs} {}", groups.join(","))
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
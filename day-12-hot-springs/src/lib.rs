@@ -0,0 +1,71 @@
+//! Day 12: Hot Springs
+//!
+//! [`springs::parse_record`] reads a [`springs::Record`], [`springs::arrangements`] and
+//! [`springs::arrangements_long`] count its arrangements.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+use aoc_core::parallel;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+use crate::springs::Records;
+
+mod generator;
+pub mod springs;
+
+pub struct HotSprings;
+
+impl Puzzle for HotSprings {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "hot-springs";
+
+    type Input = Records;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(records: &Self::Input) -> Result<Answer, Error> {
+        let sum: usize = records.0.iter().map(springs::arrangements).sum();
+        Ok(sum.into())
+    }
+
+    fn part_two(records: &Self::Input) -> Option<Result<Answer, Error>> {
+        let sum_long: usize = parallel::map(&records.0, springs::arrangements_long)
+            .iter()
+            .sum();
+        Some(Ok(sum_long.into()))
+    }
+
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        // records are kept for part two to count their unfolded
+        // arrangements on several threads once they are all read
+        let mut sum = 0;
+        let mut records = vec![];
+        let read = read_records(input, b'\n', |index, line| {
+            let record = springs::parse_record(index, line)?;
+            stopwatch.time(Part::One, || sum += springs::arrangements(&record));
+            if stopwatch.is_selected(Part::Two) {
+                records.push(record);
+            }
+            Ok(())
+        });
+        Some(read.map(|()| {
+            Streamed {
+                part_one: stopwatch.is_selected(Part::One).then(|| Ok(sum.into())),
+                part_two: stopwatch
+                    .time(Part::Two, || Self::part_two(&Records(records)))
+                    .flatten(),
+            }
+        }))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
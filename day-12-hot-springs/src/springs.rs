@@ -1,34 +1,106 @@
+use aoc_core::error::{find_invalid_char, parse_at};
+use aoc_core::input::lines;
+use aoc_core::Span;
 use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
 
 type Cache = HashMap<(usize, usize), usize>;
 
-pub fn arrangements(data: &str) -> usize {
-    let mut cache: Cache = HashMap::new();
-    let (record, groups_str) = data.split_once(" ").unwrap();
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<springs> <groups>` {0}")]
+    MalformedRecord(Span),
+    #[error("unknown spring condition {0}")]
+    UnknownSpring(Span),
+    #[error("invalid group size {0}")]
+    InvalidGroup(Span),
+}
+
+/// A row of springs and the sizes of its groups of damaged springs
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    springs: String,
+    groups: Vec<usize>,
+}
+
+impl Record {
+    /// Row of springs, each `.` operational, `#` damaged or `?` unknown
+    pub fn new(springs: &str, groups: Vec<usize>) -> Result<Record, ParseError> {
+        check_springs(0, springs)?;
+        Ok(Record {
+            springs: springs.to_string(),
+            groups,
+        })
+    }
+}
+
+/// The condition records of every row
+#[derive(Debug, PartialEq)]
+pub struct Records(pub Vec<Record>);
+
+impl FromStr for Records {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        lines(input)
+            .enumerate()
+            .map(|(index, line)| parse_record(index, line))
+            .collect::<Result<_, _>>()
+            .map(Records)
+    }
+}
+
+/// Reads a `<springs> <group sizes>` line
+pub fn parse_record(index: usize, data: &str) -> Result<Record, ParseError> {
+    let (springs, groups_str) = data
+        .split_once(" ")
+        .ok_or_else(|| ParseError::MalformedRecord(Span::line(index, data)))?;
+    check_springs(index, springs)?;
 
     let groups = groups_str
         .split(",")
-        .map(|x| x.parse().unwrap())
-        .collect::<Vec<usize>>();
+        .map(|x| parse_at(index, data, x).map_err(ParseError::InvalidGroup))
+        .collect::<Result<Vec<usize>, _>>()?;
 
-    return count_arrangements(&record.chars().collect::<Vec<char>>(), &groups, &mut cache);
+    Ok(Record {
+        springs: springs.to_string(),
+        groups,
+    })
 }
 
-pub fn arrangements_long(data: &str) -> usize {
+fn check_springs(index: usize, springs: &str) -> Result<(), ParseError> {
+    match find_invalid_char(index, springs, |c| "?.#".contains(c)) {
+        Some(span) => Err(ParseError::UnknownSpring(span)),
+        None => Ok(()),
+    }
+}
+
+/// Ways the unknown springs can be operational or damaged
+pub fn arrangements(data: &Record) -> usize {
     let mut cache: Cache = HashMap::new();
-    let (record, groups_str) = data.split_once(" ").unwrap();
 
-    let groups = groups_str
-        .split(",")
-        .map(|x| x.parse().unwrap())
-        .collect::<Vec<usize>>()
-        .repeat(5);
+    count_arrangements(
+        &data.springs.chars().collect::<Vec<char>>(),
+        &data.groups,
+        &mut cache,
+    )
+}
+
+/// Arrangements once the row is unfolded five times
+pub fn arrangements_long(data: &Record) -> usize {
+    let mut cache: Cache = HashMap::new();
 
-    return count_arrangements(
-        &[record; 5].join("?").chars().collect::<Vec<char>>(),
+    let groups = data.groups.repeat(5);
+
+    count_arrangements(
+        &[data.springs.as_str(); 5]
+            .join("?")
+            .chars()
+            .collect::<Vec<char>>(),
         &groups,
         &mut cache,
-    );
+    )
 }
 
 fn count_arrangements(mut chars: &[char], groups: &[usize], cache: &mut Cache) -> usize {
@@ -75,7 +147,7 @@ fn count_arrangements(mut chars: &[char], groups: &[usize], cache: &mut Cache) -
         None => 0,
     };
     cache.insert(key, res);
-    return res;
+    res
 }
 
 fn fits_group(mut chars: &[char], size: usize) -> Option<&[char]> {
@@ -88,15 +160,39 @@ fn fits_group(mut chars: &[char], size: usize) -> Option<&[char]> {
     }
 
     if chars.first() == Some(&'#') {
-        return None;
+        None
     } else {
-        return Some(chars);
+        Some(chars)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    // Sizes of the runs of damaged springs
+    fn groups_of(springs: &[char]) -> Vec<usize> {
+        springs
+            .split(|spring| *spring != '#')
+            .map(|run| run.len())
+            .filter(|length| *length > 0)
+            .collect()
+    }
+
+    // Tries every way of filling in the unknown springs
+    fn brute_force_arrangements(springs: &str, groups: &[usize]) -> usize {
+        let unknown: Vec<usize> = springs.match_indices('?').map(|(i, _)| i).collect();
+        (0..1_usize << unknown.len())
+            .filter(|mask| {
+                let mut filled: Vec<char> = springs.chars().collect();
+                for (bit, index) in unknown.iter().enumerate() {
+                    filled[*index] = if mask >> bit & 1 == 1 { '#' } else { '.' };
+                }
+                groups_of(&filled) == groups
+            })
+            .count()
+    }
 
     #[test]
     fn it_fits_group() {
@@ -107,8 +203,65 @@ mod tests {
         assert_eq!(fits_group(&['#', '?', '#'], 2), None);
     }
 
+    #[test]
+    fn it_parses_records() {
+        let record = parse_record(0, "???.### 1,1,3").unwrap();
+        assert_eq!(arrangements(&record), 1);
+        assert_eq!(arrangements_long(&record), 1);
+        assert_eq!(
+            parse_record(1, "???.###"),
+            Err(ParseError::MalformedRecord(Span::new(2, 1, "???.###")))
+        );
+        assert_eq!(
+            parse_record(0, "??x.### 1,1,3"),
+            Err(ParseError::UnknownSpring(Span::new(1, 3, "x")))
+        );
+        assert_eq!(
+            parse_record(0, "???.### 1,,3"),
+            Err(ParseError::InvalidGroup(Span::new(1, 11, "")))
+        );
+    }
+
+    #[test]
+    fn it_builds_records_in_code() {
+        let record = Record::new("?###????????", vec![3, 2, 1]).unwrap();
+        assert_eq!(record, parse_record(0, "?###???????? 3,2,1").unwrap());
+        assert_eq!(arrangements(&record), 10);
+        assert_eq!(
+            Record::new("?#?.x", vec![1]),
+            Err(ParseError::UnknownSpring(Span::new(1, 5, "x")))
+        );
+    }
+
     #[test]
     fn it_counts_arrangements() {
         assert_eq!(count_arrangements(&['?'], &[1], &mut HashMap::new()), 1);
     }
+
+    proptest! {
+        #[test]
+        fn it_counts_arrangements_like_brute_force(
+            springs in "[.#?]{1,12}",
+            groups in prop::collection::vec(1_usize..=4, 1..=4),
+        ) {
+            let line = format!(
+                "{springs} {}",
+                groups.iter().map(ToString::to_string).collect::<Vec<String>>().join(",")
+            );
+            let record = parse_record(0, &line).unwrap();
+            prop_assert_eq!(arrangements(&record), brute_force_arrangements(&springs, &groups));
+        }
+
+        #[test]
+        fn it_counts_unfolded_arrangements_like_brute_force(
+            springs in "[.#?]{1,2}",
+            group in 1_usize..=2,
+        ) {
+            let record = parse_record(0, &format!("{springs} {group}")).unwrap();
+            prop_assert_eq!(
+                arrangements_long(&record),
+                brute_force_arrangements(&[springs.as_str(); 5].join("?"), &[group; 5])
+            );
+        }
+    }
 }
@@ -0,0 +1,125 @@
+use aoc_core::generate::{InputRng, Rng, SliceRandom};
+
+fn row(rng: &mut InputRng, width: usize) -> Vec<char> {
+    (0..width)
+        .map(|_| match rng.gen_bool(0.5) {
+            true => '#',
+            false => '.',
+        })
+        .collect()
+}
+
+// Rows mirrored by a line before row `axis`
+fn mirrored(axis: usize, height: usize) -> std::ops::Range<usize> {
+    let reach = axis.min(height - axis);
+    axis - reach..axis + reach
+}
+
+// Tiles that differ from their reflection across the line before row `axis`
+fn differences(rows: &[Vec<char>], axis: usize) -> usize {
+    mirrored(axis, rows.len())
+        .filter(|index| *index >= axis)
+        .map(|index| {
+            let other = &rows[2 * axis - 1 - index];
+            rows[index]
+                .iter()
+                .zip(other)
+                .filter(|(a, b)| a != b)
+                .count()
+        })
+        .sum()
+}
+
+fn transpose(rows: &[Vec<char>]) -> Vec<Vec<char>> {
+    (0..rows[0].len())
+        .map(|col| rows.iter().map(|row| row[col]).collect())
+        .collect()
+}
+
+// Number of lines, across rows or columns, with a reflection off by exactly
+// `smudges` tiles
+fn reflections(rows: &[Vec<char>], smudges: usize) -> usize {
+    [rows.to_vec(), transpose(rows)]
+        .iter()
+        .map(|rows| {
+            (1..rows.len())
+                .filter(|axis| differences(rows, *axis) == smudges)
+                .count()
+        })
+        .sum()
+}
+
+// Pattern mirrored across two lines between rows, then smudged on a row the
+// first line doesn't reach so that only the second needs the smudge cleaned.
+// Patterns where any other line happens to reflect are thrown away
+fn pattern(rng: &mut InputRng) -> Vec<Vec<char>> {
+    loop {
+        let rows = candidate(rng);
+        if reflections(&rows, 0) == 1 && reflections(&rows, 1) == 1 {
+            return rows;
+        }
+    }
+}
+
+fn candidate(rng: &mut InputRng) -> Vec<Vec<char>> {
+    let (width, height) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+    let (clean, smudged, smudge) = loop {
+        let clean = rng.gen_range(1..height);
+        let smudged = rng.gen_range(1..height);
+        let rows: Vec<usize> = mirrored(smudged, height)
+            .filter(|row| !mirrored(clean, height).contains(row))
+            .collect();
+        if let Some(smudge) = rows.choose(rng).filter(|_| clean != smudged) {
+            break (clean, smudged, *smudge);
+        }
+    };
+
+    // rows equal under either reflection share the first row of their class
+    let mut class: Vec<usize> = (0..height).collect();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for axis in [clean, smudged] {
+            for index in mirrored(axis, height) {
+                let other = 2 * axis - 1 - index;
+                let lowest = class[index].min(class[other]);
+                if class[index] != lowest || class[other] != lowest {
+                    (class[index], class[other]) = (lowest, lowest);
+                    changed = true;
+                }
+            }
+        }
+    }
+    let picked: Vec<Vec<char>> = (0..height).map(|_| row(rng, width)).collect();
+    let mut rows: Vec<Vec<char>> = class.iter().map(|first| picked[*first].clone()).collect();
+
+    let col = rng.gen_range(0..width);
+    rows[smudge][col] = match rows[smudge][col] {
+        '#' => '.',
+        _ => '#',
+    };
+    rows
+}
+
+// `size` patterns, each turned on its side at even odds
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let rows = pattern(rng);
+            let (height, width) = (rows.len(), rows[0].len());
+            let vertical = rng.gen_bool(0.5);
+            (0..if vertical { width } else { height })
+                .map(|line| {
+                    (0..if vertical { height } else { width })
+                        .map(|tile| match vertical {
+                            true => rows[tile][line],
+                            false => rows[line][tile],
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
@@ -0,0 +1,44 @@
+//! Day 13: Point of Incidence
+//!
+//! [`mirrors::parse_patterns`] reads the patterns and [`mirrors::count_mirrors`] finds
+//! their mirror, with or without a smudge.
+
+use aoc_core::generate::InputRng;
+use aoc_core::parallel;
+use aoc_core::{Answer, Error, Puzzle};
+
+mod generator;
+pub mod mirrors;
+
+pub struct Mirrors;
+
+impl Puzzle for Mirrors {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "mirrors";
+
+    type Input = mirrors::Patterns;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(patterns: &Self::Input) -> Result<Answer, Error> {
+        let sum: usize =
+            parallel::map(&patterns.0, |ground| mirrors::count_mirrors(ground, &false))
+                .iter()
+                .sum();
+        Ok(sum.into())
+    }
+
+    fn part_two(patterns: &Self::Input) -> Option<Result<Answer, Error>> {
+        let sum_with_smudge: usize =
+            parallel::map(&patterns.0, |ground| mirrors::count_mirrors(ground, &true))
+                .iter()
+                .sum();
+        Some(Ok(sum_with_smudge.into()))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,23 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-mod mirrors;
+use day_13_mirrors::Mirrors;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum: usize = contents
-        .split("\n\n")
-        .map(|line| mirrors::count_mirrors(line, &false))
-        .sum();
-    println!("Sum:\n{sum}");
-    let sum_with_smudge: usize = contents
-        .split("\n\n")
-        .map(|line| mirrors::count_mirrors(line, &true))
-        .sum();
-    println!("Sum with smudge:\n{sum_with_smudge}");
+fn main() -> ExitCode {
+    aoc_core::run(&Mirrors)
 }
@@ -1,15 +1,52 @@
+use std::str::FromStr;
+
 extern crate levenshtein;
+use aoc_core::grid::{Grid, GridError};
+use aoc_core::input::paragraphs;
 use levenshtein::levenshtein;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
 
-pub fn count_mirrors(ground: &str, with_smudge: &bool) -> usize {
-    let lines = ground.lines().collect::<Vec<&str>>();
+/// The patterns of ash and rocks
+#[derive(Debug, PartialEq)]
+pub struct Patterns(pub Vec<Grid<char>>);
+
+impl FromStr for Patterns {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_patterns(input).map(Patterns)
+    }
+}
+
+/// Reads the patterns separated by blank lines
+pub fn parse_patterns(input: &str) -> Result<Vec<Grid<char>>, ParseError> {
+    paragraphs(input)
+        .into_iter()
+        .map(|(first_line, ground)| {
+            Ok(Grid::parse(ground, first_line, |c| {
+                (c == '.' || c == '#').then_some(c)
+            })?)
+        })
+        .collect()
+}
+
+/// Columns left of a vertical mirror, or 100 times the rows above a horizontal one
+pub fn count_mirrors(ground: &Grid<char>, with_smudge: &bool) -> usize {
+    let rows = get_lines(ground);
+    let lines: Vec<&str> = rows.iter().map(|x| x.as_str()).collect();
     let horizontal = find_horizontal_reflection(&lines, with_smudge);
     let vertical = find_vertical_reflection(ground, with_smudge);
     if !with_smudge {
-        if horizontal.is_some() {
-            return horizontal.unwrap().0 * 100;
+        if let Some((position, _)) = horizontal {
+            return position * 100;
         } else {
-            return vertical.and_then(|x| Some(x.0)).unwrap_or(0);
+            return vertical.map(|x| x.0).unwrap_or(0);
         }
     }
 
@@ -21,35 +58,36 @@ pub fn count_mirrors(ground: &str, with_smudge: &bool) -> usize {
         return vertical.unwrap().0;
     }
 
-    return horizontal
-        .and_then(|x| Some(x.0 * 100))
-        .or_else(|| Some(vertical.unwrap().0))
-        .unwrap();
+    horizontal
+        .map(|x| x.0 * 100)
+        .or(vertical.map(|x| x.0))
+        .unwrap_or(0)
 }
 
 fn find_horizontal_reflection(lines: &Vec<&str>, with_smudge: &bool) -> Option<(usize, bool)> {
     for i in 0..(lines.len() - 1) {
         if lines.get(i).eq(&lines.get(i + 1)) {
-            if let Some(found_smudge) = is_reflection(&lines, i, with_smudge) {
+            if let Some(found_smudge) = is_reflection(lines, i, with_smudge) {
                 if found_smudge {
                     return Some((i + 1, found_smudge));
                 }
             }
         }
-        if *with_smudge && levenshtein(lines[i], lines[i + 1]) == 1 {
-            if let Some(_) = is_reflection(&lines, i, &false) {
-                return Some((i + 1, true));
-            }
+        if *with_smudge
+            && levenshtein(lines[i], lines[i + 1]) == 1
+            && is_reflection(lines, i, &false).is_some()
+        {
+            return Some((i + 1, true));
         }
     }
 
-    return None;
+    None
 }
 
-fn find_vertical_reflection(ground: &str, with_smudge: &bool) -> Option<(usize, bool)> {
-    let convert = get_horizontal_lines(ground);
+fn find_vertical_reflection(ground: &Grid<char>, with_smudge: &bool) -> Option<(usize, bool)> {
+    let convert = get_lines(&ground.transpose());
     let horizontal_lines: Vec<&str> = convert.iter().map(|x| x.as_str()).collect();
-    return find_horizontal_reflection(&horizontal_lines, with_smudge);
+    find_horizontal_reflection(&horizontal_lines, with_smudge)
 }
 
 fn is_reflection(lines: &Vec<&str>, position: usize, with_smudge: &bool) -> Option<bool> {
@@ -70,48 +108,55 @@ fn is_reflection(lines: &Vec<&str>, position: usize, with_smudge: &bool) -> Opti
             return None;
         }
     }
-    return Some(found_smudge);
+    Some(found_smudge)
 }
 
-fn get_horizontal_lines(ground: &str) -> Vec<String> {
-    let size = ground.find("\n").unwrap();
-    let mut lines = vec![];
-
-    for i in 0..size {
-        let mut res: Vec<char> = vec![];
-        for line in ground.lines() {
-            let c = line.chars().nth(i).unwrap();
-            res.push(c);
-        }
-        lines.push(String::from_iter(res));
-    }
-
-    return lines;
+fn get_lines(ground: &Grid<char>) -> Vec<String> {
+    ground.rows().map(String::from_iter).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::Span;
+
+    fn pattern(ground: &str) -> Grid<char> {
+        parse_patterns(ground).unwrap().remove(0)
+    }
 
     #[test]
     fn it_checks_reflections() {
         // horizontal
-        assert_eq!(count_mirrors(&"..\n.#", &false), 0);
-        assert_eq!(count_mirrors(&"..\n.#", &false), 0);
-        assert_eq!(count_mirrors(&".#\n.#", &false), 100);
-        assert_eq!(count_mirrors(&"..\n.#\n.#", &false), 200);
-        assert_eq!(count_mirrors(&"..\n.#\n.#\n..", &false), 200);
-        assert_eq!(count_mirrors(&"..\n#.\n.#\n.#", &false), 300);
-        assert_eq!(count_mirrors(&"..\n#.\n.#\n.#\n#.", &false), 300);
+        assert_eq!(count_mirrors(&pattern("..\n.#"), &false), 0);
+        assert_eq!(count_mirrors(&pattern("..\n.#"), &false), 0);
+        assert_eq!(count_mirrors(&pattern(".#\n.#"), &false), 100);
+        assert_eq!(count_mirrors(&pattern("..\n.#\n.#"), &false), 200);
+        assert_eq!(count_mirrors(&pattern("..\n.#\n.#\n.."), &false), 200);
+        assert_eq!(count_mirrors(&pattern("..\n#.\n.#\n.#"), &false), 300);
+        assert_eq!(count_mirrors(&pattern("..\n#.\n.#\n.#\n#."), &false), 300);
 
         // vertical
-        assert_eq!(count_mirrors(&"....\n####", &false), 1);
+        assert_eq!(count_mirrors(&pattern("....\n####"), &false), 1);
+    }
+
+    #[test]
+    fn it_parses_patterns() {
+        assert_eq!(
+            parse_patterns("#.\n.#\n\n##").map(|patterns| patterns.len()),
+            Ok(2)
+        );
+        assert_eq!(
+            parse_patterns("#.\n.#\n\n#\n#."),
+            Err(ParseError::Grid(GridError::RaggedRow(Span::new(
+                5, 1, "#."
+            ))))
+        );
     }
 
     #[test]
     fn it_crates_horizontal_lines() {
         assert_eq!(
-            get_horizontal_lines("....\n####"),
+            get_lines(&pattern("....\n####").transpose()),
             vec![String::from(".#"); 4]
         )
     }
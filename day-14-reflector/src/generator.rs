@@ -0,0 +1,11 @@
+use aoc_core::generate::{grid, InputRng, Rng};
+
+// `size` by `size` platform, a fifth of it round rocks and a tenth cubes
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    grid(rng, size, size, |rng| match rng.gen_range(0..10) {
+        0..=1 => 'O',
+        2 => '#',
+        _ => '.',
+    })
+}
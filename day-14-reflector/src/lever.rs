@@ -1,149 +1,91 @@
+use aoc_core::grid::Grid;
 use std::collections::HashMap;
 
-pub fn count_load_north_after_cycles(schema: &str, cycles: usize) -> usize {
-    let platform: Vec<Vec<char>> = cycle(
-        &schema
-            .lines()
-            .map(|line| line.chars().collect::<Vec<char>>())
-            .collect(),
-        cycles,
-    );
-
-    return count_load_north(&platform);
+/// Load on the north beams after spinning the platform `cycles` times
+pub fn count_load_north_after_cycles(platform: &Grid<char>, cycles: usize) -> usize {
+    count_load_north(&cycle(platform, cycles))
 }
 
-pub fn cycle(v: &Vec<Vec<char>>, cycles: usize) -> Vec<Vec<char>> {
+/// The platform after tilting it north, west, south and east `cycles` times
+pub fn cycle(v: &Grid<char>, cycles: usize) -> Grid<char> {
     let mut res = v.clone();
+    // cycles done when each platform was first seen
     let mut cache: HashMap<String, usize> = HashMap::from([(cache_key(v), 0)]);
     let mut final_cycles = 0;
-    for i in 0..cycles {
+    for done in 1..=cycles {
         let cycled = cycle_east(&cycle_south(&cycle_west(&cycle_north(&res))));
-        if are_same(&cycled, &res) {
+        if cycled == res {
             return res;
         }
 
         let key = cache_key(&cycled);
-        if let Some(val) = cache.get_key_value(&key) {
-            let cycle_loop = i - val.1;
-            let remaining = cycles - i - 1;
+        res = cycled;
+        if let Some(first_seen) = cache.get(&key) {
+            let cycle_loop = done - first_seen;
+            let remaining = cycles - done;
             final_cycles = remaining % cycle_loop;
-            res = cycled;
             break;
-        } else {
-            res = cycled;
-            cache.insert(key, i);
         }
+        cache.insert(key, done);
     }
 
     for _ in 0..final_cycles {
         res = cycle_east(&cycle_south(&cycle_west(&cycle_north(&res))));
     }
 
-    return res;
+    res
 }
 
-fn count_load_north(v: &Vec<Vec<char>>) -> usize {
-    transpose(&v).iter().map(count_line_load).sum()
+fn count_load_north(v: &Grid<char>) -> usize {
+    v.transpose().rows().map(count_line_load).sum()
 }
 
-fn count_line_load(line: &Vec<char>) -> usize {
+fn count_line_load(line: &[char]) -> usize {
     let size = line.len();
     let mut total_load = 0;
-    for position in 0..size {
-        if line[position] == 'O' {
+    for (position, &tile) in line.iter().enumerate() {
+        if tile == 'O' {
             total_load += size - position;
         }
     }
 
-    return total_load;
+    total_load
 }
 
-fn cache_key(v: &Vec<Vec<char>>) -> String {
+fn cache_key(v: &Grid<char>) -> String {
     let mut key = String::new();
-    for i in 0..v.len() {
-        for j in 0..v[i].len() {
-            if v[i][j] == 'O' {
-                key.push_str(format!("({i},{j})").as_str());
-            }
-        }
-    }
-    return key;
-}
-
-fn are_same(a: &Vec<Vec<char>>, b: &Vec<Vec<char>>) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
-
-    if a.is_empty() {
-        return true;
-    }
-
-    for i in 0..a.len() {
-        if a[i].len() != b[i].len() {
-            return false;
-        }
-
-        if a[i].is_empty() {
-            return true;
-        }
-
-        for j in 0..a[0].len() {
-            if a[i][j] != b[i][j] {
-                return false;
-            }
+    for (point, tile) in v.iter() {
+        if *tile == 'O' {
+            key.push_str(format!("({},{})", point.row, point.col).as_str());
         }
     }
-
-    return true;
+    key
 }
 
-fn cycle_north(v: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    transpose(&reposition(&transpose(v)))
+fn cycle_north(v: &Grid<char>) -> Grid<char> {
+    reposition(&v.transpose()).transpose()
 }
 
-fn cycle_east(v: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    flip(&reposition(&flip(v)))
+fn cycle_east(v: &Grid<char>) -> Grid<char> {
+    reposition(&v.flip_horizontal()).flip_horizontal()
 }
 
-fn cycle_south(v: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    transpose(&flip(&reposition(&flip(&transpose(v)))))
+fn cycle_south(v: &Grid<char>) -> Grid<char> {
+    reposition(&v.transpose().flip_horizontal())
+        .flip_horizontal()
+        .transpose()
 }
 
-fn cycle_west(v: &Vec<Vec<char>>) -> Vec<Vec<char>> {
+fn cycle_west(v: &Grid<char>) -> Grid<char> {
     reposition(v)
 }
 
-fn transpose<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>>
-where
-    T: Clone,
-{
-    assert!(!v.is_empty());
-    (0..v[0].len())
-        .map(|i| v.iter().map(|inner| inner[i].clone()).collect::<Vec<T>>())
-        .collect()
-}
-
-fn flip<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>>
-where
-    T: Clone,
-{
-    assert!(!v.is_empty());
-    return v
-        .iter()
-        .map(|line| {
-            let mut res = line.clone();
-            res.reverse();
-            return res;
-        })
-        .collect();
-}
-
-fn reposition(v: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    return v.iter().map(reposition_line).collect();
+fn reposition(v: &Grid<char>) -> Grid<char> {
+    let rows: Vec<Vec<char>> = v.rows().map(reposition_line).collect();
+    Grid::from_fn(v.width(), v.height(), |point| rows[point.row][point.col])
 }
 
-fn reposition_line(line: &Vec<char>) -> Vec<char> {
+fn reposition_line(line: &[char]) -> Vec<char> {
     let mut res = vec!['.'; line.len()];
     let mut rolls_before = 0;
     for position in 0..line.len() {
@@ -163,32 +105,47 @@ fn reposition_line(line: &Vec<char>) -> Vec<char> {
         }
     }
 
-    return res;
+    res
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    // Spins the platform every single time
+    fn simulate(platform: &Grid<char>, cycles: usize) -> Grid<char> {
+        let mut platform = platform.clone();
+        for _ in 0..cycles {
+            platform = cycle_east(&cycle_south(&cycle_west(&cycle_north(&platform))));
+        }
+        platform
+    }
+
+    fn platform() -> impl Strategy<Value = Grid<char>> {
+        (1_usize..=5, 1_usize..=5).prop_flat_map(|(width, height)| {
+            prop::collection::vec(prop::sample::select(vec!['O', '.', '#']), width * height)
+                .prop_map(move |tiles| {
+                    Grid::from_fn(width, height, |point| tiles[point.row * width + point.col])
+                })
+        })
+    }
 
     #[test]
-    fn it_compares_vecs() {
-        assert_eq!(
-            are_same(
-                &vec![vec!['a', 'b'], vec!['a', 'c']],
-                &vec![vec!['a', 'b'], vec!['a', 'c']]
-            ),
-            true
-        );
+    fn it_cycles_platform() {
+        let platform = make_vec("O.#\n.O.\n#.O");
+        assert_eq!(cycle_north(&platform).to_text(), "OO#\n..O\n#..");
+        assert_eq!(cycle(&platform, 1).to_text(), ".O#\n..O\n#.O");
     }
 
     #[test]
     fn it_reposition_rocks() {
-        assert_eq!(reposition_line(&vec!['.', 'O']), vec!['O', '.']);
-        assert_eq!(reposition_line(&vec!['.', '#', 'O']), vec!['.', '#', 'O']);
-        assert_eq!(reposition_line(&vec!['.', '.', 'O']), vec!['O', '.', '.']);
-        assert_eq!(reposition_line(&vec!['#', '.', 'O']), vec!['#', 'O', '.']);
+        assert_eq!(reposition_line(&['.', 'O']), vec!['O', '.']);
+        assert_eq!(reposition_line(&['.', '#', 'O']), vec!['.', '#', 'O']);
+        assert_eq!(reposition_line(&['.', '.', 'O']), vec!['O', '.', '.']);
+        assert_eq!(reposition_line(&['#', '.', 'O']), vec!['#', 'O', '.']);
         assert_eq!(
-            reposition_line(&vec!['#', '.', 'O', 'O']),
+            reposition_line(&['#', '.', 'O', 'O']),
             vec!['#', 'O', 'O', '.']
         );
     }
@@ -227,7 +184,14 @@ mod tests {
         );
     }
 
-    fn make_vec(s: &str) -> Vec<Vec<char>> {
-        s.lines().map(|line| line.chars().collect()).collect()
+    fn make_vec(s: &str) -> Grid<char> {
+        Grid::parse(s, 0, Some).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn it_skips_cycles_like_simulation(platform in platform(), cycles in 0_usize..60) {
+            prop_assert_eq!(cycle(&platform, cycles), simulate(&platform, cycles));
+        }
     }
 }
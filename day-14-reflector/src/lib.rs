@@ -0,0 +1,42 @@
+//! Day 14: Parabolic Reflector Dish
+//!
+//! [`reflector::parse_platform`] reads the platform, [`reflector::count_load_north`]
+//! and [`lever::count_load_north_after_cycles`] weigh it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+mod generator;
+pub mod lever;
+pub mod reflector;
+
+const CYCLES: usize = 1_000_000_000;
+
+pub struct Reflector;
+
+impl Puzzle for Reflector {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "reflector";
+
+    type Input = reflector::Platform;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(platform: &Self::Input) -> Result<Answer, Error> {
+        Ok(reflector::count_load_north(&platform.0).into())
+    }
+
+    fn part_two(platform: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(lever::count_load_north_after_cycles(
+            &platform.0,
+            CYCLES,
+        )
+        .into()))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,57 +1,83 @@
-pub fn count_load_north(schema: &str) -> usize {
-    let platform: Vec<Vec<char>> = schema
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect();
+use std::str::FromStr;
 
-    return transpose(&platform).iter().map(count_line_load).sum();
+use aoc_core::grid::{Grid, GridError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
+
+/// The platform of rounded and cube-shaped rocks
+#[derive(Debug, PartialEq)]
+pub struct Platform(pub Grid<char>);
+
+impl FromStr for Platform {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_platform(input).map(Platform)
+    }
 }
 
-fn transpose<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>>
-where
-    T: Clone,
-{
-    assert!(!v.is_empty());
-    (0..v[0].len())
-        .map(|i| v.iter().map(|inner| inner[i].clone()).collect::<Vec<T>>())
-        .collect()
+/// Reads the platform of `O`, `#` and `.` tiles
+pub fn parse_platform(schema: &str) -> Result<Grid<char>, ParseError> {
+    Ok(Grid::parse(schema, 0, |c| "O.#".contains(c).then_some(c))?)
 }
 
-fn count_line_load(line: &Vec<char>) -> usize {
+/// Load on the north beams once the platform is tilted north
+pub fn count_load_north(platform: &Grid<char>) -> usize {
+    platform.transpose().rows().map(count_line_load).sum()
+}
+
+fn count_line_load(line: &[char]) -> usize {
     let size = line.len();
     let mut total_load = 0;
     let mut rolls_before = 0;
-    for position in 0..size {
-        if line[position] == '.' {
+    for (position, &tile) in line.iter().enumerate() {
+        if tile == '.' {
             rolls_before += 1;
             continue;
         }
 
-        if line[position] == '#' {
+        if tile == '#' {
             rolls_before = 0;
             continue;
         }
 
-        if line[position] == 'O' {
+        if tile == 'O' {
             total_load += size - position + rolls_before;
         }
     }
 
-    return total_load;
+    total_load
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::Span;
+
+    #[test]
+    fn it_parses_platform() {
+        assert_eq!(count_load_north(&parse_platform("O.\n.O").unwrap()), 4);
+        assert_eq!(
+            parse_platform("O.\n.@"),
+            Err(ParseError::Grid(GridError::UnknownTile(Span::new(
+                2, 2, "@"
+            ))))
+        );
+    }
 
     #[test]
     fn it_calculates_load_in_line() {
-        assert_eq!(count_line_load(&vec!['O']), 1);
-        assert_eq!(count_line_load(&vec!['O', '.']), 2);
-        assert_eq!(count_line_load(&vec!['O', '.', 'O']), 3 + 2);
-        assert_eq!(count_line_load(&vec!['O', '.', '.', 'O']), 4 + 3);
-        assert_eq!(count_line_load(&vec!['O', '#', '.', 'O']), 4 + 2);
-        assert_eq!(count_line_load(&vec!['O', '.', '#', 'O']), 4 + 1);
-        assert_eq!(count_line_load(&vec!['O', '#', '.', 'O', 'O']), 5 + 3 + 2);
+        assert_eq!(count_line_load(&['O']), 1);
+        assert_eq!(count_line_load(&['O', '.']), 2);
+        assert_eq!(count_line_load(&['O', '.', 'O']), 3 + 2);
+        assert_eq!(count_line_load(&['O', '.', '.', 'O']), 4 + 3);
+        assert_eq!(count_line_load(&['O', '#', '.', 'O']), 4 + 2);
+        assert_eq!(count_line_load(&['O', '.', '#', 'O']), 4 + 1);
+        assert_eq!(count_line_load(&['O', '#', '.', 'O', 'O']), 5 + 3 + 2);
     }
 }
@@ -0,0 +1,24 @@
+use aoc_core::generate::{word, InputRng, Rng, SliceRandom};
+
+// `size` steps over a pool of labels small enough for lenses to be replaced
+// and removed
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    let labels: Vec<String> = (0..size / 3 + 1)
+        .map(|_| {
+            let length = rng.gen_range(2..=6);
+            word(rng, length)
+        })
+        .collect();
+
+    (0..size)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            match rng.gen_ratio(1, 3) {
+                true => format!("{label}-"),
+                false => format!("{label}={}", rng.gen_range(1..=9)),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
@@ -2,7 +2,9 @@ use std::collections::HashMap;
 
 type Cache = HashMap<(usize, usize), usize>;
 
-struct Hasher {
+/// Runs the HASH algorithm, remembering the steps it has already done
+#[derive(Default)]
+pub struct Hasher {
     cache: Cache,
 }
 
@@ -13,29 +15,32 @@ impl Hasher {
         }
     }
 
+    /// Sum of the hashes of the comma separated steps
     pub fn hash_string(&mut self, string: &str) -> usize {
         string
             .as_bytes()
             .iter()
-            .fold(0 as usize, |acc, code| self.digest(&acc, &(*code as usize)))
+            .fold(0_usize, |acc, code| self.digest(&acc, &(*code as usize)))
     }
 
     fn digest(&mut self, acc: &usize, code: &usize) -> usize {
         if let Some(cached) = self.cache.get(&(*acc, *code)) {
             return *cached;
         }
-        let res = (*acc as usize + *code as usize) * 17 % 256;
+        let res = (*acc + *code) * 17 % 256;
         self.cache.insert((*acc, *code), res);
-        return res as usize;
+        res
     }
 }
 
 pub fn hash_string(string: &str) -> usize {
+    hash_steps(string.split(","))
+}
+
+/// Sum of the hashes of the steps
+pub fn hash_steps<'a>(steps: impl Iterator<Item = &'a str>) -> usize {
     let mut hasher = Hasher::new();
-    string
-        .split(",")
-        .map(|line| hasher.hash_string(line) as usize)
-        .sum()
+    steps.map(|line| hasher.hash_string(line)).sum()
 }
 
 #[cfg(test)]
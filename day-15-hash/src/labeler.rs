@@ -1,50 +1,131 @@
+use std::str::FromStr;
+
+use aoc_core::input::trim_input;
+use aoc_core::Span;
+use thiserror::Error;
+
 use crate::hash;
 
-const DASH: &u8 = &b'-';
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<label>-` or `<label>=<focal length>` {0}")]
+    MalformedStep(Span),
+    #[error("focal length must be a digit from 1 to 9 {0}")]
+    InvalidFocalLength(Span),
+}
+
+#[derive(Debug, PartialEq)]
+enum Operation {
+    Remove,
+    Assign(u8),
+}
+
+/// A `<label>-` or `<label>=<focal length>` step
+#[derive(Debug, PartialEq)]
+pub struct Step {
+    text: String,
+    label: String,
+    operation: Operation,
+}
+
+impl Step {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// The initialization sequence
+#[derive(Debug, PartialEq)]
+pub struct Sequence(pub Vec<Step>);
+
+impl FromStr for Sequence {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_steps(input).map(Sequence)
+    }
+}
+
+/// Reads the comma separated steps
+pub fn parse_steps(sequence: &str) -> Result<Vec<Step>, ParseError> {
+    let mut column = 0;
+    trim_input(sequence)
+        .split(',')
+        .map(|step| {
+            let parsed = parse_step(column, step);
+            column += step.len() + 1;
+            parsed
+        })
+        .collect()
+}
+
+/// `column` is the zero based position of `step` in the sequence
+pub fn parse_step(column: usize, step: &str) -> Result<Step, ParseError> {
+    let span = |fragment| {
+        let mut span = Span::at(0, step, fragment);
+        span.column += column;
+        span
+    };
+    let malformed = || ParseError::MalformedStep(span(step));
+    let (label, operation) = if let Some(label) = step.strip_suffix('-') {
+        (label, Operation::Remove)
+    } else {
+        let (label, focal_length) = step.split_once('=').ok_or_else(malformed)?;
+        let focal_length = match focal_length.parse::<u8>() {
+            Ok(focal_length @ 1..=9) => focal_length,
+            _ => return Err(ParseError::InvalidFocalLength(span(focal_length))),
+        };
+        (label, Operation::Assign(focal_length))
+    };
+    if label.is_empty() || label.contains(['-', '=']) {
+        return Err(malformed());
+    }
+
+    Ok(Step {
+        text: step.to_string(),
+        label: label.to_string(),
+        operation,
+    })
+}
 
+/// The 256 boxes of lenses steps are applied to
 #[derive(Debug)]
-struct Library {
+pub struct Library {
     boxes: Vec<Box>,
 }
 
-impl Library {
-    fn apply(&mut self, step: &str) {
-        match step.as_bytes().last() {
-            Some(DASH) => self.remove_operation(step),
-            Some(_) => self.assign_operation(step),
-            None => panic!("Unexpected operation in step {:?}", step),
-        }
+impl Default for Library {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    fn box_item(&mut self, label_str: &str) -> &mut Box {
-        let index = hash::hash_string(label_str);
-        self.boxes
-            .get_mut(index)
-            .expect(format!("Box should exist {index}").as_str())
+impl Library {
+    pub fn new() -> Self {
+        Library {
+            boxes: (0..256).map(|_| Box { lenses: vec![] }).collect(),
+        }
     }
 
-    fn remove_operation(&mut self, step: &str) {
-        let [label @ .., _operation] = step.as_bytes() else { todo!() };
-        let label_str = std::str::from_utf8(label).expect("Must be string");
-        self.box_item(label_str).remove_lens(label_str);
+    pub fn apply(&mut self, step: &Step) {
+        let box_item = self.box_item(&step.label);
+        match step.operation {
+            Operation::Remove => box_item.remove_lens(&step.label),
+            Operation::Assign(focal_length) => box_item.assign_lens(&step.label, focal_length),
+        }
     }
 
-    fn assign_operation(&mut self, step: &str) {
-        let [label @ .., _operation, focal_length] = step.as_bytes() else { todo!() };
-        let label_str = std::str::from_utf8(label).expect("Must be string");
-        let focal_length_num: u8 = std::str::from_utf8(&[*focal_length])
-            .expect("Must be number")
-            .parse::<u8>()
-            .unwrap();
-        self.box_item(label_str)
-            .assign_lens(label_str, focal_length_num);
+    fn box_item(&mut self, label_str: &str) -> &mut Box {
+        let index = hash::hash_string(label_str);
+        &mut self.boxes[index]
     }
 
-    fn focusing_power(&self) -> usize {
+    /// Focusing power of the lenses once every step is applied
+    pub fn focusing_power(&self) -> usize {
         self.boxes
             .iter()
             .enumerate()
-            .map(|(box_position, box_item)| {
+            .flat_map(|(box_position, box_item)| {
                 box_item
                     .lenses
                     .iter()
@@ -53,7 +134,6 @@ impl Library {
                         (box_position + 1) * (lens_position + 1) * lens.focal_length as usize
                     })
             })
-            .flatten()
             .sum()
     }
 }
@@ -71,7 +151,7 @@ struct Box {
 
 impl Box {
     fn remove_lens(&mut self, label: &str) {
-        self.lenses.retain(|lens| lens.label != label.to_string())
+        self.lenses.retain(|lens| lens.label != label)
     }
 
     fn assign_lens(&mut self, label: &str, focal_length: u8) {
@@ -90,14 +170,39 @@ impl Box {
     }
 }
 
-pub fn focusing_power(instructions: &str) -> usize {
-    let mut library = Library {
-        boxes: (0..256).map(|_| Box { lenses: vec![] }).collect(),
-    };
+pub fn focusing_power(instructions: &[Step]) -> usize {
+    let mut library = Library::new();
 
-    for step in instructions.split(',') {
+    for step in instructions {
         library.apply(step)
     }
 
-    return library.focusing_power();
+    library.focusing_power()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_focuses_lenses() {
+        let steps = parse_steps("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap();
+        assert_eq!(focusing_power(&steps), 145);
+    }
+
+    #[test]
+    fn it_reports_malformed_steps() {
+        assert_eq!(
+            parse_steps("rn=1,cm"),
+            Err(ParseError::MalformedStep(Span::new(1, 6, "cm")))
+        );
+        assert_eq!(
+            parse_steps("rn=1,,cm-"),
+            Err(ParseError::MalformedStep(Span::new(1, 6, "")))
+        );
+        assert_eq!(
+            parse_steps("rn=0"),
+            Err(ParseError::InvalidFocalLength(Span::new(1, 4, "0")))
+        );
+    }
 }
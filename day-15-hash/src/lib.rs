@@ -0,0 +1,63 @@
+//! Day 15: Lens Library
+//!
+//! [`hash::Hasher`] runs the HASH algorithm, [`labeler::parse_steps`] reads the steps a
+//! [`labeler::Library`] applies.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+use crate::hash::Hasher;
+use crate::labeler::{Library, Sequence, Step};
+
+mod generator;
+pub mod hash;
+pub mod labeler;
+
+pub struct Hash;
+
+impl Puzzle for Hash {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "hash";
+
+    type Input = Sequence;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(sequence: &Self::Input) -> Result<Answer, Error> {
+        Ok(hash::hash_steps(sequence.0.iter().map(Step::text)).into())
+    }
+
+    fn part_two(sequence: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(labeler::focusing_power(&sequence.0).into()))
+    }
+
+    // the sequence is one long line, read a step at a time
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        let mut hasher = Hasher::new();
+        let mut library = Library::new();
+        let (mut sum, mut column) = (0, 0);
+        let read = read_records(input, b',', |_, step| {
+            let parsed = labeler::parse_step(column, step)?;
+            stopwatch.time(Part::One, || sum += hasher.hash_string(step));
+            stopwatch.time(Part::Two, || library.apply(&parsed));
+            column += step.len() + 1;
+            Ok(())
+        });
+        Some(read.map(|()| Streamed {
+            part_one: stopwatch.is_selected(Part::One).then(|| Ok(sum.into())),
+            part_two: stopwatch.time(Part::Two, || Ok(library.focusing_power().into())),
+        }))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,7 +1,19 @@
+use aoc_core::grid::{Direction, Grid, GridError, Point};
+use aoc_core::parallel;
+use aoc_core::visualize::{Color, Painter};
 use std::collections::HashSet;
+use std::str::FromStr;
+use thiserror::Error;
 
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
+
+/// Tiles of the contraption
 #[derive(Debug, Clone, Copy)]
-enum TileType {
+pub enum TileType {
     Empty,
     SplitterTopBottom,
     SplitterLeftRight,
@@ -9,244 +21,207 @@ enum TileType {
     MirrorBackward,
 }
 
-#[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Top,
-    Bottom,
-    Left,
-    Right,
-}
-
 #[derive(Debug, Hash, Clone, Copy, PartialEq, Eq)]
 struct Beam {
     direction: Direction,
-    row: usize,
-    col: usize,
+    point: Point,
 }
 
-type Grid = Vec<Vec<TileType>>;
-
-fn parse_tiles(map: &str) -> Grid {
-    map.lines()
-        .map(|line| {
-            line.as_bytes()
-                .iter()
-                .map(|byte| match byte {
-                    b'.' => TileType::Empty,
-                    b'|' => TileType::SplitterTopBottom,
-                    b'-' => TileType::SplitterLeftRight,
-                    b'/' => TileType::MirrorForward,
-                    b'\\' => TileType::MirrorBackward,
-                    _ => panic!("Unknown tile {}", byte),
-                })
-                .collect()
-        })
-        .collect()
+pub type Tiles = Grid<TileType>;
+
+fn tile(c: char) -> Option<TileType> {
+    match c {
+        '.' => Some(TileType::Empty),
+        '|' => Some(TileType::SplitterTopBottom),
+        '-' => Some(TileType::SplitterLeftRight),
+        '/' => Some(TileType::MirrorForward),
+        '\\' => Some(TileType::MirrorBackward),
+        _ => None,
+    }
 }
 
-pub fn count_energized_tiles(map: &str) -> usize {
-    count_energized_tiles_for_beam(
-        &Beam {
-            direction: Direction::Right,
-            col: 0,
-            row: 0,
-        },
-        &parse_tiles(map),
-    )
+/// The contraption of mirrors and splitters
+#[derive(Debug)]
+pub struct Contraption(pub Tiles);
+
+impl FromStr for Contraption {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_tiles(input).map(Contraption)
+    }
 }
 
-pub fn count_most_energized_tiles(map: &str) -> usize {
-    let tiles = parse_tiles(map);
-    return (0..tiles.len())
-        .map(|row| {
-            if row == 0 {
-                return (0..tiles[row].len())
-                    .map(|col| Beam {
-                        direction: Direction::Bottom,
-                        row,
-                        col,
-                    })
-                    .chain([
-                        Beam {
-                            direction: Direction::Right,
-                            row: 0,
-                            col: 0,
-                        },
-                        Beam {
-                            direction: Direction::Left,
-                            row: 0,
-                            col: tiles[row].len() - 1,
-                        },
-                    ])
-                    .collect::<Vec<Beam>>();
-            }
-
-            if row == tiles.len() - 1 {
-                return (0..tiles[row].len())
-                    .map(|col| Beam {
-                        direction: Direction::Top,
-                        row,
-                        col,
-                    })
-                    .chain([
-                        Beam {
-                            direction: Direction::Right,
-                            row: 0,
-                            col: 0,
-                        },
-                        Beam {
-                            direction: Direction::Left,
-                            row: 0,
-                            col: tiles[row].len() - 1,
-                        },
-                    ])
-                    .collect::<Vec<Beam>>();
-            }
-
-            return vec![
-                Beam {
-                    direction: Direction::Right,
-                    row,
-                    col: 0,
-                },
-                Beam {
-                    direction: Direction::Left,
-                    row,
-                    col: tiles[row].len() - 1,
-                },
-            ];
-        })
-        .flatten()
-        .map(|beam| count_energized_tiles_for_beam(&beam, &tiles))
-        .max()
-        .unwrap();
+/// Reads the contraption of `.`, `/`, `\\`, `|` and `-` tiles
+pub fn parse_tiles(map: &str) -> Result<Tiles, ParseError> {
+    Ok(Grid::parse(map, 0, tile)?)
+}
+
+const ENTRY: Beam = Beam {
+    direction: Direction::East,
+    point: Point { row: 0, col: 0 },
+};
+
+/// Tiles a beam entering the top left corner heading right energizes
+pub fn count_energized_tiles(tiles: &Tiles) -> usize {
+    energized_tiles(&ENTRY, tiles).len()
+}
+
+/// Most tiles a beam entering from any edge energizes
+pub fn count_most_energized_tiles(tiles: &Tiles) -> usize {
+    energized_tiles(&best_beam(tiles), tiles).len()
 }
 
-fn count_energized_tiles_for_beam(beam: &Beam, tiles: &Grid) -> usize {
-    let mut beams: Vec<Beam> = vec![beam.clone()];
-    let mut visited_tiles = HashSet::from([beam.clone()]);
+// Entry beam from any edge energizing the most tiles
+fn best_beam(tiles: &Tiles) -> Beam {
+    let last_row = tiles.height() - 1;
+    let last_col = tiles.width() - 1;
+    let beam = |row, col, direction| Beam {
+        direction,
+        point: Point::new(row, col),
+    };
 
-    while beams.len() != 0 {
+    let beams: Vec<Beam> = (0..tiles.width())
+        .flat_map(|col| {
+            [
+                beam(0, col, Direction::South),
+                beam(last_row, col, Direction::North),
+            ]
+        })
+        .chain((0..tiles.height()).flat_map(|row| {
+            [
+                beam(row, 0, Direction::East),
+                beam(row, last_col, Direction::West),
+            ]
+        }))
+        .collect();
+    let energized = parallel::map(&beams, |beam| energized_tiles(beam, tiles).len());
+
+    beams
+        .into_iter()
+        .zip(energized)
+        .max_by_key(|(_, energized)| *energized)
+        .map_or(ENTRY, |(beam, _)| beam)
+}
+
+fn energized_tiles(beam: &Beam, tiles: &Tiles) -> HashSet<Point> {
+    let mut beams: Vec<Beam> = vec![*beam];
+    let mut visited_tiles = HashSet::from([*beam]);
+
+    while !beams.is_empty() {
         beams = beams
             .iter()
-            .map(|beam| move_beam(beam, &tiles))
-            .flatten()
-            .flatten()
+            .flat_map(|beam| move_beam(beam, tiles))
             .filter(|item| !visited_tiles.contains(item))
             .collect();
 
         visited_tiles.extend(beams.iter());
     }
 
-    return visited_tiles
-        .iter()
-        .map(|beam| (beam.row, beam.col))
-        .collect::<HashSet<(usize, usize)>>()
-        .len();
+    visited_tiles.iter().map(|beam| beam.point).collect()
 }
 
-fn move_beam(beam: &Beam, tiles: &Grid) -> Vec<Option<Beam>> {
-    match beam.direction {
-        Direction::Right => move_beam_right(beam, tiles),
-        Direction::Left => move_beam_left(beam, tiles),
-        Direction::Top => move_beam_top(beam, tiles),
-        Direction::Bottom => move_beam_bottom(beam, tiles),
-    }
-}
+/// Contraption with the tiles energized by the part's beam highlighted
+pub fn draw_energized(tiles: &Tiles, most: bool, painter: &Painter) -> String {
+    let beam = match most {
+        true => best_beam(tiles),
+        false => ENTRY,
+    };
+    let energized = energized_tiles(&beam, tiles);
 
-fn get_beam_top(beam: &Beam) -> Option<Beam> {
-    if beam.row == 0 {
-        None
-    } else {
-        Some(Beam {
-            direction: Direction::Top,
-            row: beam.row - 1,
-            col: beam.col,
-        })
-    }
-}
-
-fn get_beam_bottom(beam: &Beam, tiles: &Grid) -> Option<Beam> {
-    if beam.row + 1 >= tiles.len() {
-        None
-    } else {
-        Some(Beam {
-            direction: Direction::Bottom,
-            row: beam.row + 1,
-            col: beam.col,
-        })
-    }
-}
-
-fn get_beam_left(beam: &Beam) -> Option<Beam> {
-    if beam.col == 0 {
-        None
-    } else {
-        Some(Beam {
-            direction: Direction::Left,
-            row: beam.row,
-            col: beam.col - 1,
-        })
-    }
+    painter.grid(tiles, |point, tile| {
+        match (energized.contains(&point), glyph(*tile)) {
+            (true, '.') => ('#', Some(Color::Yellow)),
+            (true, glyph) => (glyph, Some(Color::Red)),
+            (false, glyph) => (glyph, Some(Color::Dim)),
+        }
+    })
 }
 
-fn get_beam_right(beam: &Beam, tiles: &Grid) -> Option<Beam> {
-    assert!(tiles.len() > 0);
-    if beam.col + 1 >= tiles[0].len() {
-        None
-    } else {
-        Some(Beam {
-            direction: Direction::Right,
-            row: beam.row,
-            col: beam.col + 1,
-        })
+fn glyph(tile: TileType) -> char {
+    match tile {
+        TileType::Empty => '.',
+        TileType::SplitterTopBottom => '|',
+        TileType::SplitterLeftRight => '-',
+        TileType::MirrorForward => '/',
+        TileType::MirrorBackward => '\\',
     }
 }
 
-fn move_beam_right(beam: &Beam, tiles: &Grid) -> Vec<Option<Beam>> {
-    match tiles[beam.row][beam.col] {
-        TileType::Empty => vec![get_beam_right(beam, tiles)],
-        TileType::SplitterTopBottom => {
-            vec![get_beam_top(beam), get_beam_bottom(beam, tiles)]
+// Directions a beam heading in `direction` leaves `tile` in
+fn deflect(tile: TileType, direction: Direction) -> Vec<Direction> {
+    match (tile, direction) {
+        (TileType::SplitterTopBottom, Direction::East | Direction::West) => {
+            vec![Direction::North, Direction::South]
+        }
+        (TileType::SplitterLeftRight, Direction::North | Direction::South) => {
+            vec![Direction::West, Direction::East]
         }
-        TileType::SplitterLeftRight => vec![get_beam_right(beam, tiles)],
-        TileType::MirrorForward => vec![get_beam_top(beam)],
-        TileType::MirrorBackward => vec![get_beam_bottom(beam, tiles)],
+        (TileType::MirrorForward, Direction::East | Direction::West) => {
+            vec![direction.turn_left()]
+        }
+        (TileType::MirrorForward, Direction::North | Direction::South) => {
+            vec![direction.turn_right()]
+        }
+        (TileType::MirrorBackward, Direction::East | Direction::West) => {
+            vec![direction.turn_right()]
+        }
+        (TileType::MirrorBackward, Direction::North | Direction::South) => {
+            vec![direction.turn_left()]
+        }
+        _ => vec![direction],
     }
 }
 
-fn move_beam_left(beam: &Beam, tiles: &Grid) -> Vec<Option<Beam>> {
-    match tiles[beam.row][beam.col] {
-        TileType::Empty => vec![get_beam_left(beam)],
-        TileType::SplitterTopBottom => {
-            vec![get_beam_top(beam), get_beam_bottom(beam, tiles)]
-        }
-        TileType::SplitterLeftRight => vec![get_beam_left(beam)],
-        TileType::MirrorForward => vec![get_beam_bottom(beam, tiles)],
-        TileType::MirrorBackward => vec![get_beam_top(beam)],
-    }
+fn move_beam(beam: &Beam, tiles: &Tiles) -> Vec<Beam> {
+    deflect(tiles[beam.point], beam.direction)
+        .into_iter()
+        .filter_map(|direction| {
+            Some(Beam {
+                direction,
+                point: tiles.step(beam.point, direction)?,
+            })
+        })
+        .collect()
 }
 
-fn move_beam_top(beam: &Beam, tiles: &Grid) -> Vec<Option<Beam>> {
-    match tiles[beam.row][beam.col] {
-        TileType::Empty => vec![get_beam_top(beam)],
-        TileType::SplitterTopBottom => vec![get_beam_top(beam)],
-        TileType::SplitterLeftRight => {
-            vec![get_beam_left(beam), get_beam_right(beam, tiles)]
-        }
-        TileType::MirrorForward => vec![get_beam_right(beam, tiles)],
-        TileType::MirrorBackward => vec![get_beam_left(beam)],
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Span;
+
+    #[test]
+    fn it_deflects_beams() {
+        assert_eq!(
+            deflect(TileType::MirrorForward, Direction::East),
+            [Direction::North]
+        );
+        assert_eq!(
+            deflect(TileType::MirrorBackward, Direction::North),
+            [Direction::West]
+        );
+        assert_eq!(
+            deflect(TileType::SplitterLeftRight, Direction::South),
+            [Direction::West, Direction::East]
+        );
+        assert_eq!(
+            deflect(TileType::SplitterLeftRight, Direction::East),
+            [Direction::East]
+        );
     }
-}
 
-fn move_beam_bottom(beam: &Beam, tiles: &Grid) -> Vec<Option<Beam>> {
-    match tiles[beam.row][beam.col] {
-        TileType::Empty => vec![get_beam_bottom(beam, tiles)],
-        TileType::SplitterTopBottom => vec![get_beam_bottom(beam, tiles)],
-        TileType::SplitterLeftRight => {
-            vec![get_beam_left(beam), get_beam_right(beam, tiles)]
-        }
-        TileType::MirrorForward => vec![get_beam_left(beam)],
-        TileType::MirrorBackward => vec![get_beam_right(beam, tiles)],
+    #[test]
+    fn it_energizes_tiles() {
+        let tiles = parse_tiles(".|.\n...\n.-.").unwrap();
+        assert_eq!(count_energized_tiles(&tiles), 6);
+        assert_eq!(count_most_energized_tiles(&tiles), 6);
+        assert_eq!(
+            draw_energized(&tiles, false, &Painter::new(false)),
+            "#|.\n.#.\n#-#"
+        );
+        assert_eq!(
+            parse_tiles(".|.\n.x.").unwrap_err(),
+            ParseError::Grid(GridError::UnknownTile(Span::new(2, 2, "x")))
+        );
     }
 }
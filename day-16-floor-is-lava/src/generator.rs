@@ -0,0 +1,10 @@
+use aoc_core::generate::{grid, InputRng, Rng, SliceRandom};
+
+// `size` by `size` contraption with a mirror or splitter in one tile of ten
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    grid(rng, size, size, |rng| match rng.gen_ratio(1, 10) {
+        true => *['|', '-', '/', '\\'].choose(rng).unwrap(),
+        false => '.',
+    })
+}
@@ -0,0 +1,45 @@
+//! Day 16: The Floor Will Be Lava
+//!
+//! [`beamer::parse_tiles`] reads the contraption, [`beamer::count_energized_tiles`] and
+//! [`beamer::count_most_energized_tiles`] send beams through it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::visualize::Painter;
+use aoc_core::{Answer, Error, Part, Puzzle};
+
+pub mod beamer;
+mod generator;
+
+pub struct FloorIsLava;
+
+impl Puzzle for FloorIsLava {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "floor-is-lava";
+
+    type Input = beamer::Contraption;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(tiles: &Self::Input) -> Result<Answer, Error> {
+        Ok(beamer::count_energized_tiles(&tiles.0).into())
+    }
+
+    fn part_two(tiles: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(beamer::count_most_energized_tiles(&tiles.0).into()))
+    }
+
+    fn visualize(
+        tiles: &Self::Input,
+        part: Part,
+        painter: &Painter,
+    ) -> Option<Result<String, Error>> {
+        let most = part == Part::Two;
+        Some(Ok(beamer::draw_energized(&tiles.0, most, painter)))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,17 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-mod beamer;
+use day_16_floor_is_lava::FloorIsLava;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum: usize = beamer::count_energized_tiles(&contents);
-    println!("Sum:\n{sum}");
-    let most_energized_sum: usize = beamer::count_most_energized_tiles(&contents);
-    println!("Most energized sum:\n{most_energized_sum}");
+fn main() -> ExitCode {
+    aoc_core::run(&FloorIsLava)
 }
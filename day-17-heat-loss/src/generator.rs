@@ -0,0 +1,9 @@
+use aoc_core::generate::{grid, InputRng, Rng};
+
+// `size` by `size` city, at least two blocks wide, of heat losses 1 to 9
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(2);
+    grid(rng, size, size, |rng| {
+        char::from_digit(rng.gen_range(1..=9), 10).unwrap()
+    })
+}
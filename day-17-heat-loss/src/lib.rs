@@ -0,0 +1,49 @@
+//! Day 17: Clumsy Crucible
+//!
+//! [`router::parse_city`] reads the city, [`router::min_heat_loss`] and
+//! [`router::min_heat_loss_ultra`] route crucibles through it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::visualize::Painter;
+use aoc_core::{Answer, Error, Part, Puzzle};
+
+mod generator;
+pub mod router;
+
+pub struct HeatLoss;
+
+impl Puzzle for HeatLoss {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "heat-loss";
+
+    type Input = router::Map;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(map: &Self::Input) -> Result<Answer, Error> {
+        Ok(router::min_heat_loss(&map.0)?.into())
+    }
+
+    fn part_two(map: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(
+            router::min_heat_loss_ultra(&map.0)
+                .map(Answer::from)
+                .map_err(Error::from),
+        )
+    }
+
+    fn visualize(
+        map: &Self::Input,
+        part: Part,
+        painter: &Painter,
+    ) -> Option<Result<String, Error>> {
+        let ultra = part == Part::Two;
+        Some(router::draw_route(&map.0, ultra, painter).map_err(Error::from))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,22 +1,57 @@
-use pathfinding::prelude::dijkstra;
+use std::str::FromStr;
+
+use aoc_core::grid::{Direction, Grid, GridError, Point};
+use aoc_core::search::astar;
+use aoc_core::visualize::{Color, Painter};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("city must be at least 2 blocks wide and tall")]
+    TooSmall,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("the crucible can't reach the bottom right block")]
+    NoRoute,
+}
+
+/// Heat loss of every city block
+pub type City = Grid<u8>;
+
+/// The heat loss map of the city
+#[derive(Debug, PartialEq)]
+pub struct Map(pub City);
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_city(input).map(Map)
+    }
+}
+
+/// Reads the heat loss digits of each city block
+pub fn parse_city(map: &str) -> Result<City, ParseError> {
+    let city = Grid::parse(map, 0, |c| c.to_digit(10).map(|loss| loss as u8))?;
+    if city.height() < 2 || city.width() < 2 {
+        return Err(ParseError::TooSmall);
+    }
+
+    Ok(city)
+}
 
 enum CrucibleType {
     Small,
     Ultra,
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-    Top,
-    Bottom,
-}
-
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct Block {
-    row: usize,
-    col: usize,
+    point: Point,
     direction: Direction,
     step: usize,
 }
@@ -24,19 +59,18 @@ struct Block {
 impl Block {
     fn new(row: usize, col: usize, direction: Direction, step: usize) -> Self {
         Self {
-            row,
-            col,
+            point: Point::new(row, col),
             direction,
             step,
         }
     }
 
-    fn loss(&self, grid: &Grid) -> usize {
-        (grid[self.row][self.col] - 48) as usize
+    fn loss(&self, grid: &City) -> usize {
+        grid[self.point] as usize
     }
 
-    fn successors(&self, grid: &Grid, crucible: &CrucibleType) -> Vec<(Block, usize)> {
-        if self.row == grid.len() - 1 && self.col == grid[0].len() - 1 {
+    fn successors(&self, grid: &City, crucible: &CrucibleType) -> Vec<(Block, usize)> {
+        if is_goal(self.point, grid) {
             return vec![];
         }
 
@@ -48,23 +82,14 @@ impl Block {
             CrucibleType::Small => 1,
             CrucibleType::Ultra => 4,
         };
-        let max_row = grid.len();
-        let max_col = grid[0].len();
-        let transform = |&direction| match direction {
-            Direction::Right => (0, 1),
-            Direction::Left => (0, -1),
-            Direction::Top => (-1, 0),
-            Direction::Bottom => (1, 0),
-        };
-        let next_directions = match self.direction {
-            Direction::Right => [Direction::Right, Direction::Top, Direction::Bottom],
-            Direction::Left => [Direction::Left, Direction::Top, Direction::Bottom],
-            Direction::Top => [Direction::Right, Direction::Left, Direction::Top],
-            Direction::Bottom => [Direction::Right, Direction::Left, Direction::Bottom],
-        };
+        let next_directions = [
+            self.direction,
+            self.direction.turn_left(),
+            self.direction.turn_right(),
+        ];
         let successors: Vec<(Block, usize)> = next_directions
             .iter()
-            .map(|direction| {
+            .filter_map(|direction| {
                 if self.direction.eq(direction) && self.step == max_steps {
                     return None;
                 }
@@ -73,17 +98,7 @@ impl Block {
                     return None;
                 }
 
-                let (row, col) = transform(direction);
-                let next_row = self.row as i32 + row;
-                let next_col = self.col as i32 + col;
-                if next_row == -1
-                    || next_col == -1
-                    || next_row == max_row as i32
-                    || next_col == max_col as i32
-                {
-                    return None;
-                }
-
+                let point = grid.step(self.point, *direction)?;
                 let step = if self.direction.eq(direction) {
                     self.step + 1
                 } else {
@@ -91,80 +106,119 @@ impl Block {
                 };
 
                 let next_block = Block {
-                    row: next_row as usize,
-                    col: next_col as usize,
-                    direction: direction.clone(),
+                    point,
+                    direction: *direction,
                     step,
                 };
 
-                let distance = grid[next_row as usize][next_col as usize] - 48;
-                return Some((next_block, distance as usize));
+                Some((next_block, next_block.loss(grid)))
             })
-            .flatten()
             .collect();
 
-        return successors;
+        successors
     }
 }
 
-type Grid<'a> = Vec<&'a [u8]>;
+fn is_goal(point: Point, grid: &City) -> bool {
+    point.row == grid.height() - 1 && point.col == grid.width() - 1
+}
 
-pub fn min_heat_loss(map: &str) -> usize {
-    min_heat_loss_for_crucible(map, &CrucibleType::Small)
+/// Least heat lost on the way to the bottom right with a crucible
+pub fn min_heat_loss(grid: &City) -> Result<usize, SolveError> {
+    min_heat_loss_for_crucible(grid, &CrucibleType::Small)
 }
 
-pub fn min_heat_loss_ultra(map: &str) -> usize {
-    min_heat_loss_for_crucible(map, &CrucibleType::Ultra)
+/// Least heat lost with an ultra crucible
+pub fn min_heat_loss_ultra(grid: &City) -> Result<usize, SolveError> {
+    min_heat_loss_for_crucible(grid, &CrucibleType::Ultra)
 }
 
-fn min_heat_loss_for_crucible(map: &str, crucible: &CrucibleType) -> usize {
-    let grid: Grid = map.lines().map(|line| line.as_bytes()).collect();
-    let goal = |block: &Block| block.row == grid.len() - 1 && block.col == grid[0].len() - 1;
+fn min_heat_loss_for_crucible(grid: &City, crucible: &CrucibleType) -> Result<usize, SolveError> {
+    best_route(grid, crucible)
+        .map(|(_, loss)| loss)
+        .ok_or(SolveError::NoRoute)
+}
 
-    let right = dijkstra(
-        &Block::new(0, 1, Direction::Right, 1),
-        |block| block.successors(&grid, crucible),
+// Blocks the crucible passes after leaving the top left corner, with the
+// total heat lost on the way
+fn best_route(grid: &City, crucible: &CrucibleType) -> Option<(Vec<Block>, usize)> {
+    let goal = |block: &Block| is_goal(block.point, grid);
+    // every remaining block loses at least 1 heat
+    let heuristic = |block: &Block| {
+        (grid.height() - 1 - block.point.row) + (grid.width() - 1 - block.point.col)
+    };
+
+    let right = astar(
+        Block::new(0, 1, Direction::East, 1),
+        |block| block.successors(grid, crucible),
+        heuristic,
         goal,
     );
 
-    let bottom = dijkstra(
-        &Block::new(1, 0, Direction::Bottom, 1),
-        |block| block.successors(&grid, crucible),
+    let bottom = astar(
+        Block::new(1, 0, Direction::South, 1),
+        |block| block.successors(grid, crucible),
+        heuristic,
         goal,
     );
 
     [right, bottom]
-        .iter()
+        .into_iter()
         .flatten()
-        .inspect(|(route, distance)| {
-            println!("---- Route ----");
-            print_route(route, &grid);
-            println!("---- Distance: {:?} ---", distance + route[0].loss(&grid));
+        .map(|(route, distance)| {
+            let loss = distance + route[0].loss(grid);
+            (route, loss)
         })
-        .map(|(route, distance)| distance + route[0].loss(&grid))
-        .min()
-        .unwrap_or_default()
+        .min_by_key(|(_, loss)| *loss)
 }
 
-fn print_route(route: &Vec<Block>, grid: &Grid) {
-    let mut buff = String::from("");
-    for i in 0..grid.len() {
-        for j in 0..grid[i].len() {
-            let ch =
-                if let Some(block) = route.iter().find(|block| block.row == i && block.col == j) {
-                    match block.direction {
-                        Direction::Right => '>',
-                        Direction::Left => '<',
-                        Direction::Top => '^',
-                        Direction::Bottom => 'v',
-                    }
-                } else {
-                    '.'
-                };
-            buff.push(ch)
+/// City with the best route drawn over it as arrows
+pub fn draw_route(grid: &City, ultra: bool, painter: &Painter) -> Result<String, SolveError> {
+    let crucible = match ultra {
+        true => CrucibleType::Ultra,
+        false => CrucibleType::Small,
+    };
+    let (route, _) = best_route(grid, &crucible).ok_or(SolveError::NoRoute)?;
+
+    Ok(painter.grid(grid, |point, loss| {
+        match route.iter().find(|block| block.point == point) {
+            Some(block) => (block.direction.arrow(), Some(Color::Red)),
+            None => (char::from(b'0' + loss), Some(Color::Dim)),
         }
-        buff.push('\n');
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Span;
+
+    #[test]
+    fn it_parses_city() {
+        let city = parse_city("12\n34").unwrap();
+        assert_eq!(city[Point::new(1, 0)], 3);
+        assert_eq!(min_heat_loss(&city), Ok(2 + 4));
+        assert_eq!(
+            draw_route(&city, false, &Painter::new(false)),
+            Ok("1>\n3v".to_string())
+        );
+        assert_eq!(parse_city("12"), Err(ParseError::TooSmall));
+        assert_eq!(
+            parse_city("12\n3x"),
+            Err(ParseError::Grid(GridError::UnknownTile(Span::new(
+                2, 2, "x"
+            ))))
+        );
     }
 
-    print!("{buff}");
+    #[test]
+    fn it_reports_cities_the_crucible_cant_cross() {
+        // the ultra crucible moves 4 blocks before it can stop
+        let city = parse_city("123\n456\n789").unwrap();
+        assert_eq!(min_heat_loss_ultra(&city), Err(SolveError::NoRoute));
+        assert_eq!(
+            draw_route(&city, true, &Painter::new(false)),
+            Err(SolveError::NoRoute)
+        );
+    }
 }
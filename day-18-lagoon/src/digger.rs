@@ -1,110 +1,305 @@
+use std::str::FromStr;
+
+use aoc_core::error::parse_at;
+use aoc_core::input::lines;
+#[cfg(feature = "render")]
+use aoc_core::render::{Color, Scene, Shape, Style};
+use aoc_core::Span;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<direction> <length> (#<color>)` {0}")]
+    MalformedLine(Span),
+    #[error("unknown direction {0}")]
+    UnknownDirection(Span),
+    #[error("invalid length {0}")]
+    InvalidLength(Span),
+    #[error("expected color `(#<5 hex digits length><direction digit 0-3>)` {0}")]
+    InvalidColor(Span),
+    #[error("length {0} is out of range, lengths go from 0 to {max}", max = u32::MAX)]
+    LengthOutOfRange(i64),
+}
+
+/// Directions the digger moves in
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
     Right,
 }
 
-#[derive(Debug)]
-struct Instruction {
+/// A dig plan step, moving `length` meters in one direction
+#[derive(Debug, PartialEq)]
+pub struct Instruction {
     direction: Direction,
     length: i64,
 }
 
 impl Instruction {
-    fn new(direction: &str, length: &str) -> Self {
-        Self {
+    /// Moving `length` meters, which fits in a `u32` like the lengths of a plan
+    pub fn new(direction: Direction, length: i64) -> Result<Self, ParseError> {
+        if u32::try_from(length).is_err() {
+            return Err(ParseError::LengthOutOfRange(length));
+        }
+        Ok(Self { direction, length })
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn length(&self) -> i64 {
+        self.length
+    }
+
+    fn parse(index: usize, line: &str, direction: &str, length: &str) -> Result<Self, ParseError> {
+        Ok(Self {
             direction: match direction {
                 "D" => Direction::Down,
                 "U" => Direction::Up,
                 "L" => Direction::Left,
                 "R" => Direction::Right,
-                _ => panic!("Unknown direction {}", direction),
+                _ => {
+                    return Err(ParseError::UnknownDirection(Span::at(
+                        index, line, direction,
+                    )))
+                }
             },
-            length: length.parse().expect("Length should be digit"),
-        }
+            length: parse_at::<u32>(index, line, length).map_err(ParseError::InvalidLength)? as i64,
+        })
+    }
+
+    fn from_color(index: usize, line: &str, color: &str) -> Result<Self, ParseError> {
+        let invalid = || ParseError::InvalidColor(Span::at(index, line, color));
+        let hex_color = color
+            .strip_prefix("(#")
+            .and_then(|color| color.strip_suffix(")"))
+            .filter(|hex_color| hex_color.len() == 6 && hex_color.is_ascii())
+            .ok_or_else(invalid)?;
+        let (length, dir) = hex_color.split_at(5);
+
+        Ok(Self {
+            direction: match dir {
+                "0" => Direction::Right,
+                "1" => Direction::Down,
+                "2" => Direction::Left,
+                "3" => Direction::Up,
+                _ => return Err(invalid()),
+            },
+            length: i64::from_str_radix(length, 16).map_err(|_| invalid())?,
+        })
     }
 }
 
 type DigPlan = Vec<Instruction>;
 
-pub fn volume(input: &str) -> usize {
-    shoelace_polygon(&parse_instructions(input))
+/// Both dig plans of an input
+#[derive(Debug, PartialEq)]
+pub struct DigPlans {
+    plan: DigPlan,
+    hex_plan: DigPlan,
 }
 
-pub fn volume_hex(input: &str) -> usize {
-    shoelace_polygon(&parse_instructions_hex(input))
+impl DigPlans {
+    /// Plans of instructions that are each already checked
+    pub fn new(plan: Vec<Instruction>, hex_plan: Vec<Instruction>) -> Self {
+        DigPlans { plan, hex_plan }
+    }
+
+    /// Instructions as given by their direction and length
+    pub fn plan(&self) -> &[Instruction] {
+        &self.plan
+    }
+
+    /// Instructions hidden in the colours
+    pub fn hex_plan(&self) -> &[Instruction] {
+        &self.hex_plan
+    }
 }
 
-fn parse_instructions(input: &str) -> DigPlan {
-    input
-        .lines()
-        .map(|line| {
-            let [direction, length, _] = line.splitn(3, ' ').collect::<Vec<&str>>()[..] else {
-                panic!("Can't parse {}", line);
-            };
-            return Instruction::new(direction, length);
-        })
-        .collect()
+/// Cubic meters the lagoon of the plan holds
+pub fn volume(plans: &DigPlans) -> usize {
+    shoelace_polygon(&plans.plan)
 }
 
-fn parse_instructions_hex(input: &str) -> DigPlan {
-    input
-        .lines()
-        .map(|line| {
-            let [_direction, _length, color] = line.splitn(3, ' ').collect::<Vec<&str>>()[..] else {
-                panic!("Can't parse {}", line);
-            };
-            let hex_color = &color[2..8];
-            let (length, dir) = hex_color.split_at(5);
-            return Instruction {
-                direction: match dir {
-                    "0" => Direction::Right,
-                    "1" => Direction::Down,
-                    "2" => Direction::Left,
-                    "3" => Direction::Up,
-                    _ => panic!("Can't parse {}", dir),
-                },
-                length: i64::from_str_radix(length, 16).unwrap(),
-            };
-        })
+/// Cubic meters the lagoon of the plan hidden in the colours holds
+pub fn volume_hex(plans: &DigPlans) -> usize {
+    shoelace_polygon(&plans.hex_plan)
+}
+
+impl FromStr for DigPlans {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_plans(input)
+    }
+}
+
+/// Reads the `<direction> <length> (#<colour>)` lines
+pub fn parse_plans(input: &str) -> Result<DigPlans, ParseError> {
+    let mut plan = vec![];
+    let mut hex_plan = vec![];
+    for (index, line) in lines(input).enumerate() {
+        let (instruction, hex_instruction) = parse_line(index, line)?;
+        plan.push(instruction);
+        hex_plan.push(hex_instruction);
+    }
+
+    Ok(DigPlans { plan, hex_plan })
+}
+
+/// Instruction of the plain and of the hex plan
+pub fn parse_line(index: usize, line: &str) -> Result<(Instruction, Instruction), ParseError> {
+    let [direction, length, color] = line.splitn(3, ' ').collect::<Vec<&str>>()[..] else {
+        return Err(ParseError::MalformedLine(Span::line(index, line)));
+    };
+
+    Ok((
+        Instruction::parse(index, line, direction, length)?,
+        Instruction::from_color(index, line, color)?,
+    ))
+}
+
+/// Digs the trench one instruction at a time, adding up its area with the
+/// <https://en.wikipedia.org/wiki/Shoelace_formula> as it goes
+#[derive(Debug, Default)]
+pub struct Digger {
+    position: (i64, i64),
+    sum: i64,
+    perimeter: i64,
+}
+
+impl Digger {
+    /// Returns the corner the instruction ends on
+    pub fn dig(&mut self, item: &Instruction) -> (i64, i64) {
+        let (x, y) = self.position;
+        let next = match item.direction {
+            Direction::Up => (x, y - item.length),
+            Direction::Down => (x, y + item.length),
+            Direction::Left => (x - item.length, y),
+            Direction::Right => (x + item.length, y),
+        };
+        self.sum += (y + next.1) * (x - next.0);
+        self.perimeter += item.length;
+        self.position = next;
+
+        next
+    }
+
+    pub fn volume(&self) -> usize {
+        let area = (self.sum / 2).abs();
+        (area + self.perimeter / 2 + 1) as usize
+    }
+}
+
+// Corners of the trench, starting and ending at the origin
+#[cfg(any(feature = "render", test))]
+fn trench(plan: &DigPlan) -> Vec<(i64, i64)> {
+    let mut digger = Digger::default();
+    std::iter::once((0, 0))
+        .chain(plan.iter().map(|item| digger.dig(item)))
         .collect()
 }
 
-// https://en.wikipedia.org/wiki/Shoelace_formula
 fn shoelace_polygon(plan: &DigPlan) -> usize {
-    let (mut x, mut y) = (0, 0);
-    let mut points = vec![];
-    points.push((0, 0));
-    let mut perimeter = 0;
+    let mut digger = Digger::default();
     for item in plan {
-        perimeter += item.length;
-        match item.direction {
-            Direction::Up => {
-                points.push((x, y - item.length));
-                y -= item.length;
-            }
-            Direction::Down => {
-                points.push((x, y + item.length));
-                y += item.length;
-            }
-            Direction::Left => {
-                points.push((x - item.length, y));
-                x -= item.length;
-            }
-            Direction::Right => {
-                points.push((x + item.length, y));
-                x += item.length;
-            }
-        }
+        digger.dig(item);
     }
-    let mut sum = 0;
-    for i in 0..points.len() - 2 {
-        let (x1, y1) = points[i];
-        let (x2, y2) = points[i + 1];
-        sum += (y1 + y2) * (x1 - x2);
+
+    digger.volume()
+}
+
+/// Outline of the dug lagoon, from the plain or the hex plan
+#[cfg(feature = "render")]
+pub fn lagoon_scene(plans: &DigPlans, hex: bool) -> Scene {
+    let plan = match hex {
+        true => &plans.hex_plan,
+        false => &plans.plan,
+    };
+    let points = trench(plan)
+        .into_iter()
+        .map(|(x, y)| (x as f64, y as f64))
+        .collect();
+
+    let mut scene = Scene::new(Color::WHITE);
+    scene.add(
+        Shape::Polygon(points),
+        Style::fill(Color(64, 120, 200)).with_stroke(Color(20, 40, 80)),
+    );
+    scene
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_digs_square() {
+        let plans =
+            parse_plans("R 2 (#000020)\nD 2 (#000021)\nL 2 (#000022)\nU 2 (#000023)").unwrap();
+        assert_eq!(volume(&plans), 9);
+        assert_eq!(volume_hex(&plans), 9);
+        assert_eq!(
+            trench(&plans.plan),
+            [(0, 0), (2, 0), (2, 2), (0, 2), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn it_builds_plans_in_code() {
+        let square = |length| {
+            [
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+                Direction::Up,
+            ]
+            .into_iter()
+            .map(|direction| Instruction::new(direction, length).unwrap())
+            .collect()
+        };
+        let plans = DigPlans::new(square(2), square(4));
+        assert_eq!(volume(&plans), 9);
+        assert_eq!(volume_hex(&plans), 25);
+        assert_eq!(
+            DigPlans::new(square(2), square(2)),
+            parse_plans("R 2 (#000020)\nD 2 (#000021)\nL 2 (#000022)\nU 2 (#000023)").unwrap()
+        );
+
+        assert_eq!(
+            Instruction::new(Direction::Up, -1),
+            Err(ParseError::LengthOutOfRange(-1))
+        );
+        assert_eq!(
+            Instruction::new(Direction::Up, 1 << 32),
+            Err(ParseError::LengthOutOfRange(1 << 32))
+        );
+    }
+
+    #[test]
+    fn it_reports_malformed_plan() {
+        assert_eq!(
+            parse_plans("R 6 (#70c710)\nX 5 (#0dc571)"),
+            Err(ParseError::UnknownDirection(Span::new(2, 1, "X")))
+        );
+        assert_eq!(
+            parse_plans("R six (#70c710)"),
+            Err(ParseError::InvalidLength(Span::new(1, 3, "six")))
+        );
+        assert_eq!(
+            parse_plans("R 6 (#70c7)"),
+            Err(ParseError::InvalidColor(Span::new(1, 5, "(#70c7)")))
+        );
+        assert_eq!(
+            parse_plans("R 6 (#70cɅ0)"),
+            Err(ParseError::InvalidColor(Span::new(1, 5, "(#70cɅ0)")))
+        );
+        assert_eq!(
+            parse_plans("R 6"),
+            Err(ParseError::MalformedLine(Span::new(1, 1, "R 6")))
+        );
     }
-    let area = (sum / 2).abs();
-    return (area + perimeter / 2 + 1).try_into().unwrap();
 }
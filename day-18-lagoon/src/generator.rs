@@ -0,0 +1,62 @@
+use aoc_core::generate::{closed_loop, InputRng, Rng};
+
+// Lengths of the plan stay within the five hex digits of a color
+const MAX_HEX_LENGTH: usize = 0xfffff;
+
+// Position of every coordinate after stretching the gaps between
+// neighbouring ones to random widths up to `widest`
+fn stretch(rng: &mut InputRng, size: usize, widest: usize) -> Vec<usize> {
+    let mut position = 0;
+    (0..size)
+        .map(|_| {
+            position += rng.gen_range(1..=widest);
+            position
+        })
+        .collect()
+}
+
+fn direction(from: (usize, usize), to: (usize, usize)) -> usize {
+    match (to.0.cmp(&from.0), to.1.cmp(&from.1)) {
+        (_, std::cmp::Ordering::Greater) => 0,
+        (std::cmp::Ordering::Greater, _) => 1,
+        (_, std::cmp::Ordering::Less) => 2,
+        _ => 3,
+    }
+}
+
+// Both plans trace the same random loop within a `size` by `size` square,
+// stretched differently: gaps up to nine meters for the plan and as wide
+// as the hex lengths allow for the colors
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(3);
+    let path = closed_loop(rng, size);
+    let corners: Vec<(usize, usize)> = (0..path.len())
+        .filter(|index| {
+            let previous = path[(index + path.len() - 1) % path.len()];
+            let next = path[(index + 1) % path.len()];
+            previous.0 != next.0 && previous.1 != next.1
+        })
+        .map(|index| path[index])
+        .collect();
+
+    let widest = MAX_HEX_LENGTH / size;
+    let (rows, cols) = (stretch(rng, size, 9), stretch(rng, size, 9));
+    let (hex_rows, hex_cols) = (stretch(rng, size, widest), stretch(rng, size, widest));
+
+    (0..corners.len())
+        .map(|index| {
+            let (from, to) = (corners[index], corners[(index + 1) % corners.len()]);
+            let direction = direction(from, to);
+            let length = |rows: &[usize], cols: &[usize]| {
+                rows[from.0].abs_diff(rows[to.0]) + cols[from.1].abs_diff(cols[to.1])
+            };
+            format!(
+                "{} {} (#{:05x}{direction})",
+                ["R", "D", "L", "U"][direction],
+                length(&rows, &cols),
+                length(&hex_rows, &hex_cols)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,64 @@
+//! Day 18: Lavaduct Lagoon
+//!
+//! [`digger::parse_plans`] reads the [`digger::DigPlans`], a [`digger::Digger`] follows
+//! their [`digger::Instruction`]s and [`digger::volume`] measures the lagoon.
+
+use std::io::BufRead;
+
+use aoc_core::generate::InputRng;
+use aoc_core::input::read_records;
+#[cfg(feature = "render")]
+use aoc_core::render::Scene;
+use aoc_core::{Answer, Error, Part, Puzzle, Stopwatch, Streamed};
+
+use crate::digger::Digger;
+
+pub mod digger;
+mod generator;
+
+pub struct Lagoon;
+
+impl Puzzle for Lagoon {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "lagoon";
+
+    type Input = digger::DigPlans;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(plans: &Self::Input) -> Result<Answer, Error> {
+        Ok(digger::volume(plans).into())
+    }
+
+    fn part_two(plans: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(digger::volume_hex(plans).into()))
+    }
+
+    fn stream(
+        input: &mut dyn BufRead,
+        stopwatch: &mut Stopwatch,
+    ) -> Option<Result<Streamed, Error>> {
+        let (mut plain, mut hex) = (Digger::default(), Digger::default());
+        let read = read_records(input, b'\n', |index, line| {
+            let (instruction, hex_instruction) = digger::parse_line(index, line)?;
+            stopwatch.time(Part::One, || plain.dig(&instruction));
+            stopwatch.time(Part::Two, || hex.dig(&hex_instruction));
+            Ok(())
+        });
+        Some(read.map(|()| Streamed {
+            part_one: stopwatch.time(Part::One, || Ok(plain.volume().into())),
+            part_two: stopwatch.time(Part::Two, || Ok(hex.volume().into())),
+        }))
+    }
+
+    #[cfg(feature = "render")]
+    fn render(plans: &Self::Input, part: Part) -> Option<Result<Scene, Error>> {
+        Some(Ok(digger::lagoon_scene(plans, part == Part::Two)))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,17 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-mod digger;
+use day_18_lagoon::Lagoon;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum: usize = digger::volume(&contents);
-    println!("Sum:\n{sum}");
-    let sum_hex: usize = digger::volume_hex(&contents);
-    println!("Sum hex:\n{sum_hex}");
+fn main() -> ExitCode {
+    aoc_core::run(&Lagoon)
 }
@@ -0,0 +1,81 @@
+use std::collections::{HashSet, VecDeque};
+
+use aoc_core::generate::{word, InputRng, Rng, SliceRandom};
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+const MAX_RULES: usize = 3;
+
+// Inclusive ratings that can still reach a workflow, by category
+type Ranges = [(usize, usize); 4];
+
+// Where a rule or fallback sends the parts: a new workflow while fewer than
+// `size` exist, otherwise accepted or rejected
+fn target(
+    rng: &mut InputRng,
+    ranges: Ranges,
+    size: usize,
+    taken: &mut HashSet<String>,
+    queue: &mut VecDeque<(String, Ranges)>,
+) -> String {
+    if taken.len() < size && rng.gen_bool(0.7) {
+        loop {
+            let length = rng.gen_range(2..=3);
+            let name = word(rng, length);
+            if taken.insert(name.clone()) {
+                queue.push_back((name.clone(), ranges));
+                return name;
+            }
+        }
+    }
+    ["A", "R"].choose(rng).unwrap().to_string()
+}
+
+// A tree of `size` workflows from `in` followed by `size` parts. Every rule
+// splits the ratings that reach it into two non-empty ranges
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    let mut taken = HashSet::from(["in".to_string()]);
+    let mut queue = VecDeque::from([("in".to_string(), [(1, 4000); 4])]);
+    let mut workflows = vec![];
+    while let Some((name, mut ranges)) = queue.pop_front() {
+        let mut rules = vec![];
+        for _ in 0..rng.gen_range(1..=MAX_RULES) {
+            let splittable: Vec<usize> = (0..4)
+                .filter(|category| ranges[*category].0 < ranges[*category].1)
+                .collect();
+            let Some(&category) = splittable.choose(rng) else {
+                break;
+            };
+            let (low, high) = ranges[category];
+            let mut matched = ranges;
+            let rule = match rng.gen_bool(0.5) {
+                true => {
+                    let value = rng.gen_range(low + 1..=high);
+                    matched[category] = (low, value - 1);
+                    ranges[category] = (value, high);
+                    format!("{}<{value}", CATEGORIES[category])
+                }
+                false => {
+                    let value = rng.gen_range(low..high);
+                    matched[category] = (value + 1, high);
+                    ranges[category] = (low, value);
+                    format!("{}>{value}", CATEGORIES[category])
+                }
+            };
+            let next = target(rng, matched, size, &mut taken, &mut queue);
+            rules.push(format!("{rule}:{next}"));
+        }
+        rules.push(target(rng, ranges, size, &mut taken, &mut queue));
+        workflows.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+    workflows.shuffle(rng);
+
+    let parts: Vec<String> = (0..size)
+        .map(|_| {
+            let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .collect();
+
+    format!("{}\n\n{}", workflows.join("\n"), parts.join("\n"))
+}
@@ -0,0 +1,36 @@
+//! Day 19: Aplenty
+//!
+//! [`processor::parse_system`] reads the [`processor::System`] of
+//! [`processor::Flow`]s and parts, [`processor::sum_parts`] and [`processor::sum_ranges`]
+//! sort with it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+mod generator;
+pub mod processor;
+
+pub struct Rules;
+
+impl Puzzle for Rules {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "rules";
+
+    type Input = processor::System;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(system: &Self::Input) -> Result<Answer, Error> {
+        Ok(processor::sum_parts(system).into())
+    }
+
+    fn part_two(system: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(processor::sum_ranges(system).into()))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
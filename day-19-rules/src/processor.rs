@@ -1,13 +1,41 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use aoc_core::error::parse_at;
+use aoc_core::input::lines;
+use aoc_core::input::paragraphs;
+use aoc_core::Span;
+use thiserror::Error;
 
-lazy_static! {
-    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected workflows and parts separated by an empty line")]
+    MissingParts,
+    #[error("expected `<name>{{<rules>,<fallback>}}` {0}")]
+    MalformedWorkflow(Span),
+    #[error("expected `<category><comparator><value>:<workflow>` {0}")]
+    MalformedRule(Span),
+    #[error("unknown category, expected one of `x`, `m`, `a`, `s` {0}")]
+    UnknownCategory(Span),
+    #[error("unknown comparator, expected `<` or `>` {0}")]
+    UnknownComparator(Span),
+    #[error("invalid rating {0}")]
+    InvalidRating(Span),
+    #[error("expected `{{x=<rating>,m=<rating>,a=<rating>,s=<rating>}}` {0}")]
+    MalformedPart(Span),
+    #[error("rating of `{0}` given twice {1}")]
+    RepeatedRating(char, Span),
+    #[error("part has no rating of `{0}` {1}")]
+    MissingRating(char, Span),
+    #[error("workflow `{workflow}` sends parts to `{target}`, which is neither a workflow nor `A` or `R`")]
+    UnknownWorkflow { workflow: String, target: String },
+    #[error("workflow `{0}` can send parts back to itself")]
+    Cycle(String),
+    #[error("no `in` workflow to start sorting parts with")]
+    MissingStart,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 struct Rule {
     prop: u8,
     comparator: u8,
@@ -15,22 +43,24 @@ struct Rule {
     terminal: String,
 }
 
-#[derive(Debug)]
-struct Flow {
+/// A workflow, sending a part to the first rule it matches or its fallback
+#[derive(Debug, PartialEq)]
+pub struct Flow {
     rules: Vec<Rule>,
     terminal: String,
 }
 
-#[derive(Clone, Debug)]
-struct Part {
-    x: usize,
-    m: usize,
-    a: usize,
-    s: usize,
+/// A part and its ratings
+#[derive(Clone, Debug, PartialEq)]
+pub struct Part {
+    pub x: usize,
+    pub m: usize,
+    pub a: usize,
+    pub s: usize,
 }
 
 impl Part {
-    fn total(&self) -> usize {
+    pub fn total(&self) -> usize {
         self.x + self.m + self.a + self.s
     }
 }
@@ -65,63 +95,250 @@ impl PartLimitations {
     }
 }
 
-pub fn sum_parts(contents: &str) -> usize {
-    let (flows_str, parts_str) = contents.split_once("\n\n").unwrap();
-    let flows = parse_flows(flows_str);
+/// The workflows and the parts to sort with them
+#[derive(Debug, PartialEq)]
+pub struct System {
+    flows: HashMap<String, Flow>,
+    parts: Vec<Part>,
+}
+
+impl Flow {
+    /// Workflow of rules such as `a<2006:qkq`, sending the parts no rule
+    /// matches to `fallback`
+    pub fn new(rules: &[&str], fallback: &str) -> Result<Flow, ParseError> {
+        Ok(Flow {
+            rules: rules
+                .iter()
+                .map(|rule| parse_rule(0, rule, rule))
+                .collect::<Result<_, _>>()?,
+            terminal: fallback.to_string(),
+        })
+    }
+
+    /// The workflow, or `A` or `R`, the part goes to next
+    pub fn next(&self, part: &Part) -> &str {
+        process_flow(part, self)
+    }
+
+    /// The workflow the part goes to when no rule matches
+    pub fn fallback(&self) -> &str {
+        &self.terminal
+    }
+
+    // Workflows, or `A` or `R`, the rules then the fallback send parts to
+    fn targets(&self) -> impl Iterator<Item = &str> {
+        self.rules
+            .iter()
+            .map(|rule| rule.terminal.as_str())
+            .chain([self.terminal.as_str()])
+    }
+}
 
-    parse_parts(parts_str)
+impl System {
+    /// Workflows by their name and the parts to sort with them
+    pub fn new<'a>(
+        flows: impl IntoIterator<Item = (&'a str, Flow)>,
+        parts: Vec<Part>,
+    ) -> Result<System, ParseError> {
+        let flows = flows
+            .into_iter()
+            .map(|(name, flow)| (name.to_string(), flow))
+            .collect();
+        check_flows(&flows)?;
+        Ok(System { flows, parts })
+    }
+
+    pub fn flow(&self, name: &str) -> Option<&Flow> {
+        self.flows.get(name)
+    }
+
+    pub fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    /// Whether the part ends up accepted starting from the `in` workflow
+    pub fn accepts(&self, part: &Part) -> bool {
+        process_part(part, &self.flows).is_some()
+    }
+}
+
+/// Sum of the ratings of the accepted parts
+pub fn sum_parts(system: &System) -> usize {
+    system
+        .parts
         .iter()
-        .map(|part| process_part(part, &flows).and_then(|part| Some(part.total())))
-        .flatten()
+        .filter_map(|part| process_part(part, &system.flows).map(|part| part.total()))
         .sum()
 }
 
-pub fn sum_ranges(contents: &str) -> u128 {
-    let (flows_str, _) = contents.split_once("\n\n").unwrap();
-    return all_ranges(&parse_flows(flows_str))
+/// Combinations of ratings from 1 to 4000 that are accepted
+pub fn sum_ranges(system: &System) -> u128 {
+    all_ranges(&system.flows)
         .iter()
         .map(|item| item.total())
-        .sum();
+        .sum()
 }
 
-fn parse_flows(list: &str) -> HashMap<String, Flow> {
-    list.lines()
-        .map(|line| {
-            let (name, rest) = line.split_once("{").unwrap();
-            let [rules @ .., termial] =
-                &rest.split(",").collect::<Vec<&str>>()[..] else { panic!("Can't parse rules") };
-            return (
+impl FromStr for System {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_system(input)
+    }
+}
+
+/// Reads the workflows and the parts separated by a blank line
+pub fn parse_system(contents: &str) -> Result<System, ParseError> {
+    let [(_, flows_str), (first_part_line, parts_str)] = paragraphs(contents)[..] else {
+        return Err(ParseError::MissingParts);
+    };
+
+    let flows = parse_flows(flows_str)?;
+    check_flows(&flows)?;
+    Ok(System {
+        flows,
+        parts: parse_parts(first_part_line, parts_str)?,
+    })
+}
+
+// Every part starts in `in` and has to end up accepted or rejected, so
+// workflows can only send parts to `A`, `R` or other workflows, and never
+// back to themselves
+fn check_flows(flows: &HashMap<String, Flow>) -> Result<(), ParseError> {
+    if !flows.contains_key("in") {
+        return Err(ParseError::MissingStart);
+    }
+    let mut names: Vec<&str> = flows.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    for &name in &names {
+        if let Some(target) = flows[name]
+            .targets()
+            .find(|&target| target != "A" && target != "R" && !flows.contains_key(target))
+        {
+            return Err(ParseError::UnknownWorkflow {
+                workflow: name.to_string(),
+                target: target.to_string(),
+            });
+        }
+    }
+
+    let mut visiting = HashSet::new();
+    let mut done = HashSet::new();
+    for name in names {
+        visit_flow(name, flows, &mut visiting, &mut done)?;
+    }
+    Ok(())
+}
+
+// Depth first walk from `name`, failing on a workflow already on the path
+fn visit_flow<'a>(
+    name: &'a str,
+    flows: &'a HashMap<String, Flow>,
+    visiting: &mut HashSet<&'a str>,
+    done: &mut HashSet<&'a str>,
+) -> Result<(), ParseError> {
+    let Some(flow) = flows.get(name) else {
+        return Ok(());
+    };
+    if done.contains(name) {
+        return Ok(());
+    }
+    if !visiting.insert(name) {
+        return Err(ParseError::Cycle(name.to_string()));
+    }
+    for target in flow.targets() {
+        visit_flow(target, flows, visiting, done)?;
+    }
+    visiting.remove(name);
+    done.insert(name);
+    Ok(())
+}
+
+fn parse_flows(list: &str) -> Result<HashMap<String, Flow>, ParseError> {
+    lines(list)
+        .enumerate()
+        .map(|(index, line)| {
+            let malformed = || ParseError::MalformedWorkflow(Span::line(index, line));
+            let (name, rest) = line.split_once("{").ok_or_else(malformed)?;
+            let rest = rest.strip_suffix("}").ok_or_else(malformed)?;
+            let [rules @ .., termial] = &rest.split(",").collect::<Vec<&str>>()[..] else {
+                return Err(malformed());
+            };
+            Ok((
                 name.to_string(),
                 Flow {
                     rules: rules
                         .iter()
-                        .map(|rule| {
-                            let (start, terminal) = rule.split_once(":").unwrap();
-                            return Rule {
-                                prop: start.as_bytes()[0],
-                                comparator: start.as_bytes()[1],
-                                value: start[2..].parse().expect("Should parse num from rule"),
-                                terminal: terminal.to_string(),
-                            };
-                        })
-                        .collect(),
-                    terminal: termial[0..termial.len() - 1].to_string(),
+                        .map(|rule| parse_rule(index, line, rule))
+                        .collect::<Result<_, _>>()?,
+                    terminal: termial.to_string(),
                 },
-            );
+            ))
         })
         .collect()
 }
 
-fn parse_parts(list: &str) -> Vec<Part> {
-    list.lines()
-        .map(|line| {
-            let [x, m, a, s] = NUM_RE
-                .find_iter(line)
-                .map(|m| m.as_str().parse().unwrap())
-                .collect::<Vec<usize>>()[..] else {
-                    panic!("Can't parse line")
+fn parse_rule(index: usize, line: &str, rule: &str) -> Result<Rule, ParseError> {
+    let (start, terminal) = rule
+        .split_once(":")
+        .ok_or_else(|| ParseError::MalformedRule(Span::at(index, line, rule)))?;
+    if start.len() < 3 || !start.is_char_boundary(1) || !start.is_char_boundary(2) {
+        return Err(ParseError::MalformedRule(Span::at(index, line, rule)));
+    }
+    let (prop, rest) = start.split_at(1);
+    let (comparator, value) = rest.split_at(1);
+    if !"xmas".contains(prop) {
+        return Err(ParseError::UnknownCategory(Span::at(index, line, prop)));
+    }
+    if !"<>".contains(comparator) {
+        return Err(ParseError::UnknownComparator(Span::at(
+            index, line, comparator,
+        )));
+    }
+
+    Ok(Rule {
+        prop: prop.as_bytes()[0],
+        comparator: comparator.as_bytes()[0],
+        value: parse_at(index, line, value).map_err(ParseError::InvalidRating)?,
+        terminal: terminal.to_string(),
+    })
+}
+
+fn parse_parts(first_line: usize, list: &str) -> Result<Vec<Part>, ParseError> {
+    lines(list)
+        .enumerate()
+        .map(|(index, line)| {
+            let index = first_line + index;
+            let ratings = line
+                .strip_prefix('{')
+                .and_then(|rest| rest.strip_suffix('}'))
+                .ok_or_else(|| ParseError::MalformedPart(Span::line(index, line)))?;
+            let mut values = [None; 4];
+            for rating in ratings.split(',') {
+                let (category, value) = rating
+                    .split_once('=')
+                    .ok_or_else(|| ParseError::MalformedPart(Span::at(index, line, rating)))?;
+                let slot = match category {
+                    "x" | "m" | "a" | "s" => &mut values["xmas".find(category).unwrap()],
+                    _ => return Err(ParseError::UnknownCategory(Span::at(index, line, category))),
                 };
-            return Part { x, m, a, s };
+                let value = parse_at(index, line, value).map_err(ParseError::InvalidRating)?;
+                if slot.replace(value).is_some() {
+                    let category = category.chars().next().unwrap();
+                    return Err(ParseError::RepeatedRating(
+                        category,
+                        Span::at(index, line, rating),
+                    ));
+                }
+            }
+            let missing = |category| ParseError::MissingRating(category, Span::line(index, line));
+            let [x, m, a, s] = values;
+            Ok(Part {
+                x: x.ok_or_else(|| missing('x'))?,
+                m: m.ok_or_else(|| missing('m'))?,
+                a: a.ok_or_else(|| missing('a'))?,
+                s: s.ok_or_else(|| missing('s'))?,
+            })
         })
         .collect()
 }
@@ -137,15 +354,15 @@ fn process_part<'a>(part: &'a Part, flows: &'a HashMap<String, Flow>) -> Option<
     }
 }
 
-fn process_flow<'a>(part: &'a Part, flow: &'a Flow) -> &'a String {
-    let mut stack = flow.rules.iter();
-    while let Some(rule) = stack.next() {
+fn process_flow<'a>(part: &Part, flow: &'a Flow) -> &'a String {
+    let stack = flow.rules.iter();
+    for rule in stack {
         let value = match rule.prop {
             b'x' => part.x,
             b'm' => part.m,
             b'a' => part.a,
             b's' => part.s,
-            _ => panic!("Missing prop {}", rule.prop.to_string()),
+            _ => panic!("Missing prop {}", rule.prop),
         };
         let terminal = match rule.comparator {
             b'<' => {
@@ -162,7 +379,7 @@ fn process_flow<'a>(part: &'a Part, flow: &'a Flow) -> &'a String {
                     None
                 }
             }
-            _ => panic!("Unknown comparator {}", rule.comparator.to_string()),
+            _ => panic!("Unknown comparator {}", rule.comparator),
         };
         if let Some(next) = terminal {
             return next;
@@ -186,7 +403,7 @@ fn all_ranges(flows: &HashMap<String, Flow>) -> Vec<PartLimitations> {
         }
     }
 
-    return res;
+    res
 }
 
 fn process_flow_rule(limitation: &PartLimitations, flow: &Flow) -> Vec<(String, PartLimitations)> {
@@ -220,11 +437,11 @@ fn process_flow_rule(limitation: &PartLimitations, flow: &Flow) -> Vec<(String,
         next => res.push((next.to_string(), prev_limitations)),
     }
 
-    return res;
+    res
 }
 
 fn apply_rule(limitations: &mut PartLimitations, rule: &Rule) {
-    let mut limit = match rule.prop {
+    let limit = match rule.prop {
         b'x' => &mut limitations.x,
         b'm' => &mut limitations.m,
         b'a' => &mut limitations.a,
@@ -239,7 +456,7 @@ fn apply_rule(limitations: &mut PartLimitations, rule: &Rule) {
 }
 
 fn apply_terminating_rule(limitations: &mut PartLimitations, rule: &Rule) {
-    let mut limit = match rule.prop {
+    let limit = match rule.prop {
         b'x' => &mut limitations.x,
         b'm' => &mut limitations.m,
         b'a' => &mut limitations.a,
@@ -247,8 +464,178 @@ fn apply_terminating_rule(limitations: &mut PartLimitations, rule: &Rule) {
         _ => panic!("Unknown prop"),
     };
     match rule.comparator {
-        b'<' => limit.1 = std::cmp::min(limit.1, rule.value - 1),
+        b'<' => limit.1 = std::cmp::min(limit.1, rule.value.saturating_sub(1)),
         b'>' => limit.0 = std::cmp::max(limit.0, rule.value + 1),
         _ => panic!("Unknown comparator"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sorts_parts() {
+        let system = parse_system(
+            "in{x<5:A,m>10:R,px}\npx{a>3:A,R}\n\n{x=1,m=2,a=3,s=4}\n{x=6,m=1,a=4,s=1}",
+        )
+        .unwrap();
+        assert_eq!(sum_parts(&system), 22);
+    }
+
+    #[test]
+    fn it_reports_malformed_system() {
+        assert_eq!(parse_system("in{x<5:A,R}"), Err(ParseError::MissingParts));
+        assert_eq!(
+            parse_system("in{x<5:A,R\n\n{x=1,m=2,a=3,s=4}"),
+            Err(ParseError::MalformedWorkflow(Span::new(1, 1, "in{x<5:A,R")))
+        );
+        assert_eq!(
+            parse_system("in{x<5:A,R}\npx{q>5:A,R}\n\n{x=1,m=2,a=3,s=4}"),
+            Err(ParseError::UnknownCategory(Span::new(2, 4, "q")))
+        );
+        assert_eq!(
+            parse_system("in{x=5:A,R}\n\n{x=1,m=2,a=3,s=4}"),
+            Err(ParseError::UnknownComparator(Span::new(1, 5, "=")))
+        );
+        assert_eq!(
+            parse_system("in{x<5:A,R}\n\n{x=1,m=2,a=3,s=4}\n{x=1,m=2,a=3}"),
+            Err(ParseError::MissingRating(
+                's',
+                Span::new(4, 1, "{x=1,m=2,a=3}")
+            ))
+        );
+    }
+
+    #[test]
+    fn it_reads_ratings_by_category() {
+        let system = parse_system("in{x>5:A,R}\n\n{s=1,a=2,m=3,x=9}").unwrap();
+        assert_eq!(
+            system.parts(),
+            [Part {
+                x: 9,
+                m: 3,
+                a: 2,
+                s: 1
+            }]
+        );
+        assert_eq!(sum_parts(&system), 15);
+
+        assert_eq!(
+            parse_system("in{A}\n\n{q=1,w=2,e=3,r=4}"),
+            Err(ParseError::UnknownCategory(Span::new(3, 2, "q")))
+        );
+        assert_eq!(
+            parse_system("in{A}\n\n{x=1,m=2,x=3,s=4}"),
+            Err(ParseError::RepeatedRating('x', Span::new(3, 10, "x=3")))
+        );
+        assert_eq!(
+            parse_system("in{A}\n\n{x=1,m=2,a=3,s=four}"),
+            Err(ParseError::InvalidRating(Span::new(3, 16, "four")))
+        );
+        assert_eq!(
+            parse_system("in{A}\n\n{x=1,m=2,a=3,s}"),
+            Err(ParseError::MalformedPart(Span::new(3, 14, "s")))
+        );
+    }
+
+    #[test]
+    fn it_builds_systems_in_code() {
+        let system = System::new(
+            [
+                ("in", Flow::new(&["x<5:A", "m>10:R"], "px").unwrap()),
+                ("px", Flow::new(&["a>3:A"], "R").unwrap()),
+            ],
+            vec![
+                Part {
+                    x: 1,
+                    m: 2,
+                    a: 3,
+                    s: 4,
+                },
+                Part {
+                    x: 6,
+                    m: 1,
+                    a: 4,
+                    s: 1,
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            system,
+            parse_system(
+                "in{x<5:A,m>10:R,px}\npx{a>3:A,R}\n\n{x=1,m=2,a=3,s=4}\n{x=6,m=1,a=4,s=1}"
+            )
+            .unwrap()
+        );
+        assert_eq!(sum_parts(&system), 22);
+
+        assert_eq!(
+            Flow::new(&["x<5:A", "q>5:R"], "A"),
+            Err(ParseError::UnknownCategory(Span::new(1, 1, "q")))
+        );
+        assert_eq!(
+            Flow::new(&["x<five:A"], "A"),
+            Err(ParseError::InvalidRating(Span::new(1, 3, "five")))
+        );
+        assert_eq!(
+            System::new([("in", Flow::new(&[], "px").unwrap())], vec![]),
+            Err(ParseError::UnknownWorkflow {
+                workflow: "in".to_string(),
+                target: "px".to_string()
+            })
+        );
+        assert_eq!(
+            System::new(
+                [
+                    ("in", Flow::new(&["s>9:px"], "A").unwrap()),
+                    ("px", Flow::new(&[], "in").unwrap())
+                ],
+                vec![]
+            ),
+            Err(ParseError::Cycle("in".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_reports_unsortable_workflows() {
+        assert_eq!(
+            parse_system(
+                "in{x<5:foo,A}
+
+{x=1,m=2,a=3,s=4}"
+            ),
+            Err(ParseError::UnknownWorkflow {
+                workflow: "in".to_string(),
+                target: "foo".to_string()
+            })
+        );
+        assert_eq!(
+            parse_system(
+                "in{x<5:in,in}
+
+{x=1,m=2,a=3,s=4}"
+            ),
+            Err(ParseError::Cycle("in".to_string()))
+        );
+        assert_eq!(
+            parse_system(
+                "in{x<5:A,px}
+px{m>3:qq,R}
+qq{in}
+
+{x=1,m=2,a=3,s=4}"
+            ),
+            Err(ParseError::Cycle("in".to_string()))
+        );
+        assert_eq!(
+            parse_system("ab{x<5:A,R}\n\n{x=1,m=2,a=3,s=4}"),
+            Err(ParseError::MissingStart)
+        );
+        assert_eq!(
+            System::new([("ab", Flow::new(&[], "A").unwrap())], vec![]),
+            Err(ParseError::MissingStart)
+        );
+    }
+}
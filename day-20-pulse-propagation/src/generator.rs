@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use aoc_core::generate::{word, InputRng, Rng, SliceRandom};
+
+const BITS: usize = 12;
+// the product of the periods has to fit the answer
+const MAX_COUNTERS: usize = 4;
+
+fn is_prime(number: usize) -> bool {
+    number > 1
+        && (2..)
+            .take_while(|d| d * d <= number)
+            .all(|d| !number.is_multiple_of(d))
+}
+
+fn name(rng: &mut InputRng, taken: &mut HashSet<String>) -> String {
+    loop {
+        let name = word(rng, 2);
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+// The machine behind `rx` is built like the puzzle's: a counter for every
+// five `size`, up to four, of twelve flip-flops that a conjunction resets
+// once it reaches a prime period. Every counter's conjunction is inverted
+// into the final conjunction in front of `rx`
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let counters = (size / 5).clamp(1, MAX_COUNTERS);
+    let mut periods: Vec<usize> = vec![];
+    while periods.len() < counters {
+        let period = rng.gen_range(1 << (BITS - 1)..1 << BITS);
+        if is_prime(period) && !periods.contains(&period) {
+            periods.push(period);
+        }
+    }
+
+    let mut taken = HashSet::from(["rx".to_string()]);
+    let last = name(rng, &mut taken);
+    let mut modules = vec![format!("&{last} -> rx")];
+    let mut starts = vec![];
+    for period in periods {
+        let flip_flops: Vec<String> = (0..BITS).map(|_| name(rng, &mut taken)).collect();
+        let (hub, inverter) = (name(rng, &mut taken), name(rng, &mut taken));
+        let mut resets = vec![flip_flops[0].clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut next = vec![];
+            if bit + 1 < BITS {
+                next.push(flip_flops[bit + 1].clone());
+            }
+            match period >> bit & 1 {
+                1 => next.push(hub.clone()),
+                _ => resets.push(flip_flop.clone()),
+            }
+            next.shuffle(rng);
+            modules.push(format!("%{flip_flop} -> {}", next.join(", ")));
+        }
+        resets.push(inverter.clone());
+        resets.shuffle(rng);
+        modules.push(format!("&{hub} -> {}", resets.join(", ")));
+        modules.push(format!("&{inverter} -> {last}"));
+        starts.push(flip_flops[0].clone());
+    }
+    modules.push(format!("broadcaster -> {}", starts.join(", ")));
+    modules.shuffle(rng);
+
+    modules.join("\n")
+}
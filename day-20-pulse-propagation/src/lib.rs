@@ -0,0 +1,41 @@
+//! Day 20: Pulse Propagation
+//!
+//! [`pulser::parse_modules`] wires up the [`pulser::Module`]s, [`pulser::count_pulses`]
+//! and [`pulser::count_pulses_till_machine_starts`] press the button.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+mod generator;
+pub mod pulser;
+
+const BUTTON_PRESSES: u128 = 1000;
+
+pub struct PulsePropagation;
+
+impl Puzzle for PulsePropagation {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "pulse-propagation";
+
+    type Input = pulser::Configuration;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(modules: &Self::Input) -> Result<Answer, Error> {
+        Ok(pulser::count_pulses(&modules.0, BUTTON_PRESSES).into())
+    }
+
+    fn part_two(modules: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(
+            pulser::count_pulses_till_machine_starts(&modules.0)
+                .map(Answer::from)
+                .map_err(Error::from),
+        )
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,17 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-mod pulser;
+use day_20_pulse_propagation::PulsePropagation;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum = pulser::count_pulses(&contents, 1000);
-    println!("Sum:\n{sum}");
-    let rx = pulser::count_pulses_till_machine_starts(&contents);
-    println!("Rx: {rx}");
+fn main() -> ExitCode {
+    aoc_core::run(&PulsePropagation)
 }
@@ -1,4 +1,34 @@
 use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+
+use aoc_core::input::lines;
+use aoc_core::Span;
+use num::integer::lcm;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<module> -> <destinations>` {0}")]
+    MalformedModule(Span),
+    #[error("unknown module prefix, expected `%` or `&` {0}")]
+    UnknownPrefix(Span),
+    #[error("`{0}` can't name a module, names can't be empty or hold spaces, `,`, `%` or `&`")]
+    InvalidName(String),
+    #[error("module `{0}` is given twice")]
+    DuplicateModule(String),
+    #[error("module already given {0}")]
+    RepeatedModule(Span),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("no module sends pulses to the `rx` machine")]
+    MissingMachine,
+    #[error("the `rx` machine has to be fed by a single conjunction module")]
+    UnsupportedMachine,
+    #[error("`{0}` never sends a high pulse towards the `rx` machine")]
+    NeverHigh(String),
+}
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 enum Pulse {
@@ -6,8 +36,9 @@ enum Pulse {
     High,
 }
 
-#[derive(Debug, PartialEq)]
-enum ModuleType {
+/// Kinds of modules, told apart by the prefix of their name
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModuleType {
     Broadcaster,
     FlipFlop,
     Conjunction,
@@ -30,8 +61,9 @@ impl Signal {
     }
 }
 
-#[derive(Debug)]
-struct Module {
+/// A module and the modules it is wired to
+#[derive(Clone, Debug)]
+pub struct Module {
     name: String,
     is_on: bool,
     module_type: ModuleType,
@@ -41,18 +73,70 @@ struct Module {
 }
 
 impl Module {
-    fn parse(line: &str) -> (String, Self) {
-        let (name_str, next_str) = line.split_once(" -> ").unwrap();
+    /// Module sending its pulses to `destinations`, its sources are known
+    /// once it's wired up in a [`Configuration`]
+    pub fn new(
+        name: &str,
+        module_type: ModuleType,
+        destinations: &[&str],
+    ) -> Result<Module, ParseError> {
+        for name in std::iter::once(&name).chain(destinations) {
+            if name.is_empty() || name.contains(|c: char| c.is_whitespace() || ",%&".contains(c)) {
+                return Err(ParseError::InvalidName(name.to_string()));
+            }
+        }
+        Ok(Module {
+            name: name.to_string(),
+            is_on: false,
+            module_type,
+            next: destinations.iter().map(|next| next.to_string()).collect(),
+            from: vec![],
+            previous_pulses: HashMap::new(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn module_type(&self) -> &ModuleType {
+        &self.module_type
+    }
+
+    /// Modules this one sends its pulses to
+    pub fn destinations(&self) -> &[String] {
+        &self.next
+    }
+
+    /// Modules sending pulses to this one, known once all modules are parsed
+    pub fn sources(&self) -> &[String] {
+        &self.from
+    }
+
+    fn parse(index: usize, line: &str) -> Result<(String, Self), ParseError> {
+        let (name_str, next_str) = line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::MalformedModule(Span::line(index, line)))?;
         let next = next_str.split(", ").map(|s| s.to_string()).collect();
         let (name, module_type) = match name_str {
             "broadcaster" => ("broadcaster".to_string(), ModuleType::Broadcaster),
-            name => match &name[0..1] {
-                "%" => (name[1..].to_string(), ModuleType::FlipFlop),
-                "&" => (name[1..].to_string(), ModuleType::Conjunction),
-                x => panic!("Unknown prefix {}", x),
+            name => match name.chars().next() {
+                Some('%') => (name[1..].to_string(), ModuleType::FlipFlop),
+                Some('&') => (name[1..].to_string(), ModuleType::Conjunction),
+                Some(x) => {
+                    return Err(ParseError::UnknownPrefix(Span::at(
+                        index,
+                        line,
+                        &name[..x.len_utf8()],
+                    )))
+                }
+                None => return Err(ParseError::MalformedModule(Span::line(index, line))),
             },
         };
-        (
+        if name.is_empty() {
+            return Err(ParseError::MalformedModule(Span::line(index, line)));
+        }
+        Ok((
             name.clone(),
             Module {
                 name,
@@ -62,7 +146,7 @@ impl Module {
                 from: vec![],
                 previous_pulses: HashMap::new(),
             },
-        )
+        ))
     }
 
     fn initiate_previous_pulses(&mut self, mapping: &HashMap<String, Vec<String>>) {
@@ -124,12 +208,17 @@ impl Module {
 
 const BUTTON: &str = "button";
 const BROADCASTER: &str = "broadcaster";
+// the sources of the `rx` machine in the puzzle's inputs fire within a few
+// thousand presses
+const MAX_PRESSES: u64 = 1_000_000;
 
-type Modules = HashMap<String, Module>;
+/// Modules by their name
+pub type Modules = HashMap<String, Module>;
 type Broadcasts = Vec<(u128, u128)>;
 
-pub fn count_pulses(input: &str, cycles: u128) -> u128 {
-    let mut modules = parse_modules(input);
+/// Low pulses times high pulses sent over `cycles` button presses
+pub fn count_pulses(modules: &Modules, cycles: u128) -> u128 {
+    let mut modules = modules.clone();
     let mut broadcasts: Broadcasts = vec![];
     for _i in 0..cycles {
         broadcasts.push(broadcast(&mut modules));
@@ -145,53 +234,103 @@ pub fn count_pulses(input: &str, cycles: u128) -> u128 {
     let (low, high): (Vec<_>, Vec<_>) = broadcasts.iter().cloned().unzip();
     let (rest_low, rest_high): (Vec<_>, Vec<_>) = broadcasts.iter().take(rest).cloned().unzip();
 
-    return low.iter().sum::<u128>() * high.iter().sum::<u128>() * loops.pow(2)
-        + rest_low.iter().sum::<u128>() * rest_high.iter().sum::<u128>();
+    low.iter().sum::<u128>() * high.iter().sum::<u128>() * loops.pow(2)
+        + rest_low.iter().sum::<u128>() * rest_high.iter().sum::<u128>()
 }
 
-pub fn count_pulses_till_machine_starts(input: &str) -> usize {
-    let mut modules = parse_modules(input);
-    let mut targets: HashMap<String, usize> = modules
-        .get("vf")
-        .unwrap()
-        .from
-        .iter()
-        .map(|name| (name.to_owned(), 0))
-        .collect();
-    let mut cycles: usize = 0;
-    loop {
-        cycles += 1;
-
-        let mut queue = VecDeque::from([Signal {
-            from: BUTTON.to_string(),
-            to: BROADCASTER.to_string(),
-            pulse: Pulse::Low,
-        }]);
+/// Button presses until a single low pulse reaches `rx`. The machine is fed
+/// by a conjunction, which sends a low pulse once each of its sources sent
+/// it a high pulse in the same press, and each source does so on a cycle
+/// starting with the first press it does.
+pub fn count_pulses_till_machine_starts(modules: &Modules) -> Result<u64, SolveError> {
+    let mut feeders = modules
+        .values()
+        .filter(|module| module.next.iter().any(|next| next == "rx"));
+    let feeder = feeders.next().ok_or(SolveError::MissingMachine)?;
+    if feeders.next().is_some() || feeder.module_type != ModuleType::Conjunction {
+        return Err(SolveError::UnsupportedMachine);
+    }
+
+    let mut modules = modules.clone();
+    let mut cycles: HashMap<&str, u64> = HashMap::new();
+    for presses in 1..=MAX_PRESSES {
+        let mut queue = VecDeque::from([Signal::new(BUTTON, BROADCASTER, &Pulse::Low)]);
         while let Some(signal) = queue.pop_front() {
-            if targets.contains_key(&signal.to) && signal.pulse.eq(&Pulse::Low) {
-                if let Some(target) = targets.get_mut(&signal.to) {
-                    *target = cycles;
-                }
-                match targets.iter().fold(1, |acc, (_name, cycle)| acc * cycle) {
-                    0 => {}
-                    total => return total,
+            if signal.to == feeder.name && signal.pulse == Pulse::High {
+                if let Some(source) = feeder.from.iter().find(|source| **source == signal.from) {
+                    cycles.entry(source).or_insert(presses);
                 }
             }
+            let Some(module) = modules.get_mut(&signal.to) else {
+                continue;
+            };
+            let Some(next_pulse) = module.propagate(&signal) else {
+                continue;
+            };
+            queue.extend(
+                module
+                    .next
+                    .iter()
+                    .map(|next| Signal::new(&module.name, next, &next_pulse)),
+            );
+        }
 
-            let Some(module) = modules.get_mut(&signal.to) else {continue;};
-            let Some(next_pulse) = module.propagate(&signal) else { continue };
-            let mut next_queue: VecDeque<Signal> = module
-                .next
-                .iter()
-                .map(|next| Signal::new(&module.name, &next, &next_pulse))
-                .collect();
-            queue.append(&mut next_queue);
+        if cycles.len() == feeder.from.len() {
+            return Ok(cycles.values().fold(1, |total, &cycle| lcm(total, cycle)));
+        }
+        // back where it started, the sources that didn't fire never will
+        if modules.values().all(Module::is_default) {
+            break;
         }
     }
+
+    let silent = feeder
+        .from
+        .iter()
+        .find(|source| !cycles.contains_key(source.as_str()));
+    Err(SolveError::NeverHigh(silent.cloned().unwrap_or_default()))
 }
 
-fn parse_modules(input: &str) -> Modules {
-    let mut modules: Modules = input.split("\n").map(Module::parse).collect();
+/// The module configuration
+#[derive(Debug)]
+pub struct Configuration(pub Modules);
+
+impl Configuration {
+    /// Wires up modules with distinct names
+    pub fn new(modules: impl IntoIterator<Item = Module>) -> Result<Self, ParseError> {
+        let mut named = Modules::new();
+        for module in modules {
+            if named.contains_key(&module.name) {
+                return Err(ParseError::DuplicateModule(module.name));
+            }
+            named.insert(module.name.clone(), module);
+        }
+        Ok(Configuration(wire(named)))
+    }
+}
+
+impl FromStr for Configuration {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_modules(input).map(Configuration)
+    }
+}
+
+/// Reads the `<module> -> <destinations>` lines and wires the modules up
+pub fn parse_modules(input: &str) -> Result<Modules, ParseError> {
+    let mut modules = Modules::new();
+    for (index, line) in lines(input).enumerate() {
+        let (name, module) = Module::parse(index, line)?;
+        if modules.insert(name, module).is_some() {
+            return Err(ParseError::RepeatedModule(Span::line(index, line)));
+        }
+    }
+    Ok(wire(modules))
+}
+
+// Lets every module know the modules sending pulses to it
+fn wire(mut modules: Modules) -> Modules {
     let modules_mapping = modules
         .iter()
         .map(|(name, module)| (name.to_owned(), module.next.clone()))
@@ -215,15 +354,108 @@ fn broadcast(modules: &mut Modules) -> (u128, u128) {
         } else {
             high += 1;
         }
-        let Some(module) = modules.get_mut(&signal.to) else {continue;};
-        let Some(next_pulse) = module.propagate(&signal) else { continue };
+        let Some(module) = modules.get_mut(&signal.to) else {
+            continue;
+        };
+        let Some(next_pulse) = module.propagate(&signal) else {
+            continue;
+        };
         let mut next_queue: VecDeque<Signal> = module
             .next
             .iter()
-            .map(|next| Signal::new(&module.name, &next, &next_pulse))
+            .map(|next| Signal::new(&module.name, next, &next_pulse))
             .collect();
         queue.append(&mut next_queue);
     }
 
-    return (low, high);
+    (low, high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_counts_pulses() {
+        let modules =
+            parse_modules("broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a")
+                .unwrap();
+        assert_eq!(count_pulses(&modules, 1000), 32000000);
+        assert_eq!(
+            count_pulses_till_machine_starts(&modules),
+            Err(SolveError::MissingMachine)
+        );
+    }
+
+    #[test]
+    fn it_counts_presses_till_the_machine_starts() {
+        // `a` is high every 2nd press and `b` every 4th press
+        let modules = parse_modules(
+            "broadcaster -> fa\n%fa -> fb, na\n%fb -> nb\n&na -> a\n&nb -> b\n&a -> rx\n&b -> rx",
+        )
+        .unwrap();
+        assert_eq!(
+            count_pulses_till_machine_starts(&modules),
+            Err(SolveError::UnsupportedMachine)
+        );
+
+        let modules = parse_modules(
+            "broadcaster -> fa\n%fa -> fb, na\n%fb -> nb\n&na -> inv\n&nb -> inv\n&inv -> rx",
+        )
+        .unwrap();
+        assert_eq!(count_pulses_till_machine_starts(&modules), Ok(4));
+
+        let modules = parse_modules("broadcaster -> a\n&a -> rx").unwrap();
+        assert_eq!(
+            count_pulses_till_machine_starts(&modules),
+            Err(SolveError::NeverHigh("broadcaster".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_builds_configurations_in_code() {
+        let configuration = Configuration::new([
+            Module::new("broadcaster", ModuleType::Broadcaster, &["a", "b", "c"]).unwrap(),
+            Module::new("a", ModuleType::FlipFlop, &["b"]).unwrap(),
+            Module::new("b", ModuleType::FlipFlop, &["c"]).unwrap(),
+            Module::new("c", ModuleType::FlipFlop, &["inv"]).unwrap(),
+            Module::new("inv", ModuleType::Conjunction, &["a"]).unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(count_pulses(&configuration.0, 1000), 32000000);
+        assert_eq!(configuration.0["inv"].sources(), ["c"]);
+
+        assert_eq!(
+            Module::new("%a", ModuleType::FlipFlop, &["b"]).unwrap_err(),
+            ParseError::InvalidName("%a".to_string())
+        );
+        assert_eq!(
+            Module::new("a", ModuleType::FlipFlop, &["b, c"]).unwrap_err(),
+            ParseError::InvalidName("b, c".to_string())
+        );
+        assert_eq!(
+            Configuration::new([
+                Module::new("a", ModuleType::FlipFlop, &["a"]).unwrap(),
+                Module::new("a", ModuleType::Conjunction, &["a"]).unwrap(),
+            ])
+            .unwrap_err(),
+            ParseError::DuplicateModule("a".to_string())
+        );
+    }
+
+    #[test]
+    fn it_reports_malformed_modules() {
+        assert_eq!(
+            parse_modules("broadcaster -> a\n?a -> b").unwrap_err(),
+            ParseError::UnknownPrefix(Span::new(2, 1, "?"))
+        );
+        assert_eq!(
+            parse_modules("broadcaster a").unwrap_err(),
+            ParseError::MalformedModule(Span::new(1, 1, "broadcaster a"))
+        );
+        assert_eq!(
+            parse_modules("broadcaster -> a\n%a -> b\n&b -> a\n%a -> a").unwrap_err(),
+            ParseError::RepeatedModule(Span::new(4, 1, "%a -> a"))
+        );
+    }
 }
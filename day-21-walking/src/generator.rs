@@ -0,0 +1,36 @@
+use aoc_core::generate::{InputRng, Rng};
+
+// Part two's step count only works out for gardens of this width
+const WIDTH: usize = 131;
+const MAX_ROCKS: usize = 25;
+
+// Garden of the puzzle's width with rocks on `size` percent of the plots,
+// up to a quarter. The start is in the middle and its row, its column and
+// the border are kept clear like in the puzzle
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let rocks = size.min(MAX_ROCKS) as u32;
+    let middle = WIDTH / 2;
+    (0..WIDTH)
+        .map(|row| {
+            (0..WIDTH)
+                .map(|col| {
+                    if (row, col) == (middle, middle) {
+                        'S'
+                    } else if row == middle
+                        || col == middle
+                        || row == 0
+                        || col == 0
+                        || row == WIDTH - 1
+                        || col == WIDTH - 1
+                        || !rng.gen_ratio(rocks, 100)
+                    {
+                        '.'
+                    } else {
+                        '#'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
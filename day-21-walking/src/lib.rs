@@ -0,0 +1,52 @@
+//! Day 21: Step Counter
+//!
+//! [`walker::parse_garden`] reads the [`walker::Garden`], [`walker::count_tiles`] and
+//! [`walker::count_tiles_infinite`] walk it.
+
+use aoc_core::generate::InputRng;
+use aoc_core::visualize::Painter;
+use aoc_core::{Answer, Error, Part, Puzzle};
+
+mod generator;
+pub mod walker;
+
+const STEPS: usize = 64;
+const STEPS_INFINITE: usize = 26501365;
+
+pub struct Walking;
+
+impl Puzzle for Walking {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "walking";
+
+    type Input = walker::Map;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(garden: &Self::Input) -> Result<Answer, Error> {
+        Ok(walker::count_tiles(garden, STEPS).into())
+    }
+
+    fn part_two(garden: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(
+            walker::count_tiles_infinite(garden, STEPS_INFINITE)
+                .map(Answer::from)
+                .map_err(Error::from),
+        )
+    }
+
+    // the infinite garden is far too large to draw
+    fn visualize(
+        garden: &Self::Input,
+        part: Part,
+        painter: &Painter,
+    ) -> Option<Result<String, Error>> {
+        (part == Part::One).then(|| Ok(walker::draw_reachable(garden, STEPS, painter)))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
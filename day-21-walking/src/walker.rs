@@ -1,117 +1,287 @@
-use std::collections::HashMap;
+use aoc_core::grid::{Grid, GridError, Point};
+use aoc_core::input::lines;
+use aoc_core::search::bfs_within;
+use aoc_core::visualize::{Color, Painter};
+use aoc_core::Span;
+use std::collections::HashSet;
+use std::str::FromStr;
+use thiserror::Error;
 
-#[derive(Clone, Copy, Debug)]
-enum Tile {
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("garden has no starting position `S`")]
+    MissingStart,
+    #[error("second starting position {0}")]
+    MultipleStarts(Span),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("the repeated garden can only be walked from the middle of a square garden")]
+    StartOffCentre,
+}
+
+/// Tiles of the garden
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
     Start,
     Garden,
     Rock,
 }
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct Point(i64, i64);
-
-impl Point {
-    fn next(&self, grid: &Grid) -> Vec<Point> {
-        STEPS
-            .iter()
-            .map(|step| {
-                let row = self.0 + step.0;
-                let col = self.1 + step.1;
-                let point = Point(row, col);
-                if row < 0 || col < 0 || row as usize >= grid.len() || col as usize >= grid[0].len()
-                {
-                    return None;
-                }
-                match grid[row as usize][col as usize] {
-                    Tile::Rock => None,
-                    _ => Some(point),
-                }
-            })
-            .flatten()
-            .collect()
-    }
+/// The garden map
+pub type Garden = Grid<Tile>;
+
+fn next(point: Point, grid: &Garden) -> Vec<Point> {
+    grid.neighbours(point)
+        .filter(|(_, next)| grid[*next] != Tile::Rock)
+        .map(|(_, next)| next)
+        .collect()
 }
 
-type Grid = Vec<Vec<Tile>>;
+/// Garden plots reachable in exactly `steps` steps
+pub fn count_tiles(map: &Map, steps: usize) -> usize {
+    reachable(&map.garden, map.start, steps).len()
+}
 
-const STEPS: [(i64, i64); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
-
-pub fn count_tiles(input: &str, steps: usize) -> usize {
-    let (start, grid) = parse_input(input);
-    let mut visited: HashMap<Point, usize> = HashMap::from([(start.clone(), 0)]);
-    let mut stack = vec![start.next(&grid)];
-    let mut step = 0;
-    while let Some(points) = stack.pop() {
-        step += 1;
-        if step > steps {
-            break;
-        }
-        let mut next = vec![];
-        for point in points {
-            if visited.contains_key(&point) {
-                continue;
-            }
-            visited.insert(point.clone(), step);
-            next.extend(point.next(&grid));
-        }
-        if next.len() > 0 {
-            stack.push(next);
-        }
-    }
+// Plots the elf can end on after exactly `steps` steps from `start`
+fn reachable(grid: &Garden, start: Point, steps: usize) -> HashSet<Point> {
+    let distances = bfs_within(start, steps, |point| next(*point, grid));
 
     let res = steps % 2;
-    return visited
-        .iter()
-        .filter(|(_point, step)| step.to_owned() % 2 == res)
-        .map(|(point, _step)| point)
-        .count();
+    distances
+        .into_iter()
+        .filter(|(_, step)| step % 2 == res)
+        .map(|(point, _)| point)
+        .collect()
+}
+
+/// The garden with the plots reachable in `steps` steps marked
+pub fn draw_reachable(map: &Map, steps: usize, painter: &Painter) -> String {
+    let plots = reachable(&map.garden, map.start, steps);
+    painter.grid(&map.garden, |point, tile| match tile {
+        Tile::Start => ('S', Some(Color::Red)),
+        Tile::Rock => ('#', Some(Color::Dim)),
+        Tile::Garden if plots.contains(&point) => ('O', Some(Color::Green)),
+        Tile::Garden => ('.', None),
+    })
 }
 
-pub fn count_tiles_infinite(input: &str, steps: usize) -> usize {
-    let grid_size = input.lines().count();
-    let expanded_input = expand_map(input);
+/// Reachable garden plots when the map repeats in every direction, which
+/// is extrapolated from a few repeats for a start in the middle of a square
+/// garden
+pub fn count_tiles_infinite(map: &Map, steps: usize) -> Result<usize, SolveError> {
+    let grid = &map.garden;
+    let grid_size = grid.height();
+    if grid.width() != grid_size || map.start != Point::new(grid_size / 2, grid_size / 2) {
+        return Err(SolveError::StartOffCentre);
+    }
+    let expanded = expand_map(grid);
+    // the start of the garden in the middle of the expanded ones
+    let start = Point::new(map.start.row + grid_size * 2, map.start.col + grid_size * 2);
     let expansion = steps / grid_size;
     let rest = steps - expansion * grid_size;
+    let count = |steps| reachable(&expanded, start, steps).len();
 
     let results = (
-        count_tiles(&expanded_input, rest),
-        count_tiles(&expanded_input, rest + grid_size), // expand once
-        count_tiles(&expanded_input, rest + grid_size * 2), // expand twice
+        count(rest),
+        count(rest + grid_size),     // expand once
+        count(rest + grid_size * 2), // expand twice
     );
 
-    return interpolate(expansion, results);
+    Ok(interpolate(expansion, results))
 }
 
-fn expand_map(input: &str) -> String {
-    return [input; 5]
-        .join("\n")
-        .lines()
-        .map(|l| [l; 5].join(""))
-        .collect::<Vec<String>>()
-        .join("\n");
+fn expand_map(grid: &Garden) -> Garden {
+    let (width, height) = (grid.width(), grid.height());
+    Grid::from_fn(width * 5, height * 5, |point| {
+        grid[Point::new(point.row % height, point.col % width)]
+    })
 }
 
 fn interpolate(n: usize, results: (usize, usize, usize)) -> usize {
     let a = (results.2 + results.0 - 2 * results.1) / 2;
     let b = results.1 - results.0 - a;
     let c = results.0;
-    return a * n * n + b * n + c;
-}
-
-fn parse_input(input: &str) -> (Point, Grid) {
-    let grid: Grid = input
-        .lines()
-        .map(|line| {
-            line.as_bytes()
-                .iter()
-                .map(|b| match b {
-                    b'S' => Tile::Start,
-                    b'.' => Tile::Garden,
-                    b'#' => Tile::Rock,
-                    _ => panic!("Unknown tile {}", char::from(*b)),
-                })
-                .collect()
+    a * n * n + b * n + c
+}
+
+fn tile(c: char) -> Option<Tile> {
+    match c {
+        'S' => Some(Tile::Start),
+        '.' => Some(Tile::Garden),
+        '#' => Some(Tile::Rock),
+        _ => None,
+    }
+}
+
+/// The map of the garden and where the elf starts on it
+#[derive(Debug, PartialEq)]
+pub struct Map {
+    garden: Garden,
+    start: Point,
+}
+
+impl Map {
+    pub fn garden(&self) -> &Garden {
+        &self.garden
+    }
+
+    pub fn start(&self) -> Point {
+        self.start
+    }
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_garden(input)
+    }
+}
+
+/// Reads the garden map, which has to have one `S`
+pub fn parse_garden(input: &str) -> Result<Map, ParseError> {
+    let garden = Grid::parse(input, 0, tile)?;
+
+    let mut starts = lines(input).enumerate().flat_map(|(index, line)| {
+        line.matches('S')
+            .map(move |start| Span::at(index, line, start))
+    });
+    if starts.next().is_none() {
+        return Err(ParseError::MissingStart);
+    }
+    if let Some(span) = starts.next() {
+        return Err(ParseError::MultipleStarts(span));
+    }
+
+    let start = garden.find(|tile| *tile == Tile::Start);
+    Ok(Map {
+        start: start.ok_or(ParseError::MissingStart)?,
+        garden,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::VecDeque;
+
+    // Walks the garden tiled as far as the steps reach
+    fn brute_force_infinite(map: &Map, steps: usize) -> usize {
+        let grid = &map.garden;
+        let size = grid.height() as i64;
+        let start = (map.start.row as i64, map.start.col as i64);
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 0)]);
+        let mut count = 0;
+        while let Some(((row, col), step)) = queue.pop_front() {
+            if step % 2 == steps % 2 {
+                count += 1;
+            }
+            if step == steps {
+                continue;
+            }
+            for next in [
+                (row - 1, col),
+                (row + 1, col),
+                (row, col - 1),
+                (row, col + 1),
+            ] {
+                let tile = Point::new(
+                    next.0.rem_euclid(size) as usize,
+                    next.1.rem_euclid(size) as usize,
+                );
+                if grid[tile] != Tile::Rock && seen.insert(next) {
+                    queue.push_back((next, step + 1));
+                }
+            }
+        }
+        count
+    }
+
+    // Odd square gardens shaped like the puzzle's: the start in the middle,
+    // its row, its column and the border clear, and rocks that never touch
+    // so that every plot is as far as its manhattan distance
+    fn garden() -> impl Strategy<Value = Map> {
+        (2_usize..=4).prop_flat_map(|half| {
+            let size = half * 2 + 1;
+            prop::collection::vec(prop::bool::weighted(0.3), size * size).prop_map(move |rocks| {
+                let mut garden = Grid::from_fn(size, size, |_| Tile::Garden);
+                garden[Point::new(half, half)] = Tile::Start;
+                for row in 1..size - 1 {
+                    for col in 1..size - 1 {
+                        let touching = (row - 1..=row + 1)
+                            .flat_map(|r| (col - 1..=col + 1).map(move |c| Point::new(r, c)))
+                            .any(|point| garden[point] == Tile::Rock);
+                        if rocks[row * size + col] && row != half && col != half && !touching {
+                            garden[Point::new(row, col)] = Tile::Rock;
+                        }
+                    }
+                }
+                Map {
+                    garden,
+                    start: Point::new(half, half),
+                }
+            })
         })
-        .collect();
-    return (Point(grid.len() as i64 / 2, grid.len() as i64 / 2), grid);
+    }
+
+    #[test]
+    fn it_counts_reachable_tiles() {
+        let garden = parse_garden("...\n.S.\n...").unwrap();
+        assert_eq!(count_tiles(&garden, 1), 4);
+        assert_eq!(count_tiles(&garden, 2), 5);
+        assert_eq!(
+            draw_reachable(&garden, 1, &Painter::new(false)),
+            ".O.\nOSO\n.O."
+        );
+        assert_eq!(
+            parse_garden("...\n.S.\n..O"),
+            Err(ParseError::Grid(GridError::UnknownTile(Span::new(
+                3, 3, "O"
+            ))))
+        );
+        assert_eq!(parse_garden("...\n...\n..."), Err(ParseError::MissingStart));
+        assert_eq!(
+            parse_garden("S..\n.S.\n..."),
+            Err(ParseError::MultipleStarts(Span::new(2, 2, "S")))
+        );
+        assert_eq!(expand_map(&garden.garden).width(), 15);
+        assert_eq!(
+            reachable(&expand_map(&garden.garden), Point::new(7, 7), 3).len(),
+            16
+        );
+    }
+
+    #[test]
+    fn it_walks_from_the_start() {
+        let garden = parse_garden("...\n.#.\n..S").unwrap();
+        assert_eq!(garden.start(), Point::new(2, 2));
+        assert_eq!(count_tiles(&garden, 2), 3);
+        assert_eq!(count_tiles(&garden, 64), 4);
+        assert_eq!(
+            count_tiles_infinite(&garden, 5),
+            Err(SolveError::StartOffCentre)
+        );
+        let wide = parse_garden(".....\n..S..\n.....").unwrap();
+        assert_eq!(
+            count_tiles_infinite(&wide, 5),
+            Err(SolveError::StartOffCentre)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn it_interpolates_like_the_tiled_map(garden in garden(), repeats in 0_usize..=4) {
+            let steps = garden.garden.height() / 2 + repeats * garden.garden.height();
+            prop_assert_eq!(
+                count_tiles_infinite(&garden, steps),
+                Ok(brute_force_infinite(&garden, steps))
+            );
+        }
+    }
 }
@@ -0,0 +1,45 @@
+use std::collections::HashSet;
+
+use aoc_core::generate::{InputRng, Rng};
+
+const FLOOR: usize = 10;
+const MAX_LENGTH: usize = 4;
+
+// Snapshot of `size` bricks, lines of up to four cubes along one axis above
+// a ten by ten floor, none of them overlapping
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(1);
+    let mut cubes: HashSet<[usize; 3]> = HashSet::new();
+    let mut bricks = vec![];
+    while bricks.len() < size {
+        let start = [
+            rng.gen_range(0..FLOOR),
+            rng.gen_range(0..FLOOR),
+            rng.gen_range(1..=size * 2),
+        ];
+        let (axis, length) = (rng.gen_range(0..3), rng.gen_range(1..=MAX_LENGTH));
+        let mut end = start;
+        end[axis] += length - 1;
+        if axis < 2 && end[axis] >= FLOOR {
+            continue;
+        }
+
+        let brick: Vec<[usize; 3]> = (0..length)
+            .map(|offset| {
+                let mut cube = start;
+                cube[axis] += offset;
+                cube
+            })
+            .collect();
+        if brick.iter().any(|cube| cubes.contains(cube)) {
+            continue;
+        }
+        cubes.extend(brick);
+        bricks.push(format!(
+            "{},{},{}~{},{},{}",
+            start[0], start[1], start[2], end[0], end[1], end[2]
+        ));
+    }
+
+    bricks.join("\n")
+}
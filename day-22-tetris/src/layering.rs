@@ -1,34 +1,93 @@
 use std::{
     collections::{HashMap, HashSet},
+    str::FromStr,
     vec,
 };
 
+use aoc_core::error::parse_at;
+use aoc_core::input::lines;
+use aoc_core::parallel;
+#[cfg(feature = "render")]
+use aoc_core::render::{Color, Scene, Shape, Style};
+use aoc_core::Span;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<x>,<y>,<z>~<x>,<y>,<z>` {0}")]
+    MalformedBrick(Span),
+    #[error("invalid coordinate {0}")]
+    InvalidCoordinate(Span),
+    #[error("brick end must not be before its start {0}")]
+    ReversedEnds(Span),
+    #[error("brick must be above the ground at z=1 or higher {0}")]
+    BelowGround(Span),
+    #[error("brick must stay below x={MAX_FLOOR}, y={MAX_FLOOR} and z={MAX_HEIGHT} {0}")]
+    OutOfBounds(Span),
+}
+
+// The floor is kept as a grid of heights, so it has to stay small
+const MAX_FLOOR: usize = 1_000;
+const MAX_HEIGHT: usize = 1_000_000;
+
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
 struct Point(usize, usize, usize);
 impl Point {
-    fn new(coords: &str) -> Self {
+    fn new(index: usize, line: &str, coords: &str) -> Result<Self, ParseError> {
         let [x, y, z] = coords
             .split(',')
-            .map(|s| s.parse::<usize>().expect("Must be number"))
-            .collect::<Vec<_>>()[..] else { panic!("Must be 3 coordinates in {}", coords) };
-        Self(x, y, z)
+            .map(|s| parse_at::<usize>(index, line, s).map_err(ParseError::InvalidCoordinate))
+            .collect::<Result<Vec<_>, _>>()?[..]
+        else {
+            return Err(ParseError::MalformedBrick(Span::at(index, line, coords)));
+        };
+        Ok(Self(x, y, z))
     }
 }
 
+/// A brick once every brick has settled
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-struct Brick {
+pub struct Brick {
     name: String,
     level: usize,
+    // settled corners
+    start: Point,
+    end: Point,
     supports: Vec<String>,
     supported_by: Vec<String>,
 }
 
 impl Brick {
+    /// The snapshot line the brick was read from
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Lowest corner once settled, as `(x, y, z)`
+    pub fn start(&self) -> (usize, usize, usize) {
+        (self.start.0, self.start.1, self.start.2)
+    }
+
+    /// Highest corner once settled, as `(x, y, z)`
+    pub fn end(&self) -> (usize, usize, usize) {
+        (self.end.0, self.end.1, self.end.2)
+    }
+
+    /// Names of the bricks resting on this one
+    pub fn supports(&self) -> &[String] {
+        &self.supports
+    }
+
+    /// Names of the bricks this one rests on
+    pub fn supported_by(&self) -> &[String] {
+        &self.supported_by
+    }
+
     fn can_desintegrate(&self, bricks: &HashMap<String, Brick>) -> bool {
         self.supports.iter().all(|brick| {
             bricks
                 .get(brick)
-                .and_then(|x| Some(x.supported_by.len() > 1))
+                .map(|x| x.supported_by.len() > 1)
                 .unwrap_or(false)
         })
     }
@@ -47,17 +106,16 @@ impl Brick {
             let should_fall = brick
                 .supports
                 .iter()
-                .map(|x| bricks.get(x))
-                .flatten()
+                .filter_map(|x| bricks.get(x))
                 .filter(|x| x.supported_by.iter().all(|s| removed.contains(&s)));
-            stack.extend(should_fall.into_iter());
+            stack.extend(should_fall);
         }
-        return dropped - 1;
+        dropped - 1
     }
 }
 
-pub fn count_redundant(input: &str) -> usize {
-    let bricks = parse(input);
+/// Bricks that can be taken out without any other falling
+pub fn count_redundant(bricks: &[Brick]) -> usize {
     let cache: &HashMap<String, Brick> = &bricks
         .iter()
         .map(|x| (x.name.to_owned(), x.to_owned()))
@@ -70,11 +128,11 @@ pub fn count_redundant(input: &str) -> usize {
         }
     }
 
-    return count;
+    count
 }
 
-pub fn count_chain(input: &str) -> usize {
-    let bricks = parse(input);
+/// Sum over every brick of how many others fall when it is taken out
+pub fn count_chain(bricks: &[Brick]) -> usize {
     let cache: &HashMap<String, Brick> = &bricks
         .iter()
         .map(|x| (x.name.to_owned(), x.to_owned()))
@@ -87,38 +145,123 @@ pub fn count_chain(input: &str) -> usize {
         }
     }
 
-    bricks
+    parallel::map(bricks, |brick| brick.drop_count(cache))
         .iter()
-        .fold(0, |acc, brick| acc + brick.drop_count(&cache))
+        .sum()
 }
 
-fn parse(input: &str) -> Vec<Brick> {
-    let mut bricks = input
-        .lines()
-        .map(|l| {
-            let (start, end) = l.split_once("~").expect("Line should have ~");
-            (Point::new(start), Point::new(end), l)
+/// Settled tower seen from the front (x, z) and the side (y, z), bricks that
+/// can be removed safely in green or shaded by how many others would fall
+#[cfg(feature = "render")]
+pub fn tower_scene(bricks: &[Brick], chain: bool) -> Scene {
+    let cache: &HashMap<String, Brick> = &bricks
+        .iter()
+        .map(|x| (x.name.to_owned(), x.to_owned()))
+        .collect();
+    let drops: Vec<usize> = bricks.iter().map(|brick| brick.drop_count(cache)).collect();
+    let most = drops.iter().max().copied().unwrap_or_default().max(1);
+    let width = bricks
+        .iter()
+        .map(|brick| brick.end.0 + 1)
+        .max()
+        .unwrap_or_default();
+    // the side view starts after the front one and a gap
+    let offset = (width + 2) as f64;
+
+    let mut scene = Scene::new(Color::WHITE);
+    for (brick, drop) in bricks.iter().zip(drops) {
+        let fill = match (chain, brick.can_desintegrate(cache)) {
+            (false, true) => Color(80, 180, 90),
+            (false, false) => Color(70, 110, 190),
+            (true, _) => {
+                let heat = (drop * 255 / most) as u8;
+                Color(heat, 60, 255 - heat)
+            }
+        };
+        let style = Style::fill(fill).with_stroke(Color::BLACK);
+        let (bottom, top) = (-(brick.start.2 as f64 - 1.0), -(brick.end.2 as f64));
+        scene.add(
+            Shape::Rect(
+                (brick.start.0 as f64, top),
+                ((brick.end.0 + 1) as f64, bottom),
+            ),
+            style,
+        );
+        scene.add(
+            Shape::Rect(
+                (offset + brick.start.1 as f64, top),
+                (offset + (brick.end.1 + 1) as f64, bottom),
+            ),
+            style,
+        );
+    }
+    scene
+}
+
+fn parse_snapshot(input: &str) -> Result<Vec<(Point, Point, &str)>, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, l)| {
+            let (start, end) = l
+                .split_once("~")
+                .ok_or_else(|| ParseError::MalformedBrick(Span::line(index, l)))?;
+            let (start, end) = (Point::new(index, l, start)?, Point::new(index, l, end)?);
+            if start.0 > end.0 || start.1 > end.1 || start.2 > end.2 {
+                return Err(ParseError::ReversedEnds(Span::line(index, l)));
+            }
+            if start.2 == 0 {
+                return Err(ParseError::BelowGround(Span::line(index, l)));
+            }
+            if end.0 >= MAX_FLOOR || end.1 >= MAX_FLOOR || end.2 >= MAX_HEIGHT {
+                return Err(ParseError::OutOfBounds(Span::line(index, l)));
+            }
+            Ok((start, end, l))
         })
-        .collect::<Vec<(Point, Point, &str)>>();
-    bricks.sort_by(|a, b| a.0 .2.cmp(&b.0 .2));
+        .collect()
+}
+
+/// The bricks of the snapshot once they have settled
+#[derive(Debug, PartialEq)]
+pub struct Snapshot(pub Vec<Brick>);
+
+impl FromStr for Snapshot {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse(input).map(Snapshot)
+    }
+}
 
-    let mut heights = [[0; 10]; 10];
+/// Reads the `<x>,<y>,<z>~<x>,<y>,<z>` snapshot and lets the bricks settle
+pub fn parse(input: &str) -> Result<Vec<Brick>, ParseError> {
+    let mut bricks = parse_snapshot(input)?;
+    bricks.sort_by_key(|a| a.0 .2);
+
+    let width = bricks
+        .iter()
+        .map(|brick| brick.1 .0 + 1)
+        .max()
+        .unwrap_or_default();
+    let depth = bricks
+        .iter()
+        .map(|brick| brick.1 .1 + 1)
+        .max()
+        .unwrap_or_default();
+    let mut heights = vec![vec![0; depth]; width];
     let mut brick_on_level: HashMap<(Point, Point, &str), usize> = HashMap::new();
     let mut bricks_to_level: HashMap<usize, Vec<(Point, Point, &str)>> = HashMap::new();
     for brick in bricks.iter() {
         // drop a brick down
         let mut min_level = 1;
-        for i in brick.0 .0..=brick.1 .0 {
-            for j in brick.0 .1..=brick.1 .1 {
-                min_level = heights[i][j].max(min_level);
+        for row in &heights[brick.0 .0..=brick.1 .0] {
+            for &height in &row[brick.0 .1..=brick.1 .1] {
+                min_level = height.max(min_level);
             }
         }
 
         let fill_level = min_level + brick.1 .2 - brick.0 .2 + 1;
-        for i in brick.0 .0..=brick.1 .0 {
-            for j in brick.0 .1..=brick.1 .1 {
-                heights[i][j] = fill_level;
-            }
+        for row in &mut heights[brick.0 .0..=brick.1 .0] {
+            row[brick.0 .1..=brick.1 .1].fill(fill_level);
         }
 
         brick_on_level.insert(brick.to_owned(), min_level);
@@ -137,14 +280,16 @@ fn parse(input: &str) -> Vec<Brick> {
             && other.0 .1 <= main.1 .1
     };
 
-    bricks
+    Ok(bricks
         .iter()
         .map(|position| {
             let (start, end, name) = position;
-            let brick_level = brick_on_level.get(position).unwrap();
+            let brick_level = &brick_on_level[position];
             let mut brick = Brick {
                 name: name.to_string(),
                 level: brick_level.to_owned(),
+                start: Point(start.0, start.1, *brick_level),
+                end: Point(end.0, end.1, brick_level + end.2 - start.2),
                 supports: vec![],
                 supported_by: vec![],
             };
@@ -152,7 +297,7 @@ fn parse(input: &str) -> Vec<Brick> {
 
             brick.supports = brick_on_level
                 .iter()
-                .filter(|(_brick, level)| support_level.eq(&level))
+                .filter(|(_brick, level)| support_level.eq(level))
                 .filter(|(brick, _level)| is_supported_by(brick, position))
                 .map(|((_start, _end, name), _level)| name.to_string())
                 .collect();
@@ -166,5 +311,50 @@ fn parse(input: &str) -> Vec<Brick> {
 
             brick
         })
-        .collect()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_settles_bricks() {
+        let bricks = parse("1,0,1~1,2,1\n0,0,2~2,0,2\n0,2,3~2,2,3\n0,0,4~0,2,4\n2,0,5~2,2,5\n0,1,6~2,1,6\n1,1,8~1,1,9").unwrap();
+        assert_eq!(count_redundant(&bricks), 5);
+        assert_eq!(count_chain(&bricks), 7);
+        let top = bricks.iter().find(|brick| brick.name == "1,1,8~1,1,9");
+        assert_eq!(
+            top.map(|brick| (brick.start, brick.end)),
+            Some((Point(1, 1, 5), Point(1, 1, 6)))
+        );
+    }
+
+    #[test]
+    fn it_reports_malformed_bricks() {
+        assert_eq!(
+            parse("1,0,1~1,2,1\n0,0,2").unwrap_err(),
+            ParseError::MalformedBrick(Span::new(2, 1, "0,0,2"))
+        );
+        assert_eq!(
+            parse("1,0,1~1,2").unwrap_err(),
+            ParseError::MalformedBrick(Span::new(1, 7, "1,2"))
+        );
+        assert_eq!(
+            parse("1,x,1~1,2,1").unwrap_err(),
+            ParseError::InvalidCoordinate(Span::new(1, 3, "x"))
+        );
+        assert_eq!(
+            parse("1,2,1~1,0,1").unwrap_err(),
+            ParseError::ReversedEnds(Span::new(1, 1, "1,2,1~1,0,1"))
+        );
+        assert_eq!(
+            parse("1,0,0~1,2,0").unwrap_err(),
+            ParseError::BelowGround(Span::new(1, 1, "1,0,0~1,2,0"))
+        );
+        assert_eq!(
+            parse("0,0,1~0,1444444444,3").unwrap_err(),
+            ParseError::OutOfBounds(Span::new(1, 1, "0,0,1~0,1444444444,3"))
+        );
+    }
 }
@@ -0,0 +1,44 @@
+//! Day 22: Sand Slabs
+//!
+//! [`layering::parse`] settles the [`layering::Brick`]s, [`layering::count_redundant`]
+//! and [`layering::count_chain`] take them out.
+
+use aoc_core::generate::InputRng;
+#[cfg(feature = "render")]
+use aoc_core::render::Scene;
+#[cfg(feature = "render")]
+use aoc_core::Part;
+use aoc_core::{Answer, Error, Puzzle};
+
+mod generator;
+pub mod layering;
+
+pub struct Tetris;
+
+impl Puzzle for Tetris {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "tetris";
+
+    type Input = layering::Snapshot;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(bricks: &Self::Input) -> Result<Answer, Error> {
+        Ok(layering::count_redundant(&bricks.0).into())
+    }
+
+    fn part_two(bricks: &Self::Input) -> Option<Result<Answer, Error>> {
+        Some(Ok(layering::count_chain(&bricks.0).into()))
+    }
+
+    #[cfg(feature = "render")]
+    fn render(bricks: &Self::Input, part: Part) -> Option<Result<Scene, Error>> {
+        Some(Ok(layering::tower_scene(&bricks.0, part == Part::Two)))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,17 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-mod layering;
+use day_22_tetris::Tetris;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum = layering::count_redundant(&contents);
-    println!("Sum:\n{sum}");
-    let sum_chain = layering::count_chain(&contents);
-    println!("Sum chain:\n{sum_chain}");
+fn main() -> ExitCode {
+    aoc_core::run(&Tetris)
 }
@@ -0,0 +1,126 @@
+use std::collections::{HashMap, VecDeque};
+
+use aoc_core::generate::{InputRng, Rng, SliceRandom};
+
+const MAX_CELLS: usize = 20;
+
+type Tile = (usize, usize);
+
+// Open tiles next to `tile`, with the slope leading there
+fn open_neighbours(trails: &[Vec<char>], (row, col): Tile) -> Vec<(char, Tile)> {
+    [('^', -1, 0), ('v', 1, 0), ('<', 0, -1), ('>', 0, 1)]
+        .into_iter()
+        .filter_map(|(arrow, dr, dc)| {
+            let next = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+            let tile = trails.get(next.0)?.get(next.1)?;
+            (*tile != '#').then_some((arrow, next))
+        })
+        .collect()
+}
+
+fn reverse(arrow: char) -> char {
+    match arrow {
+        '^' => 'v',
+        'v' => '^',
+        '<' => '>',
+        _ => '<',
+    }
+}
+
+// A maze of `size` / 2 cells a side, between 2 and 20, with some walls
+// knocked down so that routes can branch and meet again. Slopes next to
+// junctions all lead away from the start, so part one's routes can't loop
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let cells = (size / 2).clamp(2, MAX_CELLS);
+    let side = cells * 2 + 1;
+    let mut trails = vec![vec!['#'; side]; side];
+    let tile = |(row, col): Tile| (row * 2 + 1, col * 2 + 1);
+
+    // depth first carving from the top left cell
+    let mut visited = vec![vec![false; cells]; cells];
+    let mut stack = vec![(0, 0)];
+    visited[0][0] = true;
+    trails[1][1] = '.';
+    while let Some(&(row, col)) = stack.last() {
+        let unvisited: Vec<Tile> = [(0, 1), (2, 1), (1, 0), (1, 2)]
+            .into_iter()
+            .filter(|(dr, dc)| row + dr >= 1 && col + dc >= 1)
+            .map(|(dr, dc)| (row + dr - 1, col + dc - 1))
+            .filter(|&(r, c)| r < cells && c < cells && !visited[r][c])
+            .collect();
+        let Some(&next) = unvisited.choose(rng) else {
+            stack.pop();
+            continue;
+        };
+        visited[next.0][next.1] = true;
+        let (from, to) = (tile((row, col)), tile(next));
+        trails[(from.0 + to.0) / 2][(from.1 + to.1) / 2] = '.';
+        trails[to.0][to.1] = '.';
+        stack.push(next);
+    }
+    for _ in 0..cells * cells / 6 {
+        let (row, col) = (rng.gen_range(1..side - 1), rng.gen_range(1..side - 1));
+        if (row + col) % 2 == 1 {
+            trails[row][col] = '.';
+        }
+    }
+    let (start, end) = ((0, 1), (side - 1, side - 2));
+    trails[start.0][start.1] = '.';
+    trails[end.0][end.1] = '.';
+
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        for (_, next) in open_neighbours(&trails, current) {
+            if !distances.contains_key(&next) {
+                distances.insert(next, distances[&current] + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    let order = |tile: Tile| (distances[&tile], tile);
+
+    let junctions: Vec<Tile> = (0..side)
+        .flat_map(|row| (0..side).map(move |col| (row, col)))
+        .filter(|tile| trails[tile.0][tile.1] != '#')
+        .filter(|tile| open_neighbours(&trails, *tile).len() > 2)
+        .collect();
+    let mut slopes = vec![];
+    for junction in junctions {
+        for (arrow, first) in open_neighbours(&trails, junction) {
+            // follow the corridor to whatever ends it
+            let (mut previous, mut current) = (junction, first);
+            loop {
+                let next: Vec<Tile> = open_neighbours(&trails, current)
+                    .into_iter()
+                    .map(|(_, next)| next)
+                    .filter(|next| *next != previous)
+                    .collect();
+                match next[..] {
+                    [next] => (previous, current) = (current, next),
+                    _ => break,
+                }
+            }
+            // the openings stay flat for the route to be found
+            if current == junction || first == start || first == end {
+                continue;
+            }
+            slopes.push((
+                first,
+                match order(junction) < order(current) {
+                    true => arrow,
+                    false => reverse(arrow),
+                },
+            ));
+        }
+    }
+    for ((row, col), slope) in slopes {
+        trails[row][col] = slope;
+    }
+
+    trails
+        .iter()
+        .map(String::from_iter)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
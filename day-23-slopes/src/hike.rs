@@ -1,56 +1,118 @@
-use std::collections::HashSet;
-
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
-struct Pos(usize, usize);
-
-impl Pos {
-    fn successors(&self, grid: &Grid, visited: &HashSet<Pos>) -> Vec<(Pos, HashSet<Pos>)> {
-        let next = match grid[self.0][self.1] {
-            '.' => vec![
-                Pos(self.0, self.1 + 1),
-                Pos(self.0, self.1 - 1),
-                Pos(self.0 + 1, self.1 + 0),
-                Pos(self.0 - 1, self.1 + 0),
-            ],
-            '>' => vec![Pos(self.0, self.1 + 1)],
-            '<' => vec![Pos(self.0, self.1 - 1)],
-            '^' => vec![Pos(self.0 - 1, self.1 + 0)],
-            'v' => vec![Pos(self.0 + 1, self.1 + 0)],
-            c => panic!("Unknown direction {c}"),
-        };
-        let mut next_visited = visited.clone();
-        next_visited.extend(&HashSet::from([self.clone()]));
-
-        next.iter()
-            .filter(|next_pos| !visited.contains(&next_pos))
-            .map(|next_pos| match grid[next_pos.0][next_pos.1] {
-                '#' => None,
-                _ => Some((*next_pos, next_visited.clone())),
-            })
-            .flatten()
-            .collect()
-    }
+use std::str::FromStr;
+
+use aoc_core::grid::{Direction, Grid, GridError, Point};
+use aoc_core::search::{compress, unfold};
+use aoc_core::visualize::{Color, Painter};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("trail map must be at least 3 tiles wide and tall")]
+    TooSmall,
 }
 
-type Grid = Vec<Vec<char>>;
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("there is no route to the bottom right exit")]
+    NoRoute,
+}
 
-pub fn longest_route(input: &str) -> usize {
-    let grid: Grid = input.lines().map(|l| l.chars().collect()).collect();
-    let end = Pos(grid.len() - 2, grid[0].len() - 2);
+/// The map of paths, forest and slopes
+pub type Trails = Grid<char>;
+
+fn successors(pos: Point, grid: &Trails) -> Vec<Point> {
+    let directions = match Direction::from_arrow(grid[pos]) {
+        Some(slope) => vec![slope],
+        None => Direction::ALL.to_vec(),
+    };
+
+    directions
+        .into_iter()
+        .filter_map(|direction| grid.step(pos, direction))
+        .filter(|next_pos| grid[*next_pos] != '#')
+        .collect()
+}
 
-    let mut queue: Vec<(Pos, HashSet<Pos>)> = vec![(
-        Pos(1, 1),
-        HashSet::from([Pos(0, 1), Pos(1, 1), Pos(grid.len() - 1, grid[0].len() - 2)]),
-    )];
-    let mut all_paths = vec![];
-    while let Some((pos, visited)) = queue.pop() {
-        if pos.eq(&end) {
-            all_paths.push(visited.len());
-            continue;
-        }
+/// The map of the hiking trails
+#[derive(Debug, PartialEq)]
+pub struct Map(pub Trails);
 
-        queue.append(&mut pos.successors(&grid, &visited));
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_trails(input).map(Map)
+    }
+}
+
+/// Reads the trail map
+pub fn parse_trails(input: &str) -> Result<Trails, ParseError> {
+    let trails = Grid::parse(input, 0, |c| "#.<>^v".contains(c).then_some(c))?;
+    if trails.height() < 3 || trails.width() < 3 {
+        return Err(ParseError::TooSmall);
     }
 
-    return all_paths.iter().max().unwrap().to_owned();
+    Ok(trails)
+}
+
+fn opening(grid: &Trails, row: usize) -> Option<Point> {
+    let col = grid.row(row).iter().position(|tile| *tile == '.')?;
+    Some(Point::new(row, col))
+}
+
+/// Steps of the longest hike from the top row to the bottom row
+pub fn longest_route(grid: &Trails) -> Result<usize, SolveError> {
+    Ok(longest_hike(grid)?.1)
+}
+
+// Junctions passed on the longest hike, with its length
+fn longest_hike(grid: &Trails) -> Result<(Vec<Point>, usize), SolveError> {
+    let start = opening(grid, 0).ok_or(SolveError::NoRoute)?;
+    let end = opening(grid, grid.height() - 1).ok_or(SolveError::NoRoute)?;
+    let trails = compress(start, end, |pos| successors(*pos, grid));
+
+    trails.longest_path(&start, &end).ok_or(SolveError::NoRoute)
+}
+
+/// The map with the longest hike marked
+pub fn draw_hike(grid: &Trails, painter: &Painter) -> Result<String, SolveError> {
+    let (junctions, _) = longest_hike(grid)?;
+    let hike = unfold(&junctions, |pos| successors(*pos, grid));
+
+    Ok(painter.grid(grid, |point, tile| match tile {
+        '#' => ('#', Some(Color::Dim)),
+        _ if hike.contains(&point) => ('O', Some(Color::Yellow)),
+        _ => (*tile, None),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Span;
+
+    #[test]
+    fn it_finds_longest_route() {
+        let trails = parse_trails("#.###\n#...#\n#.#.#\n#...#\n###.#").unwrap();
+        assert_eq!(longest_route(&trails), Ok(6));
+        assert_eq!(
+            draw_hike(&trails, &Painter::new(false)),
+            Ok(String::from("#O###\n#O..#\n#O#.#\n#OOO#\n###O#"))
+        );
+        let uphill = parse_trails("#.#\n#^#\n#.#").unwrap();
+        assert_eq!(longest_route(&uphill), Err(SolveError::NoRoute));
+        assert_eq!(
+            longest_route(&parse_trails("#.###\n#.#.#\n###.#").unwrap()),
+            Err(SolveError::NoRoute)
+        );
+        assert_eq!(parse_trails("#.#\n#.#"), Err(ParseError::TooSmall));
+        assert_eq!(
+            parse_trails("#.#\n#?#\n#.#"),
+            Err(ParseError::Grid(GridError::UnknownTile(Span::new(
+                2, 2, "?"
+            ))))
+        );
+    }
 }
@@ -0,0 +1,40 @@
+//! Day 23: A Long Walk
+//!
+//! [`hike::parse_trails`] reads the [`hike::Trails`] and [`hike::longest_route`] finds
+//! the longest hike.
+
+use aoc_core::generate::InputRng;
+use aoc_core::visualize::Painter;
+use aoc_core::{Answer, Error, Part, Puzzle};
+
+mod generator;
+pub mod hike;
+
+pub struct Slopes;
+
+impl Puzzle for Slopes {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "slopes";
+
+    type Input = hike::Map;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(trails: &Self::Input) -> Result<Answer, Error> {
+        Ok(hike::longest_route(&trails.0)?.into())
+    }
+
+    fn visualize(
+        trails: &Self::Input,
+        part: Part,
+        painter: &Painter,
+    ) -> Option<Result<String, Error>> {
+        (part == Part::One).then(|| hike::draw_hike(&trails.0, painter).map_err(Error::from))
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -0,0 +1,83 @@
+use aoc_core::generate::{InputRng, Rng};
+
+const MAX_SPEED: i64 = 300;
+
+#[derive(Debug)]
+struct Stone {
+    position: [i64; 3],
+    velocity: [i64; 3],
+}
+
+fn velocity(rng: &mut InputRng) -> [i64; 3] {
+    [(); 3].map(|_| rng.gen_range(-MAX_SPEED..=MAX_SPEED))
+}
+
+// A rock thrown from somewhere around the test area and `size` hailstones,
+// each placed where the rock will hit it at its own time
+fn throw(rng: &mut InputRng, size: usize) -> (Stone, Vec<Stone>) {
+    let rock = Stone {
+        position: [(); 3].map(|_| rng.gen_range(200_000_000_000_000..=400_000_000_000_000)),
+        velocity: velocity(rng),
+    };
+    let hailstones = (0..size.max(1))
+        .map(|_| {
+            let time = rng.gen_range(100_000_000_000..=1_000_000_000_000_i64);
+            let velocity = loop {
+                let velocity = velocity(rng);
+                if velocity != rock.velocity {
+                    break velocity;
+                }
+            };
+            let position = [0, 1, 2]
+                .map(|axis| rock.position[axis] + time * (rock.velocity[axis] - velocity[axis]));
+            Stone { position, velocity }
+        })
+        .collect();
+
+    (rock, hailstones)
+}
+
+// `size` hailstones, every one of them hit by the same rock
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let (_, hailstones) = throw(rng, size);
+    hailstones
+        .iter()
+        .map(
+            |Stone {
+                 position: p,
+                 velocity: v,
+             }| {
+                format!(
+                    "{}, {}, {} @ {}, {}, {}",
+                    p[0], p[1], p[2], v[0], v[1], v[2]
+                )
+            },
+        )
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::generate::seeded;
+
+    #[test]
+    fn it_throws_the_rock_through_every_hailstone() {
+        let (rock, hailstones) = throw(&mut seeded(24), 50);
+        for stone in hailstones {
+            let axis = (0..3)
+                .find(|axis| stone.velocity[*axis] != rock.velocity[*axis])
+                .unwrap();
+            let time = (stone.position[axis] - rock.position[axis])
+                / (rock.velocity[axis] - stone.velocity[axis]);
+            assert!(time > 0);
+            for axis in 0..3 {
+                assert_eq!(
+                    stone.position[axis] + time * stone.velocity[axis],
+                    rock.position[axis] + time * rock.velocity[axis]
+                );
+            }
+        }
+    }
+}
@@ -1,38 +1,244 @@
-use geo::Line;
+use std::str::FromStr;
+
+use aoc_core::error::parse_at;
+use aoc_core::input::lines;
+#[cfg(feature = "render")]
+use aoc_core::render::{Color, Scene, Shape, Style};
+use aoc_core::Span;
 use itertools::Itertools;
-use line_intersection::LineInterval;
-
-pub fn count_intersections(input: &str, start: f64, end: f64) -> usize {
-    let lines: Vec<LineInterval<f64>> = input
-        .lines()
-        .map(|data| {
-            let (posititon, velocity) = data.split_once("@").expect("must have @");
-            let [px, py, _pz] = posititon
-                .split(',')
-                .map(|x| x.trim().parse::<f64>().unwrap())
-                .collect::<Vec<f64>>()[..] else { panic!("Can't parse posititon") };
-            let [vx, vy, _vz] = velocity
-                .split(',')
-                .map(|x| x.trim().parse::<f64>().unwrap())
-                .collect::<Vec<f64>>()[..] else { panic!("Can't parse velocity") };
-
-            LineInterval::ray(Line {
-                start: (px, py).into(),
-                end: (px + vx, py + vy).into(),
-            })
-        })
-        .collect();
-
-    lines
-        .iter()
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<px>, <py>, <pz> @ <vx>, <vy>, <vz>` {0}")]
+    MalformedHailstone(Span),
+    #[error("invalid number {0}")]
+    InvalidNumber(Span),
+}
+
+/// A hailstone, its position and velocity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    px: f64,
+    py: f64,
+    vx: f64,
+    vy: f64,
+}
+
+impl Ray {
+    fn intersection(&self, other: &Ray) -> Option<(f64, f64)> {
+        let det = self.vx * other.vy - self.vy * other.vx;
+        if det == 0.0 {
+            return None;
+        }
+
+        let dx = other.px - self.px;
+        let dy = other.py - self.py;
+        let t = (dx * other.vy - dy * other.vx) / det;
+        let u = (dx * self.vy - dy * self.vx) / det;
+        if t < 0.0 || u < 0.0 {
+            return None;
+        }
+
+        Some((self.px + t * self.vx, self.py + t * self.vy))
+    }
+
+    // Part of the ray's future path inside the square test area
+    // https://en.wikipedia.org/wiki/Liang%E2%80%93Barsky_algorithm
+    #[cfg(feature = "render")]
+    fn clip(&self, start: f64, end: f64) -> Option<((f64, f64), (f64, f64))> {
+        let (mut enter, mut exit) = (0.0, f64::INFINITY);
+        for (position, velocity) in [(self.px, self.vx), (self.py, self.vy)] {
+            if velocity == 0.0 {
+                if position < start || position > end {
+                    return None;
+                }
+                continue;
+            }
+            let (a, b) = ((start - position) / velocity, (end - position) / velocity);
+            enter = a.min(b).max(enter);
+            exit = a.max(b).min(exit);
+        }
+        if enter > exit {
+            return None;
+        }
+
+        let at = |t: f64| (self.px + t * self.vx, self.py + t * self.vy);
+        Some((at(enter), at(exit)))
+    }
+}
+
+/// Every hailstone of the input
+#[derive(Debug, PartialEq)]
+pub struct Hailstones(pub Vec<Ray>);
+
+impl FromStr for Hailstones {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        lines(input)
+            .enumerate()
+            .map(|(index, line)| parse_hailstone(index, line))
+            .collect::<Result<_, _>>()
+            .map(Hailstones)
+    }
+}
+
+/// Reads a `<px>, <py>, <pz> @ <vx>, <vy>, <vz>` line
+pub fn parse_hailstone(index: usize, data: &str) -> Result<Ray, ParseError> {
+    let malformed = |part| ParseError::MalformedHailstone(Span::at(index, data, part));
+    let (posititon, velocity) = data.split_once("@").ok_or_else(|| malformed(data))?;
+    let coordinates = |part: &str| {
+        part.split(',')
+            .map(|x| parse_at::<f64>(index, data, x).map_err(ParseError::InvalidNumber))
+            .collect::<Result<Vec<f64>, _>>()
+    };
+    let [px, py, _pz] = coordinates(posititon)?[..] else {
+        return Err(malformed(posititon));
+    };
+    let [vx, vy, _vz] = coordinates(velocity)?[..] else {
+        return Err(malformed(velocity));
+    };
+
+    Ok(Ray { px, py, vx, vy })
+}
+
+/// Pairs of hailstones whose paths cross inside the test area, ignoring `z`
+pub fn count_intersections(rays: &[Ray], start: f64, end: f64) -> usize {
+    rays.iter()
         .combinations(2)
-        .map(|pair| pair[0].relate(&pair[1]).unique_intersection())
-        .flatten()
-        .filter(|coord| {
-            coord.0.x >= start as f64
-                && coord.0.x <= end as f64
-                && coord.0.y >= start as f64
-                && coord.0.y <= end as f64
-        })
+        .filter_map(|pair| pair[0].intersection(pair[1]))
+        .filter(|coord| coord.0 >= start && coord.0 <= end && coord.1 >= start && coord.1 <= end)
         .count()
 }
+
+/// Hailstone paths through the test area, with the crossings inside it
+#[cfg(feature = "render")]
+pub fn hail_scene(rays: &[Ray], start: f64, end: f64) -> Scene {
+    let mut scene = Scene::new(Color::WHITE);
+    scene.add(
+        Shape::Rect((start, start), (end, end)),
+        Style::stroke(Color::BLACK),
+    );
+    for ray in rays {
+        if let Some((from, to)) = ray.clip(start, end) {
+            scene.add(Shape::Line(from, to), Style::stroke(Color(120, 150, 200)));
+        }
+    }
+    rays.iter()
+        .combinations(2)
+        .filter_map(|pair| pair[0].intersection(pair[1]))
+        .filter(|coord| coord.0 >= start && coord.0 <= end && coord.1 >= start && coord.1 <= end)
+        .for_each(|coord| scene.add(Shape::Dot(coord), Style::fill(Color(200, 40, 40))));
+    scene
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rays(input: &str) -> Vec<Ray> {
+        input
+            .split("\n")
+            .enumerate()
+            .map(|(index, line)| parse_hailstone(index, line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn it_reports_malformed_hailstones() {
+        assert_eq!(
+            parse_hailstone(0, "19, 13, 30 -2, 1, -2"),
+            Err(ParseError::MalformedHailstone(Span::new(
+                1,
+                1,
+                "19, 13, 30 -2, 1, -2"
+            )))
+        );
+        assert_eq!(
+            parse_hailstone(1, "19, 13 @ -2, 1, -2"),
+            Err(ParseError::MalformedHailstone(Span::new(2, 1, "19, 13 ")))
+        );
+        assert_eq!(
+            parse_hailstone(0, "19, 1x, 30 @ -2, 1, -2"),
+            Err(ParseError::InvalidNumber(Span::new(1, 5, "1x")))
+        );
+    }
+
+    #[test]
+    fn it_intersects_rays() {
+        let a = Ray {
+            px: 19.0,
+            py: 13.0,
+            vx: -2.0,
+            vy: 1.0,
+        };
+        let b = Ray {
+            px: 18.0,
+            py: 19.0,
+            vx: -1.0,
+            vy: -1.0,
+        };
+        let (x, y) = a.intersection(&b).unwrap();
+        assert!((x - 14.333).abs() < 0.001);
+        assert!((y - 15.333).abs() < 0.001);
+    }
+
+    #[test]
+    fn it_ignores_past_and_parallel() {
+        let a = Ray {
+            px: 19.0,
+            py: 13.0,
+            vx: -2.0,
+            vy: 1.0,
+        };
+        let past = Ray {
+            px: 20.0,
+            py: 19.0,
+            vx: 1.0,
+            vy: -5.0,
+        };
+        let parallel = Ray {
+            px: 18.0,
+            py: 19.0,
+            vx: -2.0,
+            vy: 1.0,
+        };
+        assert_eq!(a.intersection(&past), None);
+        assert_eq!(a.intersection(&parallel), None);
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn it_clips_rays_to_the_area() {
+        let ray = |px, vx| Ray {
+            px,
+            py: 13.0,
+            vx,
+            vy: 1.0,
+        };
+        assert_eq!(
+            ray(19.0, -2.0).clip(7.0, 27.0),
+            Some(((19.0, 13.0), (7.0, 19.0)))
+        );
+        assert_eq!(ray(30.0, 1.0).clip(7.0, 27.0), None);
+    }
+
+    #[test]
+    fn it_counts_sample_intersections() {
+        assert_eq!(
+            count_intersections(
+                &rays(
+                    "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3"
+                ),
+                7.0,
+                27.0
+            ),
+            2
+        );
+    }
+}
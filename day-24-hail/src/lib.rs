@@ -0,0 +1,52 @@
+//! Day 24: Never Tell Me The Odds
+//!
+//! [`intersections::parse_hailstone`] reads a [`intersections::Ray`] and
+//! [`intersections::count_intersections`] crosses their paths.
+
+use aoc_core::generate::InputRng;
+#[cfg(feature = "render")]
+use aoc_core::render::Scene;
+#[cfg(feature = "render")]
+use aoc_core::Part;
+use aoc_core::{Answer, Error, Puzzle};
+
+mod generator;
+pub mod intersections;
+
+const TEST_AREA_START: f64 = 200000000000000.0;
+const TEST_AREA_END: f64 = 400000000000000.0;
+
+pub struct Hail;
+
+impl Puzzle for Hail {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "hail";
+
+    type Input = intersections::Hailstones;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(hailstones: &Self::Input) -> Result<Answer, Error> {
+        Ok(
+            intersections::count_intersections(&hailstones.0, TEST_AREA_START, TEST_AREA_END)
+                .into(),
+        )
+    }
+
+    #[cfg(feature = "render")]
+    fn render(hailstones: &Self::Input, part: Part) -> Option<Result<Scene, Error>> {
+        (part == Part::One).then(|| {
+            Ok(intersections::hail_scene(
+                &hailstones.0,
+                TEST_AREA_START,
+                TEST_AREA_END,
+            ))
+        })
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
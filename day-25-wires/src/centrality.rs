@@ -1,27 +1,15 @@
 use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 
 use itertools::Itertools;
 
-use graphrs::algorithms::centrality::betweenness::betweenness_centrality;
-use graphrs::{Graph, GraphSpecs};
-
-pub fn central_edges_variants(input: &str) -> Vec<Vec<(&str, &str)>> {
-    let mut graph: Graph<&str, ()> = Graph::new(GraphSpecs::undirected_create_missing());
-    let edges = input
-        .lines()
-        .map(|line| {
-            let (start, end) = line.split_once(":").unwrap();
-            end.split_whitespace()
-                .map(|i| (start, i.trim()))
-                .collect::<Vec<(&str, &str)>>()
-        })
-        .flatten()
-        .collect::<Vec<(&str, &str)>>();
-    for (u, v) in edges.iter() {
-        let _ = graph.add_edge_tuple(u, v);
-    }
+use petgraph::graphmap::UnGraphMap;
+
+/// Sets of three edges worth cutting, the most used ones by shortest paths first
+pub fn central_edges_variants<'a>(edges: &[(&'a str, &'a str)]) -> Vec<Vec<(&'a str, &'a str)>> {
+    let graph: UnGraphMap<&str, ()> = UnGraphMap::from_edges(edges.iter());
 
-    let betweenness = betweenness_centrality(&graph, false, true).unwrap();
+    let betweenness = betweenness_centrality(&graph);
     let mut sorted: Vec<(&&str, &f64)> = betweenness.iter().collect();
     sorted.sort_by(|a, b| {
         if a.1.gt(b.1) {
@@ -42,7 +30,7 @@ pub fn central_edges_variants(input: &str) -> Vec<Vec<(&str, &str)>> {
         .filter(|edge| edges.contains(&(edge[0], edge[1])) || edges.contains(&(edge[1], edge[0])))
         .collect_vec();
 
-    return target_edges
+    target_edges
         .iter()
         .combinations(3)
         .map(|v| {
@@ -50,5 +38,61 @@ pub fn central_edges_variants(input: &str) -> Vec<Vec<(&str, &str)>> {
                 .map(|x| (*x[0], *x[1]))
                 .collect::<Vec<(&str, &str)>>()
         })
-        .collect::<Vec<Vec<(&str, &str)>>>();
+        .collect::<Vec<Vec<(&str, &str)>>>()
+}
+
+// https://en.wikipedia.org/wiki/Betweenness_centrality (Brandes' algorithm)
+fn betweenness_centrality<'a>(graph: &UnGraphMap<&'a str, ()>) -> HashMap<&'a str, f64> {
+    let mut centrality: HashMap<&str, f64> = graph.nodes().map(|node| (node, 0.0)).collect();
+    for source in graph.nodes() {
+        let mut stack: Vec<&str> = vec![];
+        let mut predecessors: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut paths: HashMap<&str, f64> = HashMap::from([(source, 1.0)]);
+        let mut distance: HashMap<&str, usize> = HashMap::from([(source, 0)]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            stack.push(node);
+            let node_distance = distance[node];
+            let node_paths = paths[node];
+            for next in graph.neighbors(node) {
+                if !distance.contains_key(next) {
+                    distance.insert(next, node_distance + 1);
+                    queue.push_back(next);
+                }
+                if distance[next] == node_distance + 1 {
+                    *paths.entry(next).or_insert(0.0) += node_paths;
+                    predecessors.entry(next).or_default().push(node);
+                }
+            }
+        }
+
+        let mut dependency: HashMap<&str, f64> = HashMap::new();
+        while let Some(node) = stack.pop() {
+            let node_dependency = dependency.get(node).copied().unwrap_or(0.0);
+            for previous in predecessors.get(node).into_iter().flatten() {
+                *dependency.entry(previous).or_insert(0.0) +=
+                    paths[previous] / paths[node] * (1.0 + node_dependency);
+            }
+            if node != source {
+                *centrality.get_mut(node).unwrap() += node_dependency;
+            }
+        }
+    }
+
+    centrality
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_finds_central_nodes() {
+        let graph: UnGraphMap<&str, ()> =
+            UnGraphMap::from_edges([("a", "b"), ("b", "c"), ("c", "d")].iter());
+        let centrality = betweenness_centrality(&graph);
+        assert_eq!(centrality["a"], 0.0);
+        assert_eq!(centrality["b"], 4.0);
+        assert_eq!(centrality["c"], 4.0);
+    }
 }
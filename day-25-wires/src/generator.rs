@@ -0,0 +1,72 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use aoc_core::generate::{word, InputRng, Rng, SliceRandom};
+
+const EXTRA_WIRES: usize = 5;
+
+// Wires are unordered, so both ends are kept sorted
+fn wire(from: &str, to: &str) -> (String, String) {
+    (from.min(to).to_string(), from.max(to).to_string())
+}
+
+// Components of a random connected group of `size`, every one wired to its
+// neighbour in a shuffled chain and to a few others at random
+fn group(
+    rng: &mut InputRng,
+    size: usize,
+    taken: &mut HashSet<String>,
+    wires: &mut BTreeSet<(String, String)>,
+) -> Vec<String> {
+    let mut components = vec![];
+    while components.len() < size {
+        let name = word(rng, 3);
+        if taken.insert(name.clone()) {
+            components.push(name);
+        }
+    }
+    for pair in components.windows(2) {
+        wires.insert(wire(&pair[0], &pair[1]));
+    }
+    for component in &components {
+        for other in components.choose_multiple(rng, EXTRA_WIRES) {
+            if other != component {
+                wires.insert(wire(component, other));
+            }
+        }
+    }
+    components
+}
+
+// Groups of `size` and up to twice as many components, at least four,
+// joined by three wires between distinct components
+pub fn generate(rng: &mut InputRng, size: usize) -> String {
+    let size = size.max(4);
+    let mut taken = HashSet::new();
+    let mut wires = BTreeSet::new();
+    let left = group(rng, size, &mut taken, &mut wires);
+    let other = rng.gen_range(size..=size * 2);
+    let right = group(rng, other, &mut taken, &mut wires);
+    let cut = left
+        .choose_multiple(rng, 3)
+        .zip(right.choose_multiple(rng, 3));
+    for (from, to) in cut {
+        wires.insert(wire(from, to));
+    }
+
+    // a wire is listed once, under either of its ends
+    let mut connections: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (from, to) in wires {
+        let (from, to) = match rng.gen_bool(0.5) {
+            true => (from, to),
+            false => (to, from),
+        };
+        connections.entry(from).or_default().push(to);
+    }
+    let mut lines: Vec<String> = connections
+        .into_iter()
+        .map(|(component, others)| format!("{component}: {}", others.join(" ")))
+        .collect();
+    lines.shuffle(rng);
+
+    lines.join("\n")
+}
@@ -0,0 +1,32 @@
+//! Day 25: Snowverload
+//!
+//! [`wires::parse_edges`] reads the [`wires::Edges`] and [`wires::count_groups`] cuts
+//! them in two.
+
+use aoc_core::generate::InputRng;
+use aoc_core::{Answer, Error, Puzzle};
+
+pub mod centrality;
+mod generator;
+pub mod wires;
+
+pub struct Wires;
+
+impl Puzzle for Wires {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "wires";
+
+    type Input = wires::Diagram;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part_one(edges: &Self::Input) -> Result<Answer, Error> {
+        Ok(wires::count_groups(&edges.0)?.into())
+    }
+
+    fn generate(rng: &mut InputRng, size: usize) -> Option<String> {
+        Some(generator::generate(rng, size))
+    }
+}
@@ -1,16 +1,7 @@
-use std::env;
-use std::fs;
+use std::process::ExitCode;
 
-mod centrality;
-mod wires;
+use day_25_wires::Wires;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let file_path = &args[1];
-
-    println!("In file {}", file_path);
-
-    let contents = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let sum = wires::count_groups(&contents);
-    println!("Sum:\n{sum}");
+fn main() -> ExitCode {
+    aoc_core::run(&Wires)
 }
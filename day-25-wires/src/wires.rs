@@ -1,30 +1,100 @@
+use std::str::FromStr;
+
+use aoc_core::input::lines;
+use aoc_core::Span;
 use petgraph::graphmap::UnGraphMap;
+use thiserror::Error;
 
 use crate::centrality::central_edges_variants;
 
-pub fn count_groups(input: &str) -> usize {
-    let edges = input
-        .lines()
-        .map(|line| {
-            let (start, end) = line.split_once(":").unwrap();
-            end.split_whitespace()
-                .map(|i| (start, i.trim()))
-                .collect::<Vec<(&str, &str)>>()
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `<component>: <connected components>` {0}")]
+    MalformedConnection(Span),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolveError {
+    #[error("no three wires split the components into two groups")]
+    CutNotFound,
+}
+
+/// Wires between components
+pub type Edges = Vec<(String, String)>;
+
+/// The wiring diagram
+#[derive(Debug, PartialEq)]
+pub struct Diagram(pub Edges);
+
+impl FromStr for Diagram {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_edges(input).map(Diagram)
+    }
+}
+
+/// Reads the `<component>: <components>` lines
+pub fn parse_edges(input: &str) -> Result<Edges, ParseError> {
+    lines(input)
+        .enumerate()
+        .map(|(index, line)| {
+            let (start, end) = line
+                .split_once(":")
+                .filter(|(start, end)| !start.is_empty() && !end.trim().is_empty())
+                .ok_or_else(|| ParseError::MalformedConnection(Span::line(index, line)))?;
+            Ok(end
+                .split_whitespace()
+                .map(|i| (start.to_string(), i.trim().to_string()))
+                .collect::<Vec<(String, String)>>())
         })
-        .flatten()
+        .collect::<Result<Vec<_>, _>>()
+        .map(|edges| edges.into_iter().flatten().collect())
+}
+
+/// Product of the sizes of the two groups left after cutting three wires
+pub fn count_groups(edges: &Edges) -> Result<usize, SolveError> {
+    let edges = edges
+        .iter()
+        .map(|(start, end)| (start.as_str(), end.as_str()))
         .collect::<Vec<(&str, &str)>>();
 
     let mut graph: UnGraphMap<&str, ()> = UnGraphMap::from_edges(edges.iter());
 
-    for target_edges in central_edges_variants(input) {
-        for x in target_edges {
+    for target_edges in central_edges_variants(&edges) {
+        for x in &target_edges {
             graph.remove_edge(x.0, x.1);
         }
         let sub = petgraph::algo::kosaraju_scc(&graph);
         if sub.len().eq(&2) {
-            return sub[0].len() * sub[1].len();
+            return Ok(sub[0].len() * sub[1].len());
+        }
+        // put the wires back before trying the next three
+        for x in target_edges {
+            graph.add_edge(x.0, x.1, ());
         }
     }
 
-    panic!("Not found");
+    Err(SolveError::CutNotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_malformed_connections() {
+        assert_eq!(
+            parse_edges("jqt: rhn xhk\nrsh frs"),
+            Err(ParseError::MalformedConnection(Span::new(2, 1, "rsh frs")))
+        );
+        assert_eq!(
+            parse_edges("jqt: rhn\n:"),
+            Err(ParseError::MalformedConnection(Span::new(2, 1, ":")))
+        );
+        assert_eq!(
+            count_groups(&parse_edges("a: b").unwrap()),
+            Err(SolveError::CutNotFound)
+        );
+    }
 }
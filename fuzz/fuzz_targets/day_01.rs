@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_core::Puzzle;
+use day_01_trebuchet::Trebuchet;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Trebuchet::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_core::Puzzle;
+use day_02_cube_conundrum::CubeConundrum;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = CubeConundrum::parse(input);
+});
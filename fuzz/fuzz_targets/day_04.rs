@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_core::Puzzle;
+use day_04_scratchcards::Scratchcards;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Scratchcards::parse(input);
+});